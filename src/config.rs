@@ -1,5 +1,6 @@
-//! Config directory for Lectern. On Linux uses XDG Base Directory:
-//! `$XDG_CONFIG_HOME/lectern` or `~/.config/lectern`.
+//! Config and cache directories for Lectern. On Linux uses XDG Base Directory:
+//! `$XDG_CONFIG_HOME/lectern` or `~/.config/lectern`, and `$XDG_CACHE_HOME/lectern`
+//! or `~/.cache/lectern`.
 
 use std::path::PathBuf;
 
@@ -22,3 +23,10 @@ pub fn config_file(name: &str) -> Option<PathBuf> {
     }
     Some(path)
 }
+
+/// Returns the Lectern cache directory. On Linux this follows XDG:
+/// `$XDG_CACHE_HOME/lectern` or `~/.cache/lectern`. Returns `None` if the
+/// base cache dir cannot be determined.
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|p| p.join(APP_CONFIG_DIR))
+}
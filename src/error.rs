@@ -0,0 +1,92 @@
+//! Error type shared by the services layer (providers, ffmpeg/ffprobe, conversion, chapter files).
+//! Variants carry enough context for handlers to react differently (e.g. offer to install ffmpeg,
+//! retry a provider) instead of only showing text.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub type Result<T, E = LecternError> = std::result::Result<T, E>;
+
+#[derive(Debug, Clone)]
+pub enum LecternError {
+    /// ffmpeg/ffprobe (named here) could not be started; usually not installed or not in PATH.
+    FfmpegMissing(&'static str),
+    /// ffprobe ran but failed or produced unusable output for `path`.
+    Ffprobe { path: String, message: String },
+    /// ffmpeg ran but exited with an error (stderr included when available).
+    Ffmpeg { exit_code: Option<i32>, stderr: String },
+    /// A provider has no entry for the request (no such book / no results). A normal answer.
+    NotFound(String),
+    /// A provider could not answer: offline, network error, throttled or server error.
+    ProviderUnavailable(String),
+    /// A provider answered with an unexpected HTTP status.
+    ProviderStatus { host: String, status: u16 },
+    /// Malformed input: provider response, chapter file, timestamp, ...
+    Parse(String),
+    /// File system error on `path`.
+    Io { path: PathBuf, message: String },
+    /// The operation was stopped before it finished (user cancel or killed process).
+    Cancelled,
+    /// Anything else (bad input path, unsupported file type, ...).
+    Other(String),
+}
+
+impl LecternError {
+    pub fn io(path: impl AsRef<Path>, err: std::io::Error) -> Self {
+        LecternError::Io {
+            path: path.as_ref().to_path_buf(),
+            message: err.to_string(),
+        }
+    }
+
+    /// Error for a failed spawn of `tool`: NotFound means the binary is missing.
+    pub fn spawn(tool: &'static str, err: std::io::Error) -> Self {
+        if err.kind() == std::io::ErrorKind::NotFound {
+            LecternError::FfmpegMissing(tool)
+        } else {
+            LecternError::Other(format!("Failed to execute {}: {}", tool, err))
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, LecternError::NotFound(_))
+    }
+
+    pub fn is_ffmpeg_missing(&self) -> bool {
+        matches!(self, LecternError::FfmpegMissing(_))
+    }
+
+    /// Worth offering a retry: the provider may answer next time.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            LecternError::ProviderUnavailable(_) => true,
+            LecternError::ProviderStatus { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for LecternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LecternError::FfmpegMissing(tool) => write!(f, "{} not found in PATH. Please install FFmpeg.", tool),
+            LecternError::Ffprobe { path, message } => write!(f, "ffprobe failed on {}: {}", path, message),
+            LecternError::Ffmpeg { exit_code, stderr } => {
+                write!(f, "FFmpeg conversion failed with exit code: {:?}", exit_code)?;
+                if !stderr.is_empty() {
+                    write!(f, "\nStderr:\n{}", stderr)?;
+                }
+                Ok(())
+            }
+            LecternError::NotFound(msg) => write!(f, "{}", msg),
+            LecternError::ProviderUnavailable(msg) => write!(f, "Provider unavailable: {}", msg),
+            LecternError::ProviderStatus { host, status } => write!(f, "{} returned unexpected status {}", host, status),
+            LecternError::Parse(msg) => write!(f, "{}", msg),
+            LecternError::Io { path, message, .. } => write!(f, "{}: {}", path.display(), message),
+            LecternError::Cancelled => write!(f, "Cancelled"),
+            LecternError::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for LecternError {}
@@ -1,4 +1,5 @@
 mod config;
+mod error;
 mod models;
 mod services;
 mod ui;
@@ -7,12 +8,68 @@ mod utils;
 use ui::Lectern;
 use iced::{Application, Settings, window};
 
+const USAGE: &str = "Usage: lectern [--preset <name>] [--list-presets]
+
+Options:
+  --preset <name>   Select a conversion preset (built-in or from conversion_presets.json)
+  --list-presets    Print the available conversion presets and exit
+  -h, --help        Print this help and exit";
+
+/// Command line options passed to the UI on startup.
+#[derive(Debug, Clone, Default)]
+pub struct CliOptions {
+    pub preset: Option<String>,
+}
+
+/// Minimal argument parsing; exits on --help, --list-presets and invalid arguments.
+fn parse_args() -> CliOptions {
+    let mut options = CliOptions::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--preset" => match args.next() {
+                Some(name) => options.preset = Some(name),
+                None => {
+                    eprintln!("--preset needs a name\n\n{}", USAGE);
+                    std::process::exit(2);
+                }
+            },
+            "--list-presets" => {
+                for preset in ui::preset_settings::load() {
+                    println!("{}", preset.name);
+                }
+                std::process::exit(0);
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            other => match other.strip_prefix("--preset=") {
+                Some(name) => options.preset = Some(name.to_string()),
+                None => {
+                    eprintln!("Unknown argument: {}\n\n{}", other, USAGE);
+                    std::process::exit(2);
+                }
+            },
+        }
+    }
+    if let Some(ref name) = options.preset {
+        if ui::preset_settings::find(&ui::preset_settings::load(), name).is_none() {
+            eprintln!("Unknown preset '{}'; run with --list-presets to see the available ones\n\n{}", name, USAGE);
+            std::process::exit(2);
+        }
+    }
+    options
+}
+
 pub fn main() -> iced::Result {
+    let options = parse_args();
     Lectern::run(Settings {
         window: window::Settings {
             size: iced::Size::new(900.0, 700.0),
             ..Default::default()
         },
+        flags: options,
         ..Default::default()
     })
 }
@@ -9,6 +9,27 @@ pub struct Chapter {
     pub is_locked: bool,
 }
 
+/// Chapters from a provider lookup. Audnexus also reports the length of Audible's
+/// "This is Audible" brand intro/outro clips (0 when unknown).
+#[derive(Debug, Clone, Default)]
+pub struct ChapterLookup {
+    pub chapters: Vec<Chapter>,
+    pub brand_intro_ms: u64,
+    pub brand_outro_ms: u64,
+}
+
+/// One Audible edition found by title/author, offered when the book has no ASIN so the user
+/// can pick the edition whose chapters to fetch.
+#[derive(Debug, Clone)]
+pub struct ChapterEdition {
+    pub asin: String,
+    pub title: String,
+    pub authors: String,
+    pub narrators: String,
+    pub runtime_ms: Option<u64>,
+    pub release_date: Option<String>,
+}
+
 impl Chapter {
     pub fn new(title: String, start_time: u64, duration: u64) -> Self {
         Self {
@@ -1,48 +1,289 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// One series the book belongs to, with its position in that series ("3", "2.5", ...).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeriesEntry {
+    pub name: String,
+    #[serde(default)]
+    pub number: Option<String>,
+}
+
+impl SeriesEntry {
+    pub fn new(name: impl Into<String>, number: Option<String>) -> Self {
+        let number = number.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        Self { name: name.into().trim().to_string(), number }
+    }
+}
+
+impl std::fmt::Display for SeriesEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.number {
+            Some(ref number) => write!(f, "{} #{}", self.name, number),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "BookMetadataRepr")]
 pub struct BookMetadata {
     pub title: String,
     pub subtitle: Option<String>,
-    pub author: String,
+    pub authors: Vec<String>,
     pub isbn: Option<String>,
     pub asin: Option<String>,
     pub description: Option<String>,
     pub cover_url: Option<String>,
     pub duration: Option<String>,
-    pub narrator: Option<String>,
+    pub narrators: Vec<String>,
     pub publisher: Option<String>,
     pub publish_year: Option<String>,
-    pub series: Option<String>,
-    pub series_number: Option<String>,
-    pub genre: Option<String>,
-    pub tags: Option<String>, // Comma-separated tags
+    pub series: Vec<SeriesEntry>,
+    pub genres: Vec<String>,
+    pub tags: Vec<String>,
     pub language: Option<String>,
     pub explicit: Option<bool>,
     pub abridged: Option<bool>,
 }
 
-impl Default for BookMetadata {
-    fn default() -> Self {
+impl BookMetadata {
+    /// Authors joined for display, search queries and single-value tags.
+    pub fn author_display(&self) -> String {
+        self.authors.join(", ")
+    }
+
+    pub fn narrator_display(&self) -> Option<String> {
+        join_names(&self.narrators)
+    }
+
+    pub fn genre_display(&self) -> Option<String> {
+        join_names(&self.genres)
+    }
+
+    pub fn tags_display(&self) -> Option<String> {
+        join_names(&self.tags)
+    }
+
+    /// The first series; used where only one fits (templates, MP4 movement).
+    pub fn primary_series(&self) -> Option<&SeriesEntry> {
+        self.series.first()
+    }
+
+    pub fn series_name(&self) -> Option<String> {
+        self.primary_series().map(|s| s.name.clone())
+    }
+
+    pub fn series_number(&self) -> Option<String> {
+        self.primary_series().and_then(|s| s.number.clone())
+    }
+}
+
+/// `None` for an empty list, otherwise the entries joined with ", ".
+pub fn join_names(names: &[String]) -> Option<String> {
+    (!names.is_empty()).then(|| names.join(", "))
+}
+
+/// Split a provider or legacy single-string list of people ("A, B & C") into names.
+/// A "Last, First" name (one comma, one word before it: "King, Stephen") is turned around
+/// instead of split, also between `;` / `&` ("King, Stephen; Straub, Peter"). Multi-word
+/// surnames ("Le Guin, Ursula K.") still split in two, and two one-word names ("Homer, Virgil")
+/// read as one inverted name.
+pub fn split_names(value: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for group in value.split([';', '&']) {
+        let inverted = group.split_once(',').and_then(|(last, first)| {
+            let (last, first) = (last.trim(), first.trim());
+            let single = !first.is_empty() && !first.contains(',') && !last.is_empty() && !last.contains(char::is_whitespace);
+            single.then(|| format!("{} {}", first, last))
+        });
+        let group_names = match inverted {
+            Some(name) => vec![name],
+            None => split_on(group, &[',']),
+        };
+        for name in group_names {
+            if !names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Split a comma-separated list (genres, tags) into entries.
+pub fn split_list(value: &str) -> Vec<String> {
+    split_on(value, &[',', ';'])
+}
+
+fn split_on(value: &str, separators: &[char]) -> Vec<String> {
+    let mut entries: Vec<String> = Vec::new();
+    for entry in value.split(separators).map(str::trim).filter(|e| !e.is_empty()) {
+        if !entries.iter().any(|e| e.eq_ignore_ascii_case(entry)) {
+            entries.push(entry.to_string());
+        }
+    }
+    entries
+}
+
+/// A list field saved before lists existed: one (comma-separated) string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrList {
+    String(String),
+    List(Vec<String>),
+}
+
+impl StringOrList {
+    fn into_names(self) -> Vec<String> {
+        match self {
+            StringOrList::String(s) => split_names(&s),
+            StringOrList::List(list) => list,
+        }
+    }
+
+    fn into_list(self) -> Vec<String> {
+        match self {
+            StringOrList::String(s) => split_list(&s),
+            StringOrList::List(list) => list,
+        }
+    }
+}
+
+/// `series` was a name with a separate `series_number`; now a list of entries.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SeriesRepr {
+    Name(String),
+    Entries(Vec<SeriesEntry>),
+}
+
+/// Accepts both the current layout and JSON saved before the list fields
+/// (`author`, `narrator`, `series` + `series_number`, `genre`, comma-separated `tags`).
+#[derive(Deserialize)]
+struct BookMetadataRepr {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    subtitle: Option<String>,
+    #[serde(default)]
+    authors: Option<Vec<String>>,
+    #[serde(default)]
+    author: Option<StringOrList>,
+    #[serde(default)]
+    isbn: Option<String>,
+    #[serde(default)]
+    asin: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    cover_url: Option<String>,
+    #[serde(default)]
+    duration: Option<String>,
+    #[serde(default)]
+    narrators: Option<Vec<String>>,
+    #[serde(default)]
+    narrator: Option<StringOrList>,
+    #[serde(default)]
+    publisher: Option<String>,
+    #[serde(default)]
+    publish_year: Option<String>,
+    #[serde(default)]
+    series: Option<SeriesRepr>,
+    #[serde(default)]
+    series_number: Option<String>,
+    #[serde(default)]
+    genres: Option<Vec<String>>,
+    #[serde(default)]
+    genre: Option<StringOrList>,
+    #[serde(default)]
+    tags: Option<StringOrList>,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    explicit: Option<bool>,
+    #[serde(default)]
+    abridged: Option<bool>,
+}
+
+impl From<BookMetadataRepr> for BookMetadata {
+    fn from(r: BookMetadataRepr) -> Self {
+        let series = match r.series {
+            Some(SeriesRepr::Entries(entries)) => entries,
+            Some(SeriesRepr::Name(name)) if !name.trim().is_empty() => vec![SeriesEntry::new(name, r.series_number)],
+            _ => Vec::new(),
+        };
         BookMetadata {
-            title: String::new(),
-            subtitle: None,
-            author: String::new(),
-            isbn: None,
-            asin: None,
-            description: None,
-            cover_url: None,
-            duration: None,
-            narrator: None,
-            publisher: None,
-            publish_year: None,
-            series: None,
-            series_number: None,
-            genre: None,
-            tags: None,
-            language: None,
-            explicit: None,
-            abridged: None,
+            title: r.title,
+            subtitle: r.subtitle,
+            authors: r.authors.or_else(|| r.author.map(StringOrList::into_names)).unwrap_or_default(),
+            isbn: r.isbn,
+            asin: r.asin,
+            description: r.description,
+            cover_url: r.cover_url,
+            duration: r.duration,
+            narrators: r.narrators.or_else(|| r.narrator.map(StringOrList::into_names)).unwrap_or_default(),
+            publisher: r.publisher,
+            publish_year: r.publish_year,
+            series,
+            genres: r.genres.or_else(|| r.genre.map(StringOrList::into_list)).unwrap_or_default(),
+            tags: r.tags.map(StringOrList::into_list).unwrap_or_default(),
+            language: r.language,
+            explicit: r.explicit,
+            abridged: r.abridged,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_single_string_fields_become_lists() {
+        let json = r#"{
+            "title": "The Book",
+            "author": "Ann Author & Bob Author",
+            "narrator": "Nora Narrator, Ned Narrator",
+            "genre": "Fantasy; Adventure",
+            "series": "Saga",
+            "series_number": "2.5",
+            "tags": "dragons, quests, Dragons",
+            "explicit": true
+        }"#;
+        let book: BookMetadata = serde_json::from_str(json).expect("legacy json");
+        assert_eq!(book.title, "The Book");
+        assert_eq!(book.authors, vec!["Ann Author", "Bob Author"]);
+        assert_eq!(book.narrators, vec!["Nora Narrator", "Ned Narrator"]);
+        assert_eq!(book.genres, vec!["Fantasy", "Adventure"]);
+        assert_eq!(book.series, vec![SeriesEntry::new("Saga", Some("2.5".to_string()))]);
+        assert_eq!(book.tags, vec!["dragons", "quests"]);
+        assert_eq!(book.explicit, Some(true));
+
+        // Saved again, it uses the list fields and reads back the same
+        let saved = serde_json::to_value(&book).expect("serializable");
+        assert_eq!(saved["authors"], serde_json::json!(["Ann Author", "Bob Author"]));
+        assert_eq!(saved["series"], serde_json::json!([{"name": "Saga", "number": "2.5"}]));
+        assert!(saved.get("author").is_none() && saved.get("series_number").is_none());
+        let reloaded: BookMetadata = serde_json::from_value(saved.clone()).expect("current json");
+        assert_eq!(serde_json::to_value(&reloaded).expect("serializable"), saved);
+    }
+
+    #[test]
+    fn series_without_number_and_empty_series() {
+        let book: BookMetadata = serde_json::from_str(r#"{"title": "A", "series": "Saga"}"#).expect("json");
+        assert_eq!(book.series, vec![SeriesEntry::new("Saga", None)]);
+        let book: BookMetadata = serde_json::from_str(r#"{"title": "A", "series": " ", "series_number": "1"}"#).expect("json");
+        assert!(book.series.is_empty());
+    }
+
+    #[test]
+    fn last_first_names_are_not_split() {
+        assert_eq!(split_names("King, Stephen"), vec!["Stephen King"]);
+        assert_eq!(split_names("Tolkien, J. R. R."), vec!["J. R. R. Tolkien"]);
+        assert_eq!(split_names("Stephen King, Peter Straub"), vec!["Stephen King", "Peter Straub"]);
+        assert_eq!(split_names("King, Stephen; Straub, Peter"), vec!["Stephen King", "Peter Straub"]);
+        assert_eq!(split_names("King, Stephen & Ann Author"), vec!["Stephen King", "Ann Author"]);
+        assert_eq!(split_names("Ann Author & Bob Author"), vec!["Ann Author", "Bob Author"]);
+        // Documented limitation: a multi-word surname is read as two people
+        assert_eq!(split_names("Le Guin, Ursula K."), vec!["Le Guin", "Ursula K."]);
+    }
+}
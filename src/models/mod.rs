@@ -1,5 +1,5 @@
 pub mod metadata;
 pub mod chapters;
 
-pub use metadata::BookMetadata;
-pub use chapters::Chapter;
+pub use metadata::{BookMetadata, SeriesEntry};
+pub use chapters::{Chapter, ChapterEdition, ChapterLookup};
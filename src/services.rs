@@ -1,13 +1,41 @@
 use tokio;
-use anyhow::Result;
+use crate::error::{LecternError, Result};
 use futures::future::join_all;
 
 pub mod ffprobe;
 pub mod playback;
 pub mod conversion;
+pub mod cover_edit;
+pub mod embedded_cover;
+pub mod matching;
+pub mod http_cache;
+pub mod input_check;
+pub mod abs_sidecar;
+pub mod metadata_import;
+pub mod tags;
+pub mod mp4_tags;
+pub mod verify;
+
+use matching::{tag_results, ProviderResult};
 
 // Define the BookMetadata structure
-pub use crate::models::BookMetadata;
+pub use crate::models::{BookMetadata, SeriesEntry};
+
+/// Audible marketplace domain suffix for a region code ("us", "uk", "jp", ...).
+pub fn audible_tld(region: &str) -> &'static str {
+    match region {
+        "ca" => ".ca",
+        "uk" => ".co.uk",
+        "au" => ".com.au",
+        "fr" => ".fr",
+        "de" => ".de",
+        "jp" => ".co.jp",
+        "it" => ".it",
+        "in" => ".in",
+        "es" => ".es",
+        _ => ".com", // default to US
+    }
+}
 
 // Define the AudioService struct
 pub struct AudioService;
@@ -20,10 +48,155 @@ fn meta_http_client() -> reqwest::Client {
         .unwrap_or_else(|_| reqwest::Client::new())
 }
 
+// Shared request layer: every provider and cover request goes through http_get so that caching,
+// per-host rate limits and retries apply everywhere.
+const PROVIDER_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(6);
+const MAX_RETRIES: u32 = 3;
+const BASE_BACKOFF_MS: u64 = 500;
+const MAX_RETRY_WAIT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Minimum spacing between requests to the same host.
+fn host_min_interval(host: &str) -> std::time::Duration {
+    let ms = if host.ends_with("audnex.us") {
+        150
+    } else if host.starts_with("api.audible") {
+        200
+    } else if host.ends_with("googleapis.com") {
+        500
+    } else if host.ends_with("openlibrary.org") {
+        350
+    } else if host.ends_with("itunes.apple.com") {
+        3000 // ~20 requests per minute
+    } else {
+        0 // Image CDNs etc.
+    };
+    std::time::Duration::from_millis(ms)
+}
+
+/// Next free request slot per host.
+fn host_slots() -> &'static std::sync::Mutex<std::collections::HashMap<String, std::time::Instant>> {
+    static SLOTS: std::sync::OnceLock<std::sync::Mutex<std::collections::HashMap<String, std::time::Instant>>> = std::sync::OnceLock::new();
+    SLOTS.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+}
+
+/// Reserve the next slot for `host` and sleep until it comes up.
+async fn wait_for_host_slot(host: &str) {
+    let interval = host_min_interval(host);
+    let wait = {
+        let mut slots = host_slots().lock().unwrap_or_else(|e| e.into_inner());
+        let now = std::time::Instant::now();
+        let slot = slots.get(host).copied().filter(|t| *t > now).unwrap_or(now);
+        slots.insert(host.to_string(), slot + interval);
+        slot.saturating_duration_since(now)
+    };
+    if !wait.is_zero() {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Push back every pending request to `host` (used when the host tells us to slow down).
+fn defer_host(host: &str, wait: std::time::Duration) {
+    let mut slots = host_slots().lock().unwrap_or_else(|e| e.into_inner());
+    let until = std::time::Instant::now() + wait;
+    let slot = slots.entry(host.to_string()).or_insert(until);
+    if *slot < until {
+        *slot = until;
+    }
+}
+
+/// Retry-After in seconds (HTTP-date values fall back to exponential backoff).
+fn retry_after(response: &reqwest::Response) -> Option<std::time::Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(std::time::Duration::from_secs)
+}
+
+/// Error for a non-success `status` on try number `attempt` (0-based); None when the request
+/// should be retried (429/5xx with retries left).
+fn status_error(host: &str, status: reqwest::StatusCode, attempt: u32) -> Option<LecternError> {
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Some(LecternError::NotFound(format!("{} has no entry for this request", host)));
+    }
+    if status != reqwest::StatusCode::TOO_MANY_REQUESTS && !status.is_server_error() {
+        return Some(LecternError::ProviderStatus { host: host.to_string(), status: status.as_u16() });
+    }
+    if attempt >= MAX_RETRIES {
+        return Some(LecternError::ProviderUnavailable(format!("{} returned {} after {} attempts", host, status, attempt + 1)));
+    }
+    None
+}
+
+/// GET `url` through the cache, per-host rate limit and retry policy. Retries 429/5xx and
+/// connection failures with exponential backoff, honoring Retry-After. Timeouts are not retried
+/// so a hanging provider still fails fast.
+pub async fn http_get(url: &str, timeout: std::time::Duration) -> Result<Vec<u8>> {
+    let policy = http_cache::policy();
+    if let Some(body) = http_cache::lookup(url, policy.offline) {
+        println!("[DEBUG] Cache hit: {}", url);
+        return Ok(body);
+    }
+    if policy.offline {
+        return Err(LecternError::ProviderUnavailable(format!("offline mode and {} is not cached", url)));
+    }
+
+    let host = url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_default();
+    let client = meta_http_client();
+    let mut attempt = 0;
+    loop {
+        wait_for_host_slot(&host).await;
+        let retry_wait = match client.get(url).timeout(timeout).send().await {
+            Ok(response) => {
+                let status = response.status();
+                if status.is_success() {
+                    let body = response.bytes().await
+                        .map_err(|e| LecternError::ProviderUnavailable(format!("{}: {}", host, e)))?
+                        .to_vec();
+                    http_cache::store(url, &body);
+                    return Ok(body);
+                }
+                if let Some(error) = status_error(&host, status, attempt) {
+                    return Err(error);
+                }
+                let wait = retry_after(&response).unwrap_or_else(|| backoff(attempt));
+                if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                    defer_host(&host, wait.min(MAX_RETRY_WAIT));
+                }
+                wait
+            }
+            Err(e) => {
+                if e.is_timeout() || attempt >= MAX_RETRIES || !e.is_connect() {
+                    return Err(LecternError::ProviderUnavailable(format!("{}: {}", host, e)));
+                }
+                backoff(attempt)
+            }
+        };
+        let retry_wait = retry_wait.min(MAX_RETRY_WAIT);
+        println!("[DEBUG] {} - retrying in {:?} (attempt {}/{})", host, retry_wait, attempt + 2, MAX_RETRIES + 1);
+        tokio::time::sleep(retry_wait).await;
+        attempt += 1;
+    }
+}
+
+/// http_get and parse the body as JSON.
+pub async fn http_get_json(url: &str, timeout: std::time::Duration) -> Result<serde_json::Value> {
+    let body = http_get(url, timeout).await?;
+    serde_json::from_slice(&body).map_err(|e| LecternError::Parse(e.to_string()))
+}
+
+fn backoff(attempt: u32) -> std::time::Duration {
+    std::time::Duration::from_millis(BASE_BACKOFF_MS << attempt.min(6))
+}
+
 #[allow(dead_code)]
 impl AudioService {
     // Method to fetch single metadata
-    pub async fn fetch_metadata(query: &str) -> Result<BookMetadata, String> {
+    pub async fn fetch_metadata(query: &str) -> Result<BookMetadata> {
         // This is a placeholder implementation
         // In a real implementation, this would call an API like Audible or Google Books
         
@@ -34,19 +207,18 @@ impl AudioService {
         Ok(BookMetadata {
             title: format!("Book Title for {}", query),
             subtitle: None,
-            author: "Sample Author".to_string(),
+            authors: vec!["Sample Author".to_string()],
             isbn: Some("1234567890".to_string()),
             asin: Some("B012345678".to_string()),
             description: Some("This is a sample book description.".to_string()),
             cover_url: Some("https://example.com/cover.jpg".to_string()),
             duration: Some("10 hours".to_string()),
-            narrator: Some("Sample Narrator".to_string()),
+            narrators: vec!["Sample Narrator".to_string()],
             publisher: Some("Sample Publisher".to_string()),
             publish_year: Some("2023".to_string()),
-            series: None,
-            series_number: None,
-            genre: None,
-            tags: None,
+            series: Vec::new(),
+            genres: Vec::new(),
+            tags: Vec::new(),
             language: None,
             explicit: None,
             abridged: None,
@@ -54,7 +226,8 @@ impl AudioService {
     }
     
     // Method to search for multiple results
-    pub async fn search_metadata(query: &str, by_asin: bool, provider: Option<&str>) -> Result<Vec<BookMetadata>, String> {
+    // Each result is tagged with the provider that returned it; ranking happens in services::matching
+    pub async fn search_metadata(query: &str, by_asin: bool, provider: Option<&str>) -> Result<Vec<ProviderResult>> {
         let provider = provider.unwrap_or("auto");
         
         // Check if query looks like an ISBN or ASIN (even if by_asin is false)
@@ -68,12 +241,12 @@ impl AudioService {
             println!("[DEBUG] Audnexus identifier search requested: {} (ASIN format: {})", query, is_asin_format);
             if let Ok(results) = Self::search_audnexus_by_asin(query).await {
                 if !results.is_empty() {
-                    return Ok(results);
+                    return Ok(tag_results("Audnexus", results));
                 }
             }
             // If Audnexus fails and it's definitely an ASIN, return error
             if is_asin_format {
-                return Err(format!("No results found for ASIN: {}. The book may not be available in the Audnexus database.", query));
+                return Err(LecternError::NotFound(format!("No results found for ASIN: {}. The book may not be available in the Audnexus database.", query)));
             }
             // For ISBNs, fall through to other providers
         }
@@ -88,7 +261,7 @@ impl AudioService {
     }
     
     // Search by query with provider selection
-    async fn search_by_query_with_provider(query: &str, provider: Option<&str>) -> Result<Vec<BookMetadata>, String> {
+    async fn search_by_query_with_provider(query: &str, provider: Option<&str>) -> Result<Vec<ProviderResult>> {
         let provider = provider.unwrap_or("auto");
         
         println!("[DEBUG] search_by_query_with_provider called with provider: '{}', query: '{}'", provider, query);
@@ -99,31 +272,31 @@ impl AudioService {
                 // But we can try to use Audible catalog API as a fallback
                 println!("[DEBUG] Audnexus provider selected for title search, trying Audible catalog API as fallback");
                 // Try US region first
-                Self::search_audible(query, "us").await
+                Self::search_audible(query, "us").await.map(|r| tag_results("Audible.com", r))
             }
             "audible_com" => {
                 println!("[DEBUG] EXCLUSIVELY using Audible.com provider for query: '{}'", query);
-                Self::search_audible(query, "us").await
+                Self::search_audible(query, "us").await.map(|r| tag_results("Audible.com", r))
             }
             "audible_ca" => {
                 println!("[DEBUG] EXCLUSIVELY using Audible.ca provider for query: '{}'", query);
-                Self::search_audible(query, "ca").await
+                Self::search_audible(query, "ca").await.map(|r| tag_results("Audible.ca", r))
             }
             "open_library" => {
                 println!("[DEBUG] EXCLUSIVELY using Open Library provider for query: '{}'", query);
-                Self::search_open_library(query).await
+                Self::search_open_library(query).await.map(|r| tag_results("Open Library", r))
             }
             "google_books" => {
                 println!("[DEBUG] EXCLUSIVELY using Google Books provider for query: '{}'", query);
-                Self::search_google_books(query).await
+                Self::search_google_books(query).await.map(|r| tag_results("Google Books", r))
             }
             "itunes" => {
                 println!("[DEBUG] EXCLUSIVELY using iTunes provider for query: '{}'", query);
-                Self::search_itunes(query).await
+                Self::search_itunes(query).await.map(|r| tag_results("iTunes", r))
             }
             "fantlab" => {
                 println!("[DEBUG] EXCLUSIVELY using FantLab.ru provider for query: '{}'", query);
-                Self::search_fantlab(query).await
+                Self::search_fantlab(query).await.map(|r| tag_results("FantLab.ru", r))
             }
             _ => {
                 // "auto" - try all providers
@@ -134,7 +307,7 @@ impl AudioService {
     }
     
     // Search by ASIN or ISBN
-    async fn search_by_identifier(identifier: &str) -> Result<Vec<BookMetadata>, String> {
+    async fn search_by_identifier(identifier: &str) -> Result<Vec<ProviderResult>> {
         // Check if it looks like an ASIN (starts with B and is 10 characters)
         let is_asin = identifier.len() == 10 && identifier.starts_with('B');
         
@@ -144,7 +317,7 @@ impl AudioService {
             if let Ok(results) = Self::search_audnexus_by_asin(identifier).await {
                 if !results.is_empty() {
                     println!("[DEBUG] Found {} results from Audnexus", results.len());
-                    return Ok(results);
+                    return Ok(tag_results("Audnexus", results));
                 }
             }
             println!("[DEBUG] Audnexus search failed, trying Open Library and Google Books as fallback...");
@@ -153,47 +326,35 @@ impl AudioService {
         // Try Open Library first (supports ISBN, not ASIN)
         if let Ok(results) = Self::search_open_library_by_isbn(identifier).await {
             if !results.is_empty() {
-                return Ok(results);
+                return Ok(tag_results("Open Library", results));
             }
         }
         
         // Try Google Books as fallback
         if let Ok(results) = Self::search_google_books_by_isbn(identifier).await {
             if !results.is_empty() {
-                return Ok(results);
+                return Ok(tag_results("Google Books", results));
             }
         }
         
         // If it's an ASIN, provide a more helpful error message
         if is_asin {
-            Err(format!("No results found for ASIN: {}. The book may not be available in the Audnexus database.", identifier))
+            Err(LecternError::NotFound(format!("No results found for ASIN: {}. The book may not be available in the Audnexus database.", identifier)))
         } else {
-            Err(format!("No results found for identifier: {}", identifier))
+            Err(LecternError::NotFound(format!("No results found for identifier: {}", identifier)))
         }
     }
     
     // Search Audnexus API by ASIN (https://api.audnex.us)
-    async fn search_audnexus_by_asin(asin: &str) -> Result<Vec<BookMetadata>, String> {
-        let client = meta_http_client();
+    async fn search_audnexus_by_asin(asin: &str) -> Result<Vec<BookMetadata>> {
         let url = format!("https://api.audnex.us/books/{}", urlencoding::encode(asin));
         
         println!("[DEBUG] Audnexus URL: {}", url);
         
-        let response = client.get(&url)
-            .timeout(std::time::Duration::from_secs(6))
-            .send()
-            .await
-            .map_err(|e| format!("Audnexus request failed: {}", e))?;
-        
-        if !response.status().is_success() {
-            if response.status() == 404 {
-                return Ok(vec![]); // No results, not an error
-            }
-            return Err(format!("Audnexus returned status: {}", response.status()));
-        }
-        
-        let json: serde_json::Value = response.json().await
-            .map_err(|e| format!("Failed to parse Audnexus response: {}", e))?;
+        let json = match http_get_json(&url, PROVIDER_TIMEOUT).await {
+            Err(e) if e.is_not_found() => return Ok(vec![]), // No results, not an error
+            other => other?,
+        };
         
         if json.get("asin").is_none() {
             return Ok(vec![]); // Invalid response
@@ -210,25 +371,21 @@ impl AudioService {
     fn parse_audnexus_book(book: &serde_json::Value) -> Option<BookMetadata> {
         let title = book.get("title")?.as_str()?.to_string();
         
-        // Extract authors
-        let author = if let Some(authors) = book.get("authors").and_then(|a| a.as_array()) {
-            authors.iter()
-                .filter_map(|a| a.get("name").and_then(|n| n.as_str()))
-                .collect::<Vec<_>>()
-                .join(", ")
-        } else {
-            String::new()
-        };
-        
-        // Extract narrators
-        let narrator = if let Some(narrators) = book.get("narrators").and_then(|n| n.as_array()) {
-            narrators.iter()
-                .filter_map(|n| n.get("name").and_then(|name| name.as_str()))
-                .collect::<Vec<_>>()
-                .join(", ")
-        } else {
-            String::new()
+        // Extract authors and narrators
+        let names = |key: &str| -> Vec<String> {
+            book.get(key)
+                .and_then(|a| a.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|a| a.get("name").and_then(|n| n.as_str()))
+                        .map(|n| n.trim().to_string())
+                        .filter(|n| !n.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
         };
+        let authors = names("authors");
+        let narrators = names("narrators");
         
         let asin = book.get("asin").and_then(|a| a.as_str()).map(|s| s.to_string());
         let isbn = book.get("isbn").and_then(|i| i.as_str()).map(|s| s.to_string());
@@ -259,29 +416,41 @@ impl AudioService {
         // Extract subtitle
         let subtitle = book.get("subtitle").and_then(|s| s.as_str()).map(|s| s.to_string());
         
-        // Extract series information
-        let series = book.get("seriesName").and_then(|s| s.as_str()).map(|s| s.to_string());
-        let series_number = book.get("seriesSequence")
-            .and_then(|n| {
-                if let Some(s) = n.as_str() {
-                    Some(s.to_string())
-                } else if let Some(u) = n.as_u64() {
-                    Some(u.to_string())
-                } else {
-                    None
-                }
-            });
-        
-        // Extract genre (from genres array)
-        let genre = book.get("genres")
-            .and_then(|g| g.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|g| g.get("name").and_then(|n| n.as_str()))
-                    .collect::<Vec<_>>()
-                    .join(", ")
+        // Extract series information: primary and secondary series, each with its position
+        let sequence = |n: &serde_json::Value| -> Option<String> {
+            if let Some(s) = n.as_str() {
+                Some(s.to_string())
+            } else {
+                n.as_u64().map(|u| u.to_string())
+            }
+        };
+        let mut series: Vec<SeriesEntry> = ["seriesPrimary", "seriesSecondary"]
+            .iter()
+            .filter_map(|key| book.get(*key))
+            .filter_map(|s| {
+                let name = s.get("name")?.as_str()?;
+                Some(SeriesEntry::new(name, s.get("position").and_then(sequence)))
             })
-            .filter(|s| !s.is_empty());
+            .filter(|s| !s.name.is_empty())
+            .collect();
+        if series.is_empty() {
+            if let Some(name) = book.get("seriesName").and_then(|s| s.as_str()).filter(|s| !s.trim().is_empty()) {
+                series.push(SeriesEntry::new(name, book.get("seriesSequence").and_then(sequence)));
+            }
+        }
+        
+        // Extract genres and tags (Audnexus marks each entry with type "genre" or "tag")
+        let mut genres = Vec::new();
+        let mut tags = Vec::new();
+        for entry in book.get("genres").and_then(|g| g.as_array()).into_iter().flatten() {
+            let Some(name) = entry.get("name").and_then(|n| n.as_str()).filter(|n| !n.trim().is_empty()) else {
+                continue;
+            };
+            match entry.get("type").and_then(|t| t.as_str()) {
+                Some("tag") => tags.push(name.trim().to_string()),
+                _ => genres.push(name.trim().to_string()),
+            }
+        }
         
         // Extract language
         let language = book.get("language").and_then(|l| l.as_str()).map(|s| s.to_string());
@@ -297,89 +466,69 @@ impl AudioService {
         let metadata = BookMetadata {
             title,
             subtitle,
-            author,
+            authors,
             isbn,
             asin,
             description,
             cover_url,
             duration,
-            narrator: if narrator.is_empty() { None } else { Some(narrator) },
+            narrators,
             publisher,
             publish_year,
             series,
-            series_number,
-            genre,
-            tags: None, // Audnexus doesn't provide tags
+            genres,
+            tags,
             language,
             explicit,
             abridged,
         };
         
         // Debug: log what fields were extracted
-        println!("[DEBUG] Audnexus extracted metadata - Title: '{}', Author: '{}', Subtitle: {:?}, ISBN: {:?}, ASIN: {:?}, Publisher: {:?}, Year: {:?}, Series: {:?}, Series#: {:?}, Genre: {:?}, Language: {:?}, Narrator: {:?}, Explicit: {:?}, Abridged: {:?}", 
-            metadata.title, metadata.author, metadata.subtitle, metadata.isbn, metadata.asin,
-            metadata.publisher, metadata.publish_year, metadata.series, metadata.series_number,
-            metadata.genre, metadata.language, metadata.narrator, metadata.explicit, metadata.abridged);
+        println!("[DEBUG] Audnexus extracted metadata - Title: '{}', Authors: {:?}, Subtitle: {:?}, ISBN: {:?}, ASIN: {:?}, Publisher: {:?}, Year: {:?}, Series: {:?}, Genres: {:?}, Tags: {:?}, Language: {:?}, Narrators: {:?}, Explicit: {:?}, Abridged: {:?}", 
+            metadata.title, metadata.authors, metadata.subtitle, metadata.isbn, metadata.asin,
+            metadata.publisher, metadata.publish_year, metadata.series,
+            metadata.genres, metadata.tags, metadata.language, metadata.narrators, metadata.explicit, metadata.abridged);
         
         Some(metadata)
     }
     
-    // Search by title/author query (auto mode - tries all providers)
-    // Default priority: Audible (best results) -> Open Library -> Google Books -> iTunes
-    async fn search_by_query(query: &str) -> Result<Vec<BookMetadata>, String> {
+    // Search by title/author query (auto mode - queries all providers)
+    // Providers are queried in parallel and their results concatenated; duplicates are merged
+    // and results ranked by match score afterwards (see services::matching).
+    async fn search_by_query(query: &str) -> Result<Vec<ProviderResult>> {
         println!("[DEBUG] search_by_query (AUTO mode) called with: '{}'", query);
         
-        let mut results = Vec::new();
-        
-        // Try providers in order of preference
-        // 1. Audible.com (best results for audiobooks, uses catalog API + Audnexus)
-        match Self::search_audible(query, "us").await {
-            Ok(audible_results) if !audible_results.is_empty() => {
-                println!("[DEBUG] Audible.com returned {} results", audible_results.len());
-                results = audible_results;
-            },
-            Ok(_) => println!("[DEBUG] Audible.com returned empty results"),
-            Err(e) => println!("[DEBUG] Audible.com error: {}", e),
-        }
-        
-        // 2. Open Library (good coverage, free)
-        if results.is_empty() {
-            match Self::search_open_library(query).await {
-                Ok(open_lib_results) if !open_lib_results.is_empty() => {
-                    println!("[DEBUG] Open Library returned {} results", open_lib_results.len());
-                    results = open_lib_results;
-                },
-                Ok(_) => println!("[DEBUG] Open Library returned empty results"),
-                Err(e) => println!("[DEBUG] Open Library error: {}", e),
-            }
-        }
-        
-        // 3. Google Books (good coverage, free)
-        if results.is_empty() {
-            match Self::search_google_books(query).await {
-                Ok(google_results) if !google_results.is_empty() => {
-                    println!("[DEBUG] Google Books returned {} results", google_results.len());
-                    results = google_results;
-                },
-                Ok(_) => println!("[DEBUG] Google Books returned empty results"),
-                Err(e) => println!("[DEBUG] Google Books error: {}", e),
-            }
-        }
+        let (audible, open_library, google, itunes) = tokio::join!(
+            Self::search_audible(query, "us"),
+            Self::search_open_library(query),
+            Self::search_google_books(query),
+            Self::search_itunes(query),
+        );
         
-        // 4. iTunes (good for audiobooks)
-        if results.is_empty() {
-            match Self::search_itunes(query).await {
-                Ok(itunes_results) if !itunes_results.is_empty() => {
-                    println!("[DEBUG] iTunes returned {} results", itunes_results.len());
-                    results = itunes_results;
+        let mut results = Vec::new();
+        let mut unavailable = Vec::new();
+        for (provider, outcome) in [
+            ("Audible.com", audible),
+            ("Open Library", open_library),
+            ("Google Books", google),
+            ("iTunes", itunes),
+        ] {
+            match outcome {
+                Ok(provider_results) if !provider_results.is_empty() => {
+                    println!("[DEBUG] {} returned {} results", provider, provider_results.len());
+                    results.extend(tag_results(provider, provider_results));
                 },
-                Ok(_) => println!("[DEBUG] iTunes returned empty results"),
-                Err(e) => println!("[DEBUG] iTunes error: {}", e),
+                Ok(_) => println!("[DEBUG] {} returned empty results", provider),
+                Err(e) if e.is_not_found() => println!("[DEBUG] {} found nothing: {}", provider, e),
+                Err(e) => {
+                    println!("[DEBUG] {} error: {}", provider, e);
+                    unavailable.push(format!("{} ({})", provider, e));
+                }
             }
         }
         
         // Try to enrich results with ASIN from Audnexus if we have ISBN
-        for book in &mut results {
+        for book in results.iter_mut().map(|r| &mut r.book) {
             if book.asin.is_none() && book.isbn.is_some() {
                 if let Ok(asin_result) = Self::search_audnexus_by_isbn(book.isbn.as_ref().unwrap()).await {
                     if let Some(enriched_book) = asin_result.first() {
@@ -394,46 +543,31 @@ impl AudioService {
         
         if results.is_empty() {
             println!("[DEBUG] No results found from any provider for: '{}'", query);
-            Err(format!("No results found for query: {}", query))
+            if unavailable.len() == 4 {
+                Err(LecternError::ProviderUnavailable(format!("no provider could be reached: {}", unavailable.join("; "))))
+            } else {
+                Err(LecternError::NotFound(format!("No results found for query: {}", query)))
+            }
         } else {
             Ok(results)
         }
     }
     
     // Search Audnexus by ISBN to get ASIN
-    async fn search_audnexus_by_isbn(_isbn: &str) -> Result<Vec<BookMetadata>, String> {
+    async fn search_audnexus_by_isbn(_isbn: &str) -> Result<Vec<BookMetadata>> {
         // Audnexus doesn't have a direct ISBN endpoint, but we can try searching by ISBN
         // For now, return empty - this would need a different approach
         Ok(Vec::new())
     }
     
     // Search Open Library API
-    async fn search_open_library(query: &str) -> Result<Vec<BookMetadata>, String> {
+    async fn search_open_library(query: &str) -> Result<Vec<BookMetadata>> {
         println!("[DEBUG] Searching Open Library for: {}", query);
-        let client = meta_http_client();
         let url = format!("https://openlibrary.org/search.json?q={}&limit=10", 
                          urlencoding::encode(query));
         println!("[DEBUG] Open Library URL: {}", url);
         
-        let response = client.get(&url)
-            .send()
-            .await
-            .map_err(|e| {
-                println!("[DEBUG] Open Library request error: {}", e);
-                format!("Open Library request failed: {}", e)
-            })?;
-        
-        println!("[DEBUG] Open Library response status: {}", response.status());
-        
-        if !response.status().is_success() {
-            return Err(format!("Open Library returned status: {}", response.status()));
-        }
-        
-        let json: serde_json::Value = response.json().await
-            .map_err(|e| {
-                println!("[DEBUG] Open Library parse error: {}", e);
-                format!("Failed to parse Open Library response: {}", e)
-            })?;
+        let json = http_get_json(&url, PROVIDER_TIMEOUT).await?;
         
         let mut results = Vec::new();
         if let Some(docs) = json.get("docs").and_then(|d| d.as_array()) {
@@ -452,21 +586,10 @@ impl AudioService {
     }
     
     // Search Open Library by ISBN
-    async fn search_open_library_by_isbn(isbn: &str) -> Result<Vec<BookMetadata>, String> {
-        let client = meta_http_client();
+    async fn search_open_library_by_isbn(isbn: &str) -> Result<Vec<BookMetadata>> {
         let url = format!("https://openlibrary.org/isbn/{}.json", isbn);
         
-        let response = client.get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("Open Library ISBN request failed: {}", e))?;
-        
-        if !response.status().is_success() {
-            return Err(format!("Open Library ISBN returned status: {}", response.status()));
-        }
-        
-        let doc: serde_json::Value = response.json().await
-            .map_err(|e| format!("Failed to parse Open Library ISBN response: {}", e))?;
+        let doc = http_get_json(&url, PROVIDER_TIMEOUT).await?;
         
         if let Some(metadata) = Self::parse_open_library_doc(&doc) {
             Ok(vec![metadata])
@@ -480,20 +603,20 @@ impl AudioService {
         let title = doc.get("title")?.as_str()?.to_string();
         
         // Extract author(s)
-        let author = if let Some(authors) = doc.get("author_name").and_then(|a| a.as_array()) {
+        let authors = if let Some(authors) = doc.get("author_name").and_then(|a| a.as_array()) {
             authors.iter()
                 .filter_map(|a| a.as_str())
+                .map(|a| a.to_string())
                 .collect::<Vec<_>>()
-                .join(", ")
         } else if let Some(author_key) = doc.get("author_key").and_then(|a| a.as_array()) {
             if let Some(first_key) = author_key.first().and_then(|k| k.as_str()) {
                 // Try to get author name from key (simplified)
-                format!("Author {}", first_key)
+                vec![format!("Author {}", first_key)]
             } else {
-                "Unknown Author".to_string()
+                vec!["Unknown Author".to_string()]
             }
         } else {
-            "Unknown Author".to_string()
+            vec!["Unknown Author".to_string()]
         };
         
         // Extract ISBN/ASIN
@@ -564,16 +687,16 @@ impl AudioService {
             });
         
         // Extract subject/genre (Open Library uses "subject" field)
-        let genre = doc.get("subject")
+        let genres = doc.get("subject")
             .and_then(|s| s.as_array())
             .map(|arr| {
                 arr.iter()
                     .filter_map(|s| s.as_str())
                     .take(3) // Limit to first 3 subjects
+                    .map(|s| s.to_string())
                     .collect::<Vec<_>>()
-                    .join(", ")
             })
-            .filter(|s| !s.is_empty());
+            .unwrap_or_default();
         
         // Extract language
         let language = doc.get("language")
@@ -590,60 +713,40 @@ impl AudioService {
         let metadata = BookMetadata {
             title,
             subtitle,
-            author,
+            authors,
             isbn,
             asin: None, // Open Library doesn't provide ASIN
             description,
             cover_url,
             duration: None,
-            narrator: None,
+            narrators: Vec::new(),
             publisher,
             publish_year,
-            series: None,
-            series_number: None,
-            genre,
-            tags: None,
+            series: Vec::new(),
+            genres,
+            tags: Vec::new(),
             language,
             explicit: None,
             abridged: None,
         };
         
         // Debug: log what fields were extracted
-        println!("[DEBUG] Open Library extracted metadata - Title: '{}', Author: '{}', Subtitle: {:?}, ISBN: {:?}, Publisher: {:?}, Year: {:?}, Genre: {:?}, Language: {:?}, Description: {:?}", 
-            metadata.title, metadata.author, metadata.subtitle, metadata.isbn, 
-            metadata.publisher, metadata.publish_year, metadata.genre, metadata.language,
+        println!("[DEBUG] Open Library extracted metadata - Title: '{}', Authors: {:?}, Subtitle: {:?}, ISBN: {:?}, Publisher: {:?}, Year: {:?}, Genres: {:?}, Language: {:?}, Description: {:?}", 
+            metadata.title, metadata.authors, metadata.subtitle, metadata.isbn, 
+            metadata.publisher, metadata.publish_year, metadata.genres, metadata.language,
             metadata.description.as_ref().map(|d| if d.len() > 50 { format!("{}...", &d[..50]) } else { d.clone() }));
         
         Some(metadata)
     }
     
     // Search Google Books API
-    async fn search_google_books(query: &str) -> Result<Vec<BookMetadata>, String> {
+    async fn search_google_books(query: &str) -> Result<Vec<BookMetadata>> {
         println!("[DEBUG] Searching Google Books for: {}", query);
-        let client = meta_http_client();
         let url = format!("https://www.googleapis.com/books/v1/volumes?q={}&maxResults=10", 
                          urlencoding::encode(query));
         println!("[DEBUG] Google Books URL: {}", url);
         
-        let response = client.get(&url)
-            .send()
-            .await
-            .map_err(|e| {
-                println!("[DEBUG] Google Books request error: {}", e);
-                format!("Google Books request failed: {}", e)
-            })?;
-        
-        println!("[DEBUG] Google Books response status: {}", response.status());
-        
-        if !response.status().is_success() {
-            return Err(format!("Google Books returned status: {}", response.status()));
-        }
-        
-        let json: serde_json::Value = response.json().await
-            .map_err(|e| {
-                println!("[DEBUG] Google Books parse error: {}", e);
-                format!("Failed to parse Google Books response: {}", e)
-            })?;
+        let json = http_get_json(&url, PROVIDER_TIMEOUT).await?;
         
         let mut results = Vec::new();
         if let Some(items) = json.get("items").and_then(|i| i.as_array()) {
@@ -662,7 +765,7 @@ impl AudioService {
     }
     
     // Search Google Books by ISBN
-    async fn search_google_books_by_isbn(isbn: &str) -> Result<Vec<BookMetadata>, String> {
+    async fn search_google_books_by_isbn(isbn: &str) -> Result<Vec<BookMetadata>> {
         Self::search_google_books(&format!("isbn:{}", isbn)).await
     }
     
@@ -673,13 +776,13 @@ impl AudioService {
         let title = volume_info.get("title")?.as_str()?.to_string();
         
         // Extract author(s)
-        let author = if let Some(authors) = volume_info.get("authors").and_then(|a| a.as_array()) {
+        let authors = if let Some(authors) = volume_info.get("authors").and_then(|a| a.as_array()) {
             authors.iter()
                 .filter_map(|a| a.as_str())
+                .map(|a| a.to_string())
                 .collect::<Vec<_>>()
-                .join(", ")
         } else {
-            "Unknown Author".to_string()
+            vec!["Unknown Author".to_string()]
         };
         
         // Extract ISBN
@@ -735,16 +838,16 @@ impl AudioService {
             .map(|s| s.to_string());
         
         // Extract categories/genre
-        let genre = volume_info.get("categories")
+        let genres = volume_info.get("categories")
             .and_then(|c| c.as_array())
             .map(|arr| {
                 arr.iter()
                     .filter_map(|c| c.as_str())
                     .take(3) // Limit to first 3 categories
+                    .map(|c| c.to_string())
                     .collect::<Vec<_>>()
-                    .join(", ")
             })
-            .filter(|s| !s.is_empty());
+            .unwrap_or_default();
         
         // Extract language
         let language = volume_info.get("language").and_then(|l| l.as_str()).map(|s| s.to_string());
@@ -752,35 +855,34 @@ impl AudioService {
         let metadata = BookMetadata {
             title,
             subtitle,
-            author,
+            authors,
             isbn,
             asin: None, // Google Books doesn't provide ASIN
             description,
             cover_url,
             duration: None,
-            narrator: None,
+            narrators: Vec::new(),
             publisher,
             publish_year,
-            series: None,
-            series_number: None,
-            genre,
-            tags: None,
+            series: Vec::new(),
+            genres,
+            tags: Vec::new(),
             language,
             explicit: None,
             abridged: None,
         };
         
         // Debug: log what fields were extracted
-        println!("[DEBUG] Google Books extracted metadata - Title: '{}', Author: '{}', Subtitle: {:?}, ISBN: {:?}, Publisher: {:?}, Year: {:?}, Genre: {:?}, Language: {:?}, Description: {:?}", 
-            metadata.title, metadata.author, metadata.subtitle, metadata.isbn, 
-            metadata.publisher, metadata.publish_year, metadata.genre, metadata.language,
+        println!("[DEBUG] Google Books extracted metadata - Title: '{}', Authors: {:?}, Subtitle: {:?}, ISBN: {:?}, Publisher: {:?}, Year: {:?}, Genres: {:?}, Language: {:?}, Description: {:?}", 
+            metadata.title, metadata.authors, metadata.subtitle, metadata.isbn, 
+            metadata.publisher, metadata.publish_year, metadata.genres, metadata.language,
             metadata.description.as_ref().map(|d| if d.len() > 50 { format!("{}...", &d[..50]) } else { d.clone() }));
         
         Some(metadata)
     }
     
     // Method to convert audio files to M4B
-    pub async fn convert_to_m4b(input_files: Vec<String>, output_path: &str) -> Result<(), String> {
+    pub async fn convert_to_m4b(input_files: Vec<String>, output_path: &str) -> Result<()> {
         // Implementation would use FFmpeg to convert files
         // This is a placeholder
         println!("Converting {} files to M4B at {}", input_files.len(), output_path);
@@ -793,7 +895,7 @@ impl AudioService {
         _token: &str,
         library_id: &str,
         file_path: &str,
-    ) -> Result<(), String> {
+    ) -> Result<()> {
         // Implementation would use reqwest to upload to Audiobookshelf
         // This is a placeholder
         println!("Uploading {} to Audiobookshelf at {} with library {}", file_path, host, library_id);
@@ -801,7 +903,7 @@ impl AudioService {
     }
     
     // Method to scan library in Audiobookshelf
-    pub async fn scan_library(host: &str, _token: &str, library_id: &str) -> Result<(), String> {
+    pub async fn scan_library(host: &str, _token: &str, library_id: &str) -> Result<()> {
         // Implementation would use reqwest to trigger library scan
         // This is a placeholder
         println!("Scanning library {} at {}", library_id, host);
@@ -809,34 +911,22 @@ impl AudioService {
     }
     
     // Fetch chapters from Audnexus by ASIN
-    pub async fn fetch_chapters_by_asin(asin: &str, region: &str) -> Result<Vec<crate::models::Chapter>, String> {
+    pub async fn fetch_chapters_by_asin(asin: &str, region: &str) -> Result<crate::models::ChapterLookup> {
         
-        let client = meta_http_client();
         let url = format!("https://api.audnex.us/books/{}/chapters?region={}", 
                          urlencoding::encode(asin), region);
         
         println!("[DEBUG] Fetching chapters from Audnexus: {}", url);
         
-        let response = client.get(&url)
-            .timeout(std::time::Duration::from_secs(6))
-            .send()
-            .await
-            .map_err(|e| format!("Audnexus chapters request failed: {}", e))?;
-        
-        if !response.status().is_success() {
-            if response.status() == 404 {
-                return Err("No chapters found for this ASIN".to_string());
-            }
-            return Err(format!("Audnexus returned status: {}", response.status()));
-        }
-        
-        let json: serde_json::Value = response.json().await
-            .map_err(|e| format!("Failed to parse Audnexus chapters response: {}", e))?;
+        let json = http_get_json(&url, PROVIDER_TIMEOUT).await.map_err(|e| match e {
+            LecternError::NotFound(_) => LecternError::NotFound("No chapters found for this ASIN".to_string()),
+            other => other,
+        })?;
         
         // Audnexus returns chapters in a "chapters" array
         let chapters_array = json.get("chapters")
             .and_then(|c| c.as_array())
-            .ok_or_else(|| "No chapters array in response".to_string())?;
+            .ok_or_else(|| LecternError::Parse("No chapters array in response".to_string()))?;
         
         let mut chapters = Vec::new();
         for (index, chapter_json) in chapters_array.iter().enumerate() {
@@ -864,40 +954,100 @@ impl AudioService {
             });
         }
         
-        println!("[DEBUG] Parsed {} chapters from Audnexus", chapters.len());
-        Ok(chapters)
+        let brand_ms = |key: &str| json.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+        let brand_intro_ms = brand_ms("brandIntroDurationMs");
+        let brand_outro_ms = brand_ms("brandOutroDurationMs");
+        
+        println!("[DEBUG] Parsed {} chapters from Audnexus (brand intro {} ms, outro {} ms)", chapters.len(), brand_intro_ms, brand_outro_ms);
+        Ok(crate::models::ChapterLookup { chapters, brand_intro_ms, brand_outro_ms })
+    }
+    
+    // Find Audible editions by title/author for a chapter lookup without an ASIN. Closest runtime
+    // to `runtime_ms` (the book's duration, when known) first.
+    pub async fn find_chapter_editions(title: &str, author: &str, region: &str, runtime_ms: Option<u64>) -> Result<Vec<crate::models::ChapterEdition>> {
+        let mut query_params = vec![
+            ("num_results", "20"),
+            ("products_sort_by", "Relevance"),
+            ("response_groups", "contributors,product_attrs,product_desc"),
+            ("title", title),
+        ];
+        if !author.is_empty() {
+            query_params.push(("author", author));
+        }
+        let query_string: String = query_params.iter()
+            .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+            .collect::<Vec<_>>()
+            .join("&");
+        let url = format!("https://api.audible{}/1.0/catalog/products?{}", audible_tld(region), query_string);
+        
+        println!("[DEBUG] Finding chapter editions (region: {}): {}", region, url);
+        
+        let json = http_get_json(&url, PROVIDER_TIMEOUT).await?;
+        let editions = Self::parse_chapter_editions(&json, runtime_ms);
+        println!("[DEBUG] Found {} Audible editions for '{}'", editions.len(), title);
+        if editions.is_empty() {
+            Err(LecternError::NotFound(format!("No editions found on Audible{} for: {}", audible_tld(region), title)))
+        } else {
+            Ok(editions)
+        }
+    }
+    
+    // Editions in an Audible catalog products response; closest runtime to `runtime_ms` first
+    fn parse_chapter_editions(json: &serde_json::Value, runtime_ms: Option<u64>) -> Vec<crate::models::ChapterEdition> {
+        let names = |product: &serde_json::Value, key: &str| {
+            product.get(key)
+                .and_then(|list| list.as_array())
+                .map(|list| list.iter()
+                    .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
+                    .collect::<Vec<_>>()
+                    .join(", "))
+                .unwrap_or_default()
+        };
+        let mut editions: Vec<crate::models::ChapterEdition> = json.get("products")
+            .and_then(|p| p.as_array())
+            .map(|products| products.iter().filter_map(|product| {
+                let asin = product.get("asin")?.as_str()?.to_string();
+                let title = product.get("title").and_then(|t| t.as_str()).unwrap_or(&asin).to_string();
+                let title = match product.get("subtitle").and_then(|s| s.as_str()) {
+                    Some(subtitle) if !subtitle.is_empty() => format!("{}: {}", title, subtitle),
+                    _ => title,
+                };
+                Some(crate::models::ChapterEdition {
+                    asin,
+                    title,
+                    authors: names(product, "authors"),
+                    narrators: names(product, "narrators"),
+                    runtime_ms: product.get("runtime_length_min").and_then(|m| m.as_u64()).map(|m| m * 60_000),
+                    release_date: product.get("release_date").and_then(|d| d.as_str()).map(|d| d.to_string()),
+                })
+            }).collect())
+            .unwrap_or_default();
+        
+        if let Some(runtime) = runtime_ms {
+            // Editions without a runtime last; the sort is stable so relevance breaks ties
+            editions.sort_by_key(|e| e.runtime_ms.map(|r| r.abs_diff(runtime)).unwrap_or(u64::MAX));
+        }
+        editions
     }
     
     // Search Audible.com/ca directly
     // For ASIN: uses Audnexus API directly
     // For title/author: uses Audible Catalog API to find ASINs, then Audnexus for full details
-    async fn search_audible(query: &str, region: &str) -> Result<Vec<BookMetadata>, String> {
+    async fn search_audible(query: &str, region: &str) -> Result<Vec<BookMetadata>> {
         // Check if query looks like an ASIN
         let is_asin = query.len() == 10 && query.starts_with('B');
         
         if is_asin {
             // Direct ASIN lookup via Audnexus
-            let client = meta_http_client();
             let url = format!("https://api.audnex.us/books/{}?region={}", 
                              urlencoding::encode(query), region);
             
             println!("[DEBUG] Audible (region: {}) ASIN search URL: {}", region, url);
             
-            let response = client.get(&url)
-                .timeout(std::time::Duration::from_secs(6))
-                .send()
-                .await
-                .map_err(|e| format!("Audible request failed: {}", e))?;
-            
-            if !response.status().is_success() {
-                if response.status() == 404 {
-                    return Ok(vec![]);
-                }
-                return Err(format!("Audible returned status: {}", response.status()));
-            }
-            
-            let json: serde_json::Value = response.json().await
-                .map_err(|e| format!("Failed to parse Audible response: {}", e))?;
+            let json = match http_get_json(&url, PROVIDER_TIMEOUT).await {
+                Err(e) if e.is_not_found() => return Ok(vec![]), // No results, not an error
+                other => other?,
+            };
             
             if json.get("asin").is_none() {
                 return Ok(vec![]);
@@ -910,21 +1060,8 @@ impl AudioService {
             }
         } else {
             // Title/author search: Use Audible Catalog API to find ASINs, then fetch details from Audnexus
-            let client = meta_http_client();
             
-            // Map region to TLD
-            let tld = match region {
-                "ca" => ".ca",
-                "uk" => ".co.uk",
-                "au" => ".com.au",
-                "fr" => ".fr",
-                "de" => ".de",
-                "jp" => ".co.jp",
-                "it" => ".it",
-                "in" => ".in",
-                "es" => ".es",
-                _ => ".com", // default to US
-            };
+            let tld = audible_tld(region);
             
             // Build query parameters for Audible Catalog API
             let query_params = vec![
@@ -943,18 +1080,7 @@ impl AudioService {
             
             println!("[DEBUG] Audible (region: {}) Catalog search URL: {}", region, url);
             
-            let response = client.get(&url)
-                .timeout(std::time::Duration::from_secs(6))
-                .send()
-                .await
-                .map_err(|e| format!("Audible catalog request failed: {}", e))?;
-            
-            if !response.status().is_success() {
-                return Err(format!("Audible catalog returned status: {}", response.status()));
-            }
-            
-            let json: serde_json::Value = response.json().await
-                .map_err(|e| format!("Failed to parse Audible catalog response: {}", e))?;
+            let json = http_get_json(&url, PROVIDER_TIMEOUT).await?;
             
             // Extract ASINs from products
             let asins: Vec<String> = if let Some(products) = json.get("products").and_then(|p| p.as_array()) {
@@ -981,7 +1107,7 @@ impl AudioService {
             }
             
             if results.is_empty() {
-                Err(format!("No results found on Audible{} for: {}", tld, query))
+                Err(LecternError::NotFound(format!("No results found on Audible{} for: {}", tld, query)))
             } else {
                 Ok(results)
             }
@@ -989,28 +1115,16 @@ impl AudioService {
     }
     
     // Search Audnexus API by ASIN with region parameter
-    async fn search_audnexus_by_asin_with_region(asin: &str, region: &str) -> Result<Vec<BookMetadata>, String> {
-        let client = meta_http_client();
+    async fn search_audnexus_by_asin_with_region(asin: &str, region: &str) -> Result<Vec<BookMetadata>> {
         let url = format!("https://api.audnex.us/books/{}?region={}", 
                          urlencoding::encode(asin), region);
         
         println!("[DEBUG] Audnexus (region: {}) URL: {}", region, url);
         
-        let response = client.get(&url)
-            .timeout(std::time::Duration::from_secs(6))
-            .send()
-            .await
-            .map_err(|e| format!("Audnexus request failed: {}", e))?;
-        
-        if !response.status().is_success() {
-            if response.status() == 404 {
-                return Ok(vec![]);
-            }
-            return Err(format!("Audnexus returned status: {}", response.status()));
-        }
-        
-        let json: serde_json::Value = response.json().await
-            .map_err(|e| format!("Failed to parse Audnexus response: {}", e))?;
+        let json = match http_get_json(&url, PROVIDER_TIMEOUT).await {
+            Err(e) if e.is_not_found() => return Ok(vec![]), // No results, not an error
+            other => other?,
+        };
         
         if json.get("asin").is_none() {
             return Ok(vec![]);
@@ -1024,26 +1138,14 @@ impl AudioService {
     }
     
     // Search iTunes Store API
-    async fn search_itunes(query: &str) -> Result<Vec<BookMetadata>, String> {
-        let client = meta_http_client();
+    async fn search_itunes(query: &str) -> Result<Vec<BookMetadata>> {
         // iTunes Search API - audiobook media type
         let url = format!("https://itunes.apple.com/search?term={}&media=audiobook&limit=10", 
                          urlencoding::encode(query));
         
         println!("[DEBUG] iTunes URL: {}", url);
         
-        let response = client.get(&url)
-            .timeout(std::time::Duration::from_secs(6))
-            .send()
-            .await
-            .map_err(|e| format!("iTunes request failed: {}", e))?;
-        
-        if !response.status().is_success() {
-            return Err(format!("iTunes returned status: {}", response.status()));
-        }
-        
-        let json: serde_json::Value = response.json().await
-            .map_err(|e| format!("Failed to parse iTunes response: {}", e))?;
+        let json = http_get_json(&url, PROVIDER_TIMEOUT).await?;
         
         let mut results = Vec::new();
         if let Some(results_array) = json.get("results").and_then(|r| r.as_array()) {
@@ -1056,7 +1158,7 @@ impl AudioService {
         }
         
         if results.is_empty() {
-            Err(format!("No results found on iTunes for: {}", query))
+            Err(LecternError::NotFound(format!("No results found on iTunes for: {}", query)))
         } else {
             Ok(results)
         }
@@ -1126,19 +1228,18 @@ impl AudioService {
         Some(BookMetadata {
             title: track_name,
             subtitle: None,
-            author: artist_name,
+            authors: crate::models::metadata::split_names(&artist_name),
             isbn,
             asin: None, // iTunes doesn't provide ASIN
             description,
             cover_url,
             duration,
-            narrator: None, // iTunes search results don't include narrator
+            narrators: Vec::new(), // iTunes search results don't include narrator
             publisher: None,
             publish_year,
-            series,
-            series_number,
-            genre,
-            tags: None,
+            series: series.map(|name| vec![SeriesEntry::new(name, series_number)]).unwrap_or_default(),
+            genres: genre.into_iter().collect(),
+            tags: Vec::new(),
             language,
             explicit: None, // iTunes has contentAdvisoryRating but it's not in search results
             abridged: None,
@@ -1146,36 +1247,109 @@ impl AudioService {
     }
     
     // Search FantLab.ru API
-    async fn search_fantlab(_query: &str) -> Result<Vec<BookMetadata>, String> {
+    async fn search_fantlab(_query: &str) -> Result<Vec<BookMetadata>> {
         // FantLab.ru doesn't have a public API, so this would require web scraping
         // For now, return an error indicating it's not implemented
-        Err("FantLab.ru search is not yet implemented. FantLab.ru does not provide a public API.".to_string())
+        Err(LecternError::ProviderUnavailable("FantLab.ru search is not yet implemented. FantLab.ru does not provide a public API.".to_string()))
     }
 }
 
 // Add a helper function to get metadata from a file
 #[allow(dead_code)]
-pub async fn get_file_metadata(file_path: &str) -> Result<BookMetadata, String> {
+pub async fn get_file_metadata(file_path: &str) -> Result<BookMetadata> {
     // This would extract metadata from audio files
     // For now, return mock data
     Ok(BookMetadata {
         title: format!("Audio File: {}", file_path),
         subtitle: None,
-        author: "Unknown Author".to_string(),
+        authors: vec!["Unknown Author".to_string()],
         isbn: None,
         asin: None,
         description: None,
         cover_url: None,
         duration: Some("00:00:00".to_string()),
-        narrator: None,
+        narrators: Vec::new(),
         publisher: None,
         publish_year: None,
-        series: None,
-        series_number: None,
-        genre: None,
-        tags: None,
+        series: Vec::new(),
+        genres: Vec::new(),
+        tags: Vec::new(),
         language: None,
         explicit: None,
         abridged: None,
     })
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn not_found_is_a_normal_answer() {
+        let error = status_error("api.audnex.us", StatusCode::NOT_FOUND, 0).unwrap();
+        assert!(error.is_not_found());
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn throttling_and_server_errors_retry_then_become_unavailable() {
+        for status in [StatusCode::TOO_MANY_REQUESTS, StatusCode::INTERNAL_SERVER_ERROR, StatusCode::SERVICE_UNAVAILABLE] {
+            assert!(status_error("host", status, 0).is_none(), "{} should be retried", status);
+            let error = status_error("host", status, MAX_RETRIES).unwrap();
+            assert!(matches!(error, LecternError::ProviderUnavailable(_)), "{} gave {:?}", status, error);
+            assert!(error.is_retryable());
+        }
+    }
+
+    #[test]
+    fn other_statuses_fail_without_retry() {
+        let error = status_error("host", StatusCode::FORBIDDEN, 0).unwrap();
+        assert!(matches!(error, LecternError::ProviderStatus { status: 403, .. }));
+        assert!(!error.is_retryable());
+    }
+
+    /// Trimmed Audible catalog response for a title search.
+    const EDITIONS_FIXTURE: &str = r#"{
+        "products": [
+            {"asin": "B00ABRIDGED", "title": "The Book", "subtitle": "Abridged Edition",
+             "authors": [{"asin": "A1", "name": "Ann Author"}], "narrators": [{"name": "Nora Narrator"}],
+             "runtime_length_min": 300, "release_date": "2010-01-01"},
+            {"asin": "B00NORUNTIM", "title": "The Book"},
+            {"title": "No ASIN, skipped"},
+            {"asin": "B00UNABRIDG", "title": "The Book", "subtitle": "",
+             "authors": [{"name": "Ann Author"}, {"name": "Bob Author"}],
+             "narrators": [{"name": "Nora Narrator"}, {"name": "Ned Narrator"}],
+             "runtime_length_min": 725, "release_date": "2015-06-30"},
+            {"asin": "B00DRAMATIZ", "title": "The Book", "subtitle": "A Full-Cast Dramatization", "runtime_length_min": 680}
+        ],
+        "total_results": 5
+    }"#;
+
+    #[test]
+    fn editions_are_parsed_in_relevance_order() {
+        let json: serde_json::Value = serde_json::from_str(EDITIONS_FIXTURE).expect("fixture");
+        let editions = AudioService::parse_chapter_editions(&json, None);
+        let asins: Vec<&str> = editions.iter().map(|e| e.asin.as_str()).collect();
+        assert_eq!(asins, vec!["B00ABRIDGED", "B00NORUNTIM", "B00UNABRIDG", "B00DRAMATIZ"]);
+        let first = &editions[0];
+        assert_eq!(first.title, "The Book: Abridged Edition");
+        assert_eq!(first.authors, "Ann Author");
+        assert_eq!(first.narrators, "Nora Narrator");
+        assert_eq!(first.runtime_ms, Some(300 * 60_000));
+        assert_eq!(first.release_date.as_deref(), Some("2010-01-01"));
+        assert_eq!(editions[1].runtime_ms, None);
+        assert_eq!(editions[2].title, "The Book");
+        assert_eq!(editions[2].narrators, "Nora Narrator, Ned Narrator");
+    }
+
+    #[test]
+    fn editions_closest_to_the_runtime_come_first() {
+        let json: serde_json::Value = serde_json::from_str(EDITIONS_FIXTURE).expect("fixture");
+        // 12h 5m of audio: the unabridged edition, then the dramatization, then the abridged one
+        let editions = AudioService::parse_chapter_editions(&json, Some(726 * 60_000));
+        let asins: Vec<&str> = editions.iter().map(|e| e.asin.as_str()).collect();
+        assert_eq!(asins, vec!["B00UNABRIDG", "B00DRAMATIZ", "B00ABRIDGED", "B00NORUNTIM"]);
+        assert!(AudioService::parse_chapter_editions(&serde_json::json!({"products": []}), None).is_empty());
+    }
+}
@@ -0,0 +1,386 @@
+// Audiobookshelf sidecar files
+// Audiobookshelf reads `metadata.json` and `.opf` files next to the audio during a library scan.
+// They keep what MP4 atoms cannot: several authors, narrators and series, genre and tag lists.
+// Written next to the converted book; read back when a source folder contains them.
+
+use crate::error::{LecternError, Result};
+use crate::models::chapters::Chapter;
+use crate::models::{BookMetadata, SeriesEntry};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub const METADATA_JSON: &str = "metadata.json";
+pub const METADATA_OPF: &str = "metadata.opf";
+
+/// Metadata (and chapters, metadata.json only) read from a sidecar.
+#[derive(Debug, Clone, Default)]
+pub struct SidecarImport {
+    pub metadata: BookMetadata,
+    pub chapters: Vec<Chapter>,
+}
+
+/// Whether `name` is a sidecar this module reads.
+pub fn is_sidecar_file_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower == METADATA_JSON || lower.ends_with(".opf")
+}
+
+/// Read a sidecar by file name. Ok(None) for a metadata.json that is not Audiobookshelf's.
+pub fn read_sidecar(path: &str) -> Result<Option<SidecarImport>> {
+    let content = fs::read_to_string(path).map_err(|e| LecternError::io(path, e))?;
+    if path.to_lowercase().ends_with(".opf") {
+        Ok(Some(SidecarImport { metadata: parse_opf(&content)?, chapters: Vec::new() }))
+    } else {
+        parse_abs_json(&content)
+    }
+}
+
+/// Write metadata.json and metadata.opf into `dir`. Returns the written paths.
+pub fn write_sidecars(dir: &Path, book: &BookMetadata, chapters: &[Chapter]) -> Result<Vec<String>> {
+    fs::create_dir_all(dir).map_err(|e| LecternError::io(dir, e))?;
+    let mut written = Vec::new();
+    for (name, content) in [(METADATA_JSON, abs_json(book, chapters)), (METADATA_OPF, opf(book))] {
+        let path = dir.join(name);
+        fs::write(&path, content).map_err(|e| LecternError::io(&path, e))?;
+        written.push(path.to_string_lossy().to_string());
+    }
+    println!("[DEBUG] Wrote Audiobookshelf sidecars to {}", dir.display());
+    Ok(written)
+}
+
+/// Series entry from Audiobookshelf's "Name #2" string (`SeriesEntry`'s Display is the reverse).
+pub fn parse_series_label(label: &str) -> SeriesEntry {
+    match label.rsplit_once(" #") {
+        Some((name, number)) if !number.trim().is_empty() => SeriesEntry::new(name, Some(number.to_string())),
+        _ => SeriesEntry::new(label, None),
+    }
+}
+
+/// Trimmed, non-empty entries.
+fn clean_list(values: Vec<String>) -> Vec<String> {
+    values.into_iter().map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect()
+}
+
+fn abs_json(book: &BookMetadata, chapters: &[Chapter]) -> String {
+    let chapters: Vec<serde_json::Value> = chapters
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            serde_json::json!({
+                "id": i,
+                "start": c.start_time as f64 / 1000.0,
+                "end": (c.start_time + c.duration) as f64 / 1000.0,
+                "title": c.title,
+            })
+        })
+        .collect();
+    let json = serde_json::json!({
+        "tags": book.tags,
+        "chapters": chapters,
+        "title": book.title,
+        "subtitle": book.subtitle,
+        "authors": book.authors,
+        "narrators": book.narrators,
+        "series": book.series.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+        "genres": book.genres,
+        "publishedYear": book.publish_year,
+        "publishedDate": null,
+        "publisher": book.publisher,
+        "description": book.description,
+        "isbn": book.isbn,
+        "asin": book.asin,
+        "language": book.language,
+        "explicit": book.explicit.unwrap_or(false),
+        "abridged": book.abridged.unwrap_or(false),
+    });
+    serde_json::to_string_pretty(&json).unwrap_or_default()
+}
+
+fn parse_abs_json(content: &str) -> Result<Option<SidecarImport>> {
+    let json: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| LecternError::Parse(format!("Invalid metadata.json: {}", e)))?;
+    // Other tools also write a metadata.json; Audiobookshelf's has an authors array
+    if !json.get("authors").is_some_and(|a| a.is_array()) {
+        return Ok(None);
+    }
+    let text = |key: &str| {
+        json.get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    let list = |key: &str| -> Vec<String> {
+        json.get(key)
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect())
+            .unwrap_or_default()
+    };
+    let metadata = BookMetadata {
+        title: text("title").unwrap_or_default(),
+        subtitle: text("subtitle"),
+        authors: clean_list(list("authors")),
+        narrators: clean_list(list("narrators")),
+        series: clean_list(list("series")).iter().map(|s| parse_series_label(s)).collect(),
+        genres: clean_list(list("genres")),
+        tags: clean_list(list("tags")),
+        publish_year: text("publishedYear"),
+        publisher: text("publisher"),
+        description: text("description"),
+        isbn: text("isbn"),
+        asin: text("asin"),
+        language: text("language"),
+        explicit: json.get("explicit").and_then(|v| v.as_bool()),
+        abridged: json.get("abridged").and_then(|v| v.as_bool()),
+        ..Default::default()
+    };
+    let mut chapters: Vec<Chapter> = json.get("chapters")
+        .and_then(|c| c.as_array())
+        .map(|chapters| {
+            chapters.iter().filter_map(|c| {
+                let start = c.get("start")?.as_f64()?;
+                let end = c.get("end").and_then(|e| e.as_f64()).unwrap_or(start);
+                let title = c.get("title").and_then(|t| t.as_str()).unwrap_or("").to_string();
+                let start_ms = (start * 1000.0).round() as u64;
+                let end_ms = (end * 1000.0).round() as u64;
+                Some(Chapter::new(title, start_ms, end_ms.saturating_sub(start_ms)))
+            }).collect()
+        })
+        .unwrap_or_default();
+    chapters.sort_by_key(|c| c.start_time);
+    Ok(Some(SidecarImport { metadata, chapters }))
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn xml_unescape(value: &str) -> String {
+    let value = value.trim();
+    let value = value
+        .strip_prefix("<![CDATA[")
+        .and_then(|v| v.strip_suffix("]]>"))
+        .unwrap_or(value);
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// OPF 2.0 package with Dublin Core fields and Calibre's series meta (what Audiobookshelf reads).
+fn opf(book: &BookMetadata) -> String {
+    let mut lines = Vec::new();
+    let mut element = |tag: &str, attrs: &str, value: &str| {
+        if !value.trim().is_empty() {
+            lines.push(format!("    <dc:{}{}>{}</dc:{}>", tag, attrs, xml_escape(value.trim()), tag));
+        }
+    };
+    element("title", "", &book.title);
+    if let Some(ref subtitle) = book.subtitle {
+        element("subtitle", "", subtitle);
+    }
+    for author in &book.authors {
+        element("creator", " opf:role=\"aut\"", author);
+    }
+    for narrator in &book.narrators {
+        element("creator", " opf:role=\"nrt\"", narrator);
+    }
+    let optional = [
+        ("publisher", "", &book.publisher),
+        ("date", "", &book.publish_year),
+        ("description", "", &book.description),
+        ("language", "", &book.language),
+        ("identifier", " opf:scheme=\"ISBN\"", &book.isbn),
+        ("identifier", " opf:scheme=\"ASIN\"", &book.asin),
+    ];
+    for (tag, attrs, value) in optional {
+        if let Some(value) = value {
+            element(tag, attrs, value);
+        }
+    }
+    for genre in &book.genres {
+        element("subject", "", genre);
+    }
+    for tag in &book.tags {
+        element("tag", "", tag);
+    }
+    // Calibre's meta holds one series; the rest are only in metadata.json
+    if let Some(series) = book.primary_series().filter(|s| !s.name.is_empty()) {
+        lines.push(format!("    <meta name=\"calibre:series\" content=\"{}\"/>", xml_escape(&series.name)));
+        if let Some(ref number) = series.number {
+            lines.push(format!("    <meta name=\"calibre:series_index\" content=\"{}\"/>", xml_escape(number)));
+        }
+    }
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <package xmlns=\"http://www.idpf.org/2007/opf\" version=\"2.0\" unique-identifier=\"BookId\">\n\
+         \x20 <metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\" xmlns:opf=\"http://www.idpf.org/2007/opf\">\n\
+         {}\n\
+         \x20 </metadata>\n\
+         </package>\n",
+        lines.join("\n")
+    )
+}
+
+/// Value of `name="..."` in an attribute string (namespace prefix optional).
+fn attribute(attrs: &str, name: &str) -> Option<String> {
+    static ATTRIBUTE: OnceLock<Regex> = OnceLock::new();
+    let re = ATTRIBUTE.get_or_init(|| {
+        Regex::new(r#"(?:^|\s)(?:\w+:)?([\w-]+)\s*=\s*["']([^"']*)["']"#).expect("valid regex")
+    });
+    re.captures_iter(attrs)
+        .find(|c| c[1].eq_ignore_ascii_case(name))
+        .map(|c| xml_unescape(&c[2]))
+}
+
+pub fn parse_opf(content: &str) -> Result<BookMetadata> {
+    static ELEMENT: OnceLock<Regex> = OnceLock::new();
+    static META: OnceLock<Regex> = OnceLock::new();
+    let element_re = ELEMENT.get_or_init(|| Regex::new(r"(?s)<dc:(\w+)([^>]*)>(.*?)</dc:(\w+)>").expect("valid regex"));
+    let meta_re = META.get_or_init(|| Regex::new(r"<meta\s([^>]*?)/?>").expect("valid regex"));
+
+    let mut book = BookMetadata::default();
+    let (mut series, mut series_index) = (None, None);
+    for captures in element_re.captures_iter(content) {
+        let tag = captures[1].to_lowercase();
+        let attrs = &captures[2];
+        let value = xml_unescape(&captures[3]);
+        if value.is_empty() || !captures[4].eq_ignore_ascii_case(&tag) {
+            continue;
+        }
+        match tag.as_str() {
+            "title" if book.title.is_empty() => book.title = value,
+            "subtitle" => book.subtitle = Some(value),
+            "creator" => match attribute(attrs, "role").as_deref() {
+                Some("nrt") | Some("narrator") => book.narrators.push(value),
+                Some("aut") | None => book.authors.push(value),
+                _ => {}
+            },
+            "publisher" => book.publisher = Some(value),
+            "date" => book.publish_year = Some(value.chars().take(4).collect()),
+            "description" => book.description = Some(value),
+            "language" => book.language = Some(value),
+            "subject" => book.genres.push(value),
+            "tag" => book.tags.push(value),
+            "identifier" => match attribute(attrs, "scheme").map(|s| s.to_uppercase()).as_deref() {
+                Some("ISBN") => book.isbn = Some(value),
+                Some("ASIN") | Some("AUDIBLE_ASIN") => book.asin = Some(value),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    for captures in meta_re.captures_iter(content) {
+        let attrs = &captures[1];
+        match (attribute(attrs, "name").as_deref(), attribute(attrs, "content")) {
+            (Some("calibre:series"), Some(name)) if !name.is_empty() => series = Some(name),
+            (Some("calibre:series_index"), Some(index)) if !index.is_empty() => series_index = Some(index),
+            _ => {}
+        }
+    }
+    if let Some(name) = series {
+        book.series.push(SeriesEntry::new(name, series_index));
+    }
+    if book.title.is_empty() && book.authors.is_empty() {
+        return Err(LecternError::Parse("No title or author in OPF".to_string()));
+    }
+    Ok(book)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book() -> BookMetadata {
+        BookMetadata {
+            title: "The Book & Other Stories".to_string(),
+            subtitle: Some("A <Subtitle>".to_string()),
+            authors: vec!["Ann Author".to_string(), "Bob Author".to_string()],
+            narrators: vec!["Nora Narrator".to_string()],
+            series: vec![SeriesEntry::new("Saga", Some("2.5".to_string())), SeriesEntry::new("Universe", None)],
+            genres: vec!["Fantasy".to_string(), "Adventure".to_string()],
+            tags: vec!["dragons".to_string()],
+            publish_year: Some("2020".to_string()),
+            publisher: Some("Publisher".to_string()),
+            description: Some("Line one.\nLine \"two\".".to_string()),
+            isbn: Some("9780000000000".to_string()),
+            asin: Some("B000000000".to_string()),
+            language: Some("English".to_string()),
+            explicit: Some(true),
+            abridged: Some(false),
+            ..BookMetadata::default()
+        }
+    }
+
+    /// Fields both sidecars carry.
+    fn assert_same_fields(read: &BookMetadata, written: &BookMetadata) {
+        assert_eq!(read.title, written.title);
+        assert_eq!(read.subtitle, written.subtitle);
+        assert_eq!(read.authors, written.authors);
+        assert_eq!(read.narrators, written.narrators);
+        assert_eq!(read.genres, written.genres);
+        assert_eq!(read.tags, written.tags);
+        assert_eq!(read.publish_year, written.publish_year);
+        assert_eq!(read.publisher, written.publisher);
+        assert_eq!(read.description, written.description);
+        assert_eq!(read.isbn, written.isbn);
+        assert_eq!(read.asin, written.asin);
+        assert_eq!(read.language, written.language);
+    }
+
+    #[test]
+    fn written_sidecars_read_back() {
+        let dir = std::env::temp_dir().join(format!("lectern-sidecar-test-{}", std::process::id()));
+        let chapters = vec![Chapter::new("Opening".to_string(), 0, 61_500), Chapter::new("Middle".to_string(), 61_500, 120_000)];
+        let written = write_sidecars(&dir, &book(), &chapters).expect("sidecars written");
+        assert_eq!(written.len(), 2);
+
+        let json = read_sidecar(&dir.join(METADATA_JSON).to_string_lossy()).expect("readable").expect("Audiobookshelf json");
+        assert_same_fields(&json.metadata, &book());
+        assert_eq!(json.metadata.series, book().series);
+        assert_eq!((json.metadata.explicit, json.metadata.abridged), (Some(true), Some(false)));
+        let spans: Vec<(&str, u64, u64)> = json.chapters.iter().map(|c| (c.title.as_str(), c.start_time, c.duration)).collect();
+        assert_eq!(spans, vec![("Opening", 0, 61_500), ("Middle", 61_500, 120_000)]);
+
+        // The OPF only has room for the primary series
+        let opf = read_sidecar(&dir.join(METADATA_OPF).to_string_lossy()).expect("readable").expect("opf");
+        assert_same_fields(&opf.metadata, &book());
+        assert_eq!(opf.metadata.series, book().series[..1].to_vec());
+        assert!(opf.chapters.is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn opf_attributes_with_and_without_prefix() {
+        let content = r#"<?xml version="1.0"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:opf="http://www.idpf.org/2007/opf">
+    <dc:title>Title</dc:title>
+    <dc:creator opf:file-as="Author, Ann" opf:role="aut">Ann Author</dc:creator>
+    <dc:creator role='nrt'>Nora Narrator</dc:creator>
+    <dc:creator opf:role="edt">Ed Editor</dc:creator>
+    <dc:identifier opf:scheme="isbn">9780000000000</dc:identifier>
+    <dc:identifier opf:scheme="AUDIBLE_ASIN">B000000000</dc:identifier>
+    <dc:identifier opf:scheme="uuid">ignored</dc:identifier>
+    <dc:date>2019-05-01</dc:date>
+    <meta content="3" name="calibre:series_index"/>
+    <meta name="calibre:series" content="Saga &amp; Co"/>
+  </metadata>
+</package>"#;
+        let book = parse_opf(content).expect("valid opf");
+        assert_eq!(book.authors, vec!["Ann Author"]);
+        assert_eq!(book.narrators, vec!["Nora Narrator"]);
+        assert_eq!(book.isbn.as_deref(), Some("9780000000000"));
+        assert_eq!(book.asin.as_deref(), Some("B000000000"));
+        assert_eq!(book.publish_year.as_deref(), Some("2019"));
+        assert_eq!(book.series, vec![SeriesEntry::new("Saga & Co", Some("3".to_string()))]);
+        assert_eq!(attribute(r#" opf:file-as="Author, Ann" opf:role="aut""#, "as"), None);
+    }
+}
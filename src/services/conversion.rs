@@ -9,7 +9,8 @@ use tokio::process::Command as TokioCommand;
 use std::fs;
 use std::io::Write;
 use tempfile::TempDir;
-use anyhow::{Result, Context};
+use crate::error::{LecternError, Result};
+use crate::services::cover_edit::{prepare_cover, CoverEdit};
 
 // Data structures
 #[derive(Debug, Clone)]
@@ -20,16 +21,27 @@ pub struct ConversionConfig {
     pub cover_image_path: Option<String>,
     pub chapters: Vec<Chapter>,
     pub audio_bitrate: Option<String>, // e.g., "128k"
-    pub audio_codec: String, // "aac", "copy", "libopus"
+    pub audio_codec: String, // "aac", "copy", "opus" (M4B only; other formats use their own codec)
     pub audio_channels: Option<u32>,
+    pub sample_rate: Option<u32>, // Resample to this rate; None keeps the source rate
+    pub aac_profile: AacProfile,
+    pub output_format: OutputFormat,
     pub processing_options: ProcessingOptions,
 }
 
 #[derive(Debug, Clone)]
 pub struct ProcessingOptions {
-    pub normalize_volume: bool,
+    pub normalization: Normalization,
+    pub trim: TrimOptions,
+    pub split: SplitMode,
+    pub encode_strategy: EncodeStrategy,
     pub rewrite_chapters: bool,
-    pub max_cover_size: u32,
+    /// Input files left out of a directory input (e.g. failed the input check).
+    pub skip_files: Vec<String>,
+    /// Write Audiobookshelf's metadata.json and metadata.opf next to the output.
+    pub write_sidecars: bool,
+    /// Crop, size and JPEG quality of the embedded cover.
+    pub cover_edit: CoverEdit,
     #[allow(dead_code)]
     pub use_temp_dir: bool,
     pub atomic_write: bool,
@@ -38,15 +50,432 @@ pub struct ProcessingOptions {
 impl Default for ProcessingOptions {
     fn default() -> Self {
         Self {
-            normalize_volume: true,
+            normalization: Normalization::SPEECHNORM_DEFAULT,
+            trim: TrimOptions::default(),
+            split: SplitMode::None,
+            encode_strategy: EncodeStrategy::SingleProcess,
             rewrite_chapters: false,
-            max_cover_size: 1000,
+            skip_files: Vec::new(),
+            write_sidecars: false,
+            cover_edit: CoverEdit::default(),
             use_temp_dir: true,
             atomic_write: true,
         }
     }
 }
 
+/// Audio removed from the ends of the book (e.g. Audible's "This is Audible" brand clips).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TrimOptions {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// Also remove silence left at the start/end after the fixed trim.
+    pub silence: bool,
+}
+
+impl TrimOptions {
+    pub fn is_enabled(&self) -> bool {
+        self.start_ms > 0 || self.end_ms > 0 || self.silence
+    }
+}
+
+/// The part of the book that is kept, resolved from `TrimOptions` before encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrimRange {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub total_ms: u64,
+}
+
+impl TrimRange {
+    /// `atrim` filter keeping [start_ms, end_ms), timestamps reset to 0.
+    fn filter(&self) -> String {
+        format!(
+            "atrim=start={:.3}:end={:.3},asetpts=PTS-STARTPTS",
+            self.start_ms as f64 / 1000.0,
+            self.end_ms as f64 / 1000.0
+        )
+    }
+}
+
+/// Trim and normalization filters joined into one chain.
+fn audio_filter_chain(trim: Option<&TrimRange>, normalization: Option<String>) -> Option<String> {
+    match (trim.map(|t| t.filter()), normalization) {
+        (Some(trim), Some(norm)) => Some(format!("{},{}", trim, norm)),
+        (trim, norm) => trim.or(norm),
+    }
+}
+
+/// Volume normalization strategy applied during the encode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Normalization {
+    None,
+    /// Dynamic speech normalizer (ffmpeg `speechnorm`): expansion, raise and peak parameters.
+    SpeechNorm { expansion: f64, raise: f64, peak: f64 },
+    /// EBU R128 two-pass `loudnorm`: the first pass measures the whole book, the second applies
+    /// linear gain towards the target integrated loudness (LUFS), true peak (dBTP) and range (LU).
+    Loudnorm { target_i: f64, true_peak: f64, lra: f64 },
+}
+
+impl Normalization {
+    pub const SPEECHNORM_DEFAULT: Normalization = Normalization::SpeechNorm { expansion: 6.5, raise: 0.0001, peak: 0.95 };
+    pub const LOUDNORM_DEFAULT: Normalization = Normalization::Loudnorm { target_i: -18.0, true_peak: -1.5, lra: 11.0 };
+
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, Normalization::None)
+    }
+
+    /// Audio filter for the final encode. Loudnorm uses the first-pass measurement when given
+    /// (linear gain); without one it falls back to single-pass dynamic mode.
+    pub fn filter(&self, measured: Option<&LoudnessMeasurement>) -> Option<String> {
+        match *self {
+            Normalization::None => None,
+            Normalization::SpeechNorm { expansion, raise, peak } => {
+                Some(format!("speechnorm=e={}:r={}:p={}:l=1", expansion, raise, peak))
+            }
+            Normalization::Loudnorm { target_i, true_peak, lra } => {
+                let mut filter = format!("loudnorm=I={}:TP={}:LRA={}", target_i, true_peak, lra);
+                if let Some(m) = measured {
+                    filter.push_str(&format!(
+                        ":measured_I={}:measured_TP={}:measured_LRA={}:measured_thresh={}:offset={}:linear=true",
+                        m.input_i, m.input_tp, m.input_lra, m.input_thresh, m.target_offset
+                    ));
+                }
+                Some(filter)
+            }
+        }
+    }
+}
+
+/// Normalization choice shown in the Convert view (parameters are edited separately).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NormalizationMode {
+    None,
+    SpeechNorm,
+    Loudnorm,
+}
+
+impl NormalizationMode {
+    pub const ALL: [NormalizationMode; 3] = [
+        NormalizationMode::None,
+        NormalizationMode::SpeechNorm,
+        NormalizationMode::Loudnorm,
+    ];
+}
+
+impl std::fmt::Display for NormalizationMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NormalizationMode::None => write!(f, "None"),
+            NormalizationMode::SpeechNorm => write!(f, "Speech normalizer"),
+            NormalizationMode::Loudnorm => write!(f, "EBU R128 (two-pass)"),
+        }
+    }
+}
+
+/// Output container/codec profile. Each one embeds tags, chapters and cover the way its
+/// container supports: MP4 atoms (M4B), Vorbis comments (Ogg Opus, FLAC), ID3v2 CHAP/CTOC (MP3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    M4b,
+    OggOpus,
+    Mp3,
+    Flac,
+}
+
+impl OutputFormat {
+    pub const ALL: [OutputFormat; 4] = [OutputFormat::M4b, OutputFormat::OggOpus, OutputFormat::Mp3, OutputFormat::Flac];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::M4b => "m4b",
+            OutputFormat::OggOpus => "opus",
+            OutputFormat::Mp3 => "mp3",
+            OutputFormat::Flac => "flac",
+        }
+    }
+
+    /// ffmpeg muxer, passed explicitly so temp names (".tmp.opus") never matter.
+    /// M4B keeps ffmpeg's extension-based choice (ipod/mp4).
+    fn muxer(&self) -> Option<&'static str> {
+        match self {
+            OutputFormat::M4b => None,
+            OutputFormat::OggOpus => Some("ogg"),
+            OutputFormat::Mp3 => Some("mp3"),
+            OutputFormat::Flac => Some("flac"),
+        }
+    }
+
+    /// Codec for this format; M4B honours the codec chosen in the config.
+    fn codec(&self, configured: &str) -> &'static str {
+        match self {
+            OutputFormat::M4b => match configured {
+                "copy" => "copy",
+                "opus" => "libopus",
+                _ => "aac",
+            },
+            OutputFormat::OggOpus => "libopus",
+            OutputFormat::Mp3 => "libmp3lame",
+            OutputFormat::Flac => "flac",
+        }
+    }
+
+    /// Whether the cover goes in as an attached picture stream (Ogg uses a METADATA_BLOCK_PICTURE comment).
+    fn has_cover_stream(&self) -> bool {
+        !matches!(self, OutputFormat::OggOpus)
+    }
+
+    /// `path` with this format's extension.
+    pub fn apply_extension(&self, path: &str) -> String {
+        Path::new(path).with_extension(self.extension()).to_string_lossy().to_string()
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::M4b => write!(f, "M4B (AAC)"),
+            OutputFormat::OggOpus => write!(f, "Ogg Opus"),
+            OutputFormat::Mp3 => write!(f, "MP3 (ID3 chapters)"),
+            OutputFormat::Flac => write!(f, "FLAC"),
+        }
+    }
+}
+
+/// AAC profile. HE-AAC needs ffmpeg built with libfdk_aac; without it LC is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AacProfile {
+    #[default]
+    Lc,
+    HeV1,
+    HeV2,
+}
+
+impl AacProfile {
+    pub const ALL: [AacProfile; 3] = [AacProfile::Lc, AacProfile::HeV1, AacProfile::HeV2];
+
+    /// Profile name as reported by ffprobe.
+    fn probe_name(&self) -> &'static str {
+        match self {
+            AacProfile::Lc => "LC",
+            AacProfile::HeV1 => "HE-AAC",
+            AacProfile::HeV2 => "HE-AACv2",
+        }
+    }
+
+    /// `-profile:a` value for libfdk_aac.
+    fn fdk_profile(&self) -> &'static str {
+        match self {
+            AacProfile::Lc => "aac_low",
+            AacProfile::HeV1 => "aac_he",
+            AacProfile::HeV2 => "aac_he_v2",
+        }
+    }
+}
+
+impl std::fmt::Display for AacProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AacProfile::Lc => write!(f, "AAC-LC"),
+            AacProfile::HeV1 => write!(f, "HE-AAC v1"),
+            AacProfile::HeV2 => write!(f, "HE-AAC v2"),
+        }
+    }
+}
+
+/// Whether the installed ffmpeg has the libfdk_aac encoder (needed for HE-AAC).
+/// Probed once with `ffmpeg -encoders`.
+pub fn fdk_aac_available() -> bool {
+    static AVAILABLE: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        let available = StdCommand::new("ffmpeg")
+            .args(["-hide_banner", "-encoders"])
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).lines().any(|l| l.split_whitespace().nth(1) == Some("libfdk_aac")))
+            .unwrap_or(false);
+        println!("[DEBUG] libfdk_aac available: {}", available);
+        available
+    })
+}
+
+/// Rates libopus accepts.
+const OPUS_SAMPLE_RATES: [u32; 5] = [8000, 12000, 16000, 24000, 48000];
+
+/// Encoder resolved from the config: the codec ffmpeg should use, its profile arguments and the
+/// output sample rate, after falling back for a missing encoder or an unsupported combination.
+#[derive(Debug, Clone)]
+struct AudioEncoder {
+    codec: &'static str,
+    /// Effective AAC profile (LC for non-AAC codecs).
+    profile: AacProfile,
+    /// Extra encoder arguments (`-profile:a ...`).
+    args: Vec<String>,
+    /// Explicit output sample rate.
+    sample_rate: Option<u32>,
+    /// Fallbacks taken, shown with the conversion result.
+    warnings: Vec<String>,
+}
+
+fn resolve_encoder(config: &ConversionConfig, input_params: Option<&AudioParams>) -> AudioEncoder {
+    let mut codec = config.output_format.codec(&config.audio_codec);
+    let mut profile = AacProfile::Lc;
+    let mut args = Vec::new();
+    let mut warnings = Vec::new();
+
+    if codec == "aac" && config.aac_profile != AacProfile::Lc {
+        profile = config.aac_profile;
+        let channels = config.audio_channels.or(input_params.map(|p| p.channels)).unwrap_or(2);
+        if profile == AacProfile::HeV2 && channels != 2 {
+            // Parametric stereo needs a stereo signal
+            warnings.push("HE-AAC v2 needs stereo audio; encoded as HE-AAC v1".to_string());
+            profile = AacProfile::HeV1;
+        }
+        if fdk_aac_available() {
+            codec = "libfdk_aac";
+            args.push("-profile:a".to_string());
+            args.push(profile.fdk_profile().to_string());
+        } else {
+            warnings.push(format!("{} needs ffmpeg built with libfdk_aac, which is not available; encoded as AAC-LC", profile));
+            profile = AacProfile::Lc;
+        }
+    }
+
+    let mut sample_rate = config.sample_rate;
+    if codec == "libopus" {
+        if let Some(rate) = sample_rate.filter(|r| !OPUS_SAMPLE_RATES.contains(r)) {
+            warnings.push(format!("Opus does not support {} Hz; encoded at 48000 Hz", rate));
+            sample_rate = Some(48000);
+        }
+    }
+
+    AudioEncoder { codec, profile, args, sample_rate, warnings }
+}
+
+/// How multi-file books are encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeStrategy {
+    /// One ffmpeg process decodes and encodes every file in sequence.
+    SingleProcess,
+    /// Each file is encoded to AAC in its own process (one per CPU core), then the segments are
+    /// joined with `-c copy`. Only used for directories encoded to AAC.
+    ParallelFiles,
+}
+
+impl EncodeStrategy {
+    pub const ALL: [EncodeStrategy; 2] = [EncodeStrategy::SingleProcess, EncodeStrategy::ParallelFiles];
+}
+
+impl std::fmt::Display for EncodeStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncodeStrategy::SingleProcess => write!(f, "Single process"),
+            EncodeStrategy::ParallelFiles => write!(f, "Parallel per file"),
+        }
+    }
+}
+
+/// Split the output into several files, cutting only on chapter boundaries.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SplitMode {
+    None,
+    MaxDurationMs(u64),
+    MaxBytes(u64),
+}
+
+impl SplitMode {
+    /// Longest allowed part in ms, given the encoded book's total duration and size
+    /// (size limits are converted using the book's average bitrate).
+    pub fn max_part_ms(&self, total_ms: u64, total_bytes: u64) -> Option<u64> {
+        match *self {
+            SplitMode::None => None,
+            SplitMode::MaxDurationMs(ms) => Some(ms.max(1)),
+            SplitMode::MaxBytes(bytes) => {
+                if total_bytes == 0 {
+                    return None;
+                }
+                Some(((bytes as f64 / total_bytes as f64) * total_ms as f64).max(1.0) as u64)
+            }
+        }
+    }
+}
+
+/// Split choice shown in the Convert view (the limit is edited separately).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitBy {
+    None,
+    Duration,
+    Size,
+}
+
+impl SplitBy {
+    pub const ALL: [SplitBy; 3] = [SplitBy::None, SplitBy::Duration, SplitBy::Size];
+}
+
+impl std::fmt::Display for SplitBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SplitBy::None => write!(f, "Single file"),
+            SplitBy::Duration => write!(f, "Max duration (hours)"),
+            SplitBy::Size => write!(f, "Max size (MB)"),
+        }
+    }
+}
+
+/// How the start/end trim is chosen in the Convert view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrimMode {
+    None,
+    Manual,
+    AudibleBrand,
+}
+
+impl TrimMode {
+    pub const ALL: [TrimMode; 3] = [TrimMode::None, TrimMode::Manual, TrimMode::AudibleBrand];
+}
+
+impl std::fmt::Display for TrimMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrimMode::None => write!(f, "No trim"),
+            TrimMode::Manual => write!(f, "Trim seconds"),
+            TrimMode::AudibleBrand => write!(f, "Audible intro/outro"),
+        }
+    }
+}
+
+/// Values reported by the loudnorm measurement pass (LUFS / dBTP / LU).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoudnessMeasurement {
+    pub input_i: f64,
+    pub input_tp: f64,
+    pub input_lra: f64,
+    pub input_thresh: f64,
+    pub target_offset: f64,
+}
+
+/// Outcome of a successful conversion.
+#[derive(Debug, Clone)]
+pub struct ConversionReport {
+    /// Written files: one, or one per part when splitting.
+    pub output_paths: Vec<String>,
+    pub normalization: Normalization,
+    /// First-pass measurement when two-pass loudnorm was used.
+    pub loudness: Option<LoudnessMeasurement>,
+    /// Whether audio was copied or re-encoded, and why.
+    pub plan: EncodePlan,
+    /// Kept range when the start/end was trimmed.
+    pub trim: Option<TrimRange>,
+    /// Encoder fallbacks taken (e.g. HE-AAC without libfdk_aac).
+    pub warnings: Vec<String>,
+    /// Checks of the written file(s) against what was asked for.
+    pub verification: crate::services::verify::VerificationReport,
+    /// Audiobookshelf sidecar files written next to the output.
+    pub sidecars: Vec<String>,
+}
+
 #[derive(Debug)]
 pub enum InputType {
     SingleM4B(String),
@@ -54,9 +483,10 @@ pub enum InputType {
     SingleAudioFile(String),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AudioParams {
     pub codec: String,
+    pub profile: Option<String>, // e.g. "LC", "HE-AAC"; copied streams must share it
     pub sample_rate: u32,
     pub channels: u32,
     pub bitrate: Option<u32>,
@@ -69,6 +499,58 @@ pub enum ConcatMethod {
     FilterComplex,  // Slower, re-encodes for gapless
 }
 
+/// How the inputs get into the output, decided by `plan_encode` from a probe of every input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EncodePlan {
+    /// Every input already has the output codec and matching parameters: lossless `-c:a copy`.
+    StreamCopy { codec: String, sample_rate: u32, channels: u32 },
+    /// Most inputs are copied; the `reencode` files are first encoded to match them.
+    PartialCopy { codec: String, sample_rate: u32, channels: u32, reencode: Vec<String> },
+    /// Inputs share parameters but need a new encode; joined with the concat demuxer.
+    Reencode { reason: String },
+    /// Inputs differ from each other: decoded through filter_complex and re-encoded.
+    ReencodeFiltered { reason: String },
+}
+
+impl EncodePlan {
+    fn concat_method(&self) -> ConcatMethod {
+        match self {
+            EncodePlan::ReencodeFiltered { .. } => ConcatMethod::FilterComplex,
+            _ => ConcatMethod::Demuxer,
+        }
+    }
+
+    pub fn is_copy(&self) -> bool {
+        matches!(self, EncodePlan::StreamCopy { .. } | EncodePlan::PartialCopy { .. })
+    }
+}
+
+impl std::fmt::Display for EncodePlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncodePlan::StreamCopy { codec, sample_rate, channels } => write!(
+                f,
+                "Stream copy: all inputs are {} {} Hz, {} ch, matching the output, so audio was not re-encoded",
+                codec, sample_rate, channels
+            ),
+            EncodePlan::PartialCopy { codec, sample_rate, channels, reencode } => write!(
+                f,
+                "Stream copy with {} re-encoded file(s): the other inputs are {} {} Hz, {} ch; {} did not match",
+                reencode.len(),
+                codec,
+                sample_rate,
+                channels,
+                reencode.iter()
+                    .map(|p| Path::new(p).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| p.clone()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            EncodePlan::Reencode { reason } => write!(f, "Re-encoded: {}", reason),
+            EncodePlan::ReencodeFiltered { reason } => write!(f, "Re-encoded (filter_complex): {}", reason),
+        }
+    }
+}
+
 // Phase 1: Input Detection & Preparation
 
 /// Detect the type of input (M4B file, directory, or single audio file)
@@ -76,7 +558,7 @@ pub fn detect_input_type(path: &str) -> Result<InputType> {
     let path = Path::new(path);
     
     if !path.exists() {
-        return Err(anyhow::anyhow!("Input path does not exist: {}", path.display()));
+        return Err(LecternError::io(path, std::io::Error::new(std::io::ErrorKind::NotFound, "input path does not exist")));
     }
     
     if path.is_dir() {
@@ -96,11 +578,30 @@ pub fn detect_input_type(path: &str) -> Result<InputType> {
             if audio_exts.contains(&ext.as_str()) {
                 Ok(InputType::SingleAudioFile(path.to_string_lossy().to_string()))
             } else {
-                Err(anyhow::anyhow!("Unsupported file type: {}", ext))
+                Err(LecternError::Other(format!("Unsupported file type: {}", ext)))
             }
         }
     } else {
-        Err(anyhow::anyhow!("Invalid input path: {}", path.display()))
+        Err(LecternError::Other(format!("Invalid input path: {}", path.display())))
+    }
+}
+
+/// Input type for a conversion, without the files the user chose to skip.
+fn config_input_type(config: &ConversionConfig) -> Result<InputType> {
+    let skip = &config.processing_options.skip_files;
+    match detect_input_type(&config.input_path)? {
+        InputType::Directory(files) if !skip.is_empty() => {
+            let files: Vec<String> = files.into_iter().filter(|f| !skip.contains(f)).collect();
+            if files.is_empty() {
+                return Err(LecternError::Other("All input files were skipped".to_string()));
+            }
+            println!("[DEBUG] Skipping {} input file(s)", skip.len());
+            Ok(InputType::Directory(files))
+        }
+        InputType::SingleM4B(path) | InputType::SingleAudioFile(path) if skip.contains(&path) => {
+            Err(LecternError::Other(format!("The only input file was skipped: {}", path)))
+        }
+        other => Ok(other),
     }
 }
 
@@ -108,7 +609,7 @@ pub fn detect_input_type(path: &str) -> Result<InputType> {
 pub fn collect_audio_files(dir: &str) -> Result<Vec<String>> {
     let dir_path = Path::new(dir);
     if !dir_path.is_dir() {
-        return Err(anyhow::anyhow!("Not a directory: {}", dir));
+        return Err(LecternError::Other(format!("Not a directory: {}", dir)));
     }
     
     let audio_exts = ["mp3", "aac", "wav", "flac", "m4a", "m4b", "ogg", "opus"];
@@ -116,9 +617,8 @@ pub fn collect_audio_files(dir: &str) -> Result<Vec<String>> {
     
     println!("[DEBUG] collect_audio_files: scanning directory: {}", dir);
     
-    for entry in fs::read_dir(dir_path)
-        .with_context(|| format!("Failed to read directory: {}", dir))? {
-        let entry = entry?;
+    for entry in fs::read_dir(dir_path).map_err(|e| LecternError::io(dir_path, e))? {
+        let entry = entry.map_err(|e| LecternError::io(dir_path, e))?;
         let path = entry.path();
         
         if path.is_file() {
@@ -139,7 +639,7 @@ pub fn collect_audio_files(dir: &str) -> Result<Vec<String>> {
     println!("[DEBUG] collect_audio_files: found {} audio files", files.len());
     
     if files.is_empty() {
-        return Err(anyhow::anyhow!("No audio files found in directory: {}", dir));
+        return Err(LecternError::NotFound(format!("No audio files found in directory: {}", dir)));
     }
     
     Ok(files)
@@ -156,24 +656,24 @@ pub fn probe_audio_file(path: &str) -> Result<AudioParams> {
             path,
         ])
         .output()
-        .with_context(|| format!("Failed to execute ffprobe on: {}", path))?;
+        .map_err(|e| LecternError::spawn("ffprobe", e))?;
     
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow::anyhow!("ffprobe failed: {}", stderr));
+        return Err(LecternError::Ffprobe { path: path.to_string(), message: stderr.trim().to_string() });
     }
     
     let json: serde_json::Value = serde_json::from_slice(&output.stdout)
-        .with_context(|| "Failed to parse ffprobe JSON output")?;
+        .map_err(|e| LecternError::Ffprobe { path: path.to_string(), message: format!("invalid JSON output: {}", e) })?;
     
     // Find audio stream
     let streams = json.get("streams")
         .and_then(|s| s.as_array())
-        .ok_or_else(|| anyhow::anyhow!("No streams found in ffprobe output"))?;
+        .ok_or_else(|| LecternError::Ffprobe { path: path.to_string(), message: "no streams in output".to_string() })?;
     
     let audio_stream = streams.iter()
         .find(|s| s.get("codec_type").and_then(|c| c.as_str()) == Some("audio"))
-        .ok_or_else(|| anyhow::anyhow!("No audio stream found"))?;
+        .ok_or_else(|| LecternError::Ffprobe { path: path.to_string(), message: "no audio stream".to_string() })?;
     
     let codec = audio_stream.get("codec_name")
         .and_then(|c| c.as_str())
@@ -219,8 +719,14 @@ pub fn probe_audio_file(path: &str) -> Result<AudioParams> {
         .map(|d| (d * 1000.0).round() as u64)
         .unwrap_or(0);
     
+    let profile = audio_stream.get("profile")
+        .and_then(|p| p.as_str())
+        .filter(|p| !p.is_empty() && *p != "unknown")
+        .map(|p| p.to_string());
+    
     Ok(AudioParams {
         codec,
+        profile,
         sample_rate,
         channels,
         bitrate,
@@ -238,77 +744,97 @@ pub fn get_total_duration(files: &[String]) -> Result<u64> {
     Ok(total_ms)
 }
 
-/// Determine which concat method to use
-pub fn should_use_concat_demuxer(files: &[String], normalize_volume: bool) -> Result<ConcatMethod> {
-    // If volume normalization is enabled, always use FilterComplex (must re-encode anyway)
-    if normalize_volume {
-        return Ok(ConcatMethod::FilterComplex);
-    }
-    
+/// (codec, profile, sample rate, channels): inputs with equal keys can be joined without re-encoding.
+type ParamsKey = (String, Option<String>, u32, u32);
+
+/// Decide between stream copy and re-encoding. `ffmpeg_codec` is the output encoder ("copy"
+/// when the user asked for stream copy). Inputs are copied when they already have the output
+/// codec, profile, sample rate and channels and nothing requires decoding (normalization, a chosen
+/// bitrate). For AAC output, when at least half the inputs qualify the rest are re-encoded to
+/// match them and everything is still joined without touching the copied files.
+/// `profile` is the AAC profile of the output (LC for other codecs).
+pub fn plan_encode(files: &[String], ffmpeg_codec: &str, profile: AacProfile, config: &ConversionConfig) -> Result<EncodePlan> {
     if files.is_empty() {
-        return Err(anyhow::anyhow!("No files provided"));
+        return Err(LecternError::Other("No files provided".to_string()));
+    }
+    let params = files.iter().map(|f| probe_audio_file(f)).collect::<Result<Vec<_>>>()?;
+    let key = |p: &AudioParams| -> ParamsKey { (p.codec.clone(), p.profile.clone(), p.sample_rate, p.channels) };
+    let all_match = params.iter().all(|p| key(p) == key(&params[0]));
+    let first = &params[0];
+    
+    // Explicit copy: only possible when the inputs can be joined as they are
+    if ffmpeg_codec == "copy" {
+        if !all_match {
+            return Err(LecternError::Other(
+                "Inputs differ in codec, sample rate or channels and cannot be stream copied; choose a codec to re-encode".to_string(),
+            ));
+        }
+        return Ok(EncodePlan::StreamCopy { codec: first.codec.clone(), sample_rate: first.sample_rate, channels: first.channels });
     }
     
-    // Probe first file to get reference parameters
-    let first_params = probe_audio_file(&files[0])?;
+    let mismatch = if all_match { EncodePlan::Reencode { reason: String::new() } } else { EncodePlan::ReencodeFiltered { reason: String::new() } };
+    let reencode = |reason: String| match mismatch {
+        EncodePlan::Reencode { .. } => Ok(EncodePlan::Reencode { reason }),
+        _ => Ok(EncodePlan::ReencodeFiltered { reason: format!("{}; inputs also differ from each other", reason) }),
+    };
+    if config.processing_options.normalization.is_enabled() {
+        return reencode("normalization needs decoded audio".to_string());
+    }
+    if config.processing_options.trim.is_enabled() {
+        return reencode("trimming needs decoded audio".to_string());
+    }
+    if config.audio_bitrate.is_some() {
+        return reencode("a fixed bitrate was chosen".to_string());
+    }
+    let output_codec = match ffmpeg_codec {
+        "libopus" => "opus",
+        "libmp3lame" => "mp3",
+        "libfdk_aac" => "aac",
+        other => other,
+    };
+    let copyable = |p: &AudioParams| {
+        p.codec == output_codec
+            && config.audio_channels.is_none_or(|c| c == p.channels)
+            && config.sample_rate.is_none_or(|r| r == p.sample_rate)
+            && (output_codec != "aac" || p.profile.as_deref().unwrap_or("LC") == profile.probe_name())
+    };
     
-    // Check if all files have matching parameters
-    for file in files.iter().skip(1) {
-        let params = probe_audio_file(file)?;
-        
-        if params.codec != first_params.codec
-            || params.sample_rate != first_params.sample_rate
-            || params.channels != first_params.channels {
-            // Parameters differ - use FilterComplex for gapless playback
-            return Ok(ConcatMethod::FilterComplex);
+    // Reference: the most common parameter set among inputs that already have the output codec
+    let mut counts: Vec<(ParamsKey, usize)> = Vec::new();
+    for p in params.iter().filter(|p| copyable(p)) {
+        match counts.iter_mut().find(|(k, _)| *k == key(p)) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((key(p), 1)),
         }
     }
-    
-    // All parameters match - can use fast concat demuxer
-    Ok(ConcatMethod::Demuxer)
+    let Some((reference, matching)) = counts.into_iter().max_by_key(|(_, n)| *n) else {
+        return reencode(format!("inputs are {}, output needs {}", first.codec, output_codec));
+    };
+    let (codec, _, sample_rate, channels) = reference.clone();
+    if matching == files.len() {
+        return Ok(EncodePlan::StreamCopy { codec, sample_rate, channels });
+    }
+    // Segments come from ffmpeg's AAC encoder, which only produces AAC-LC
+    let lc = reference.1.as_deref().is_none_or(|profile| profile == "LC");
+    if output_codec == "aac" && lc && matching * 2 >= files.len() {
+        let reencode = files.iter().zip(&params)
+            .filter(|(_, p)| key(p) != reference)
+            .map(|(f, _)| f.clone())
+            .collect();
+        return Ok(EncodePlan::PartialCopy { codec, sample_rate, channels, reencode });
+    }
+    reencode(format!("only {} of {} inputs match the output format", matching, files.len()))
 }
 
 // Phase 2: Metadata File Generation
 
 /// Generate FFMETADATA1 file content
-pub fn generate_ffmetadata(book: &BookMetadata, chapters: &[Chapter]) -> String {
+pub fn generate_ffmetadata(book: &BookMetadata, chapters: &[Chapter], format: OutputFormat) -> String {
     let mut metadata = String::from(";FFMETADATA1\n");
     
-    // Add metadata fields
-    if !book.title.is_empty() {
-        metadata.push_str(&format!("title={}\n", escape_metadata_value(&book.title)));
-    }
-    if !book.author.is_empty() {
-        metadata.push_str(&format!("artist={}\n", escape_metadata_value(&book.author)));
-    }
-    if let Some(ref series) = book.series {
-        metadata.push_str(&format!("album={}\n", escape_metadata_value(series)));
-    }
-    if let Some(ref genre) = book.genre {
-        metadata.push_str(&format!("genre={}\n", escape_metadata_value(genre)));
-    }
-    if let Some(ref year) = book.publish_year {
-        metadata.push_str(&format!("date={}\n", escape_metadata_value(year)));
-    }
-    if let Some(ref publisher) = book.publisher {
-        metadata.push_str(&format!("publisher={}\n", escape_metadata_value(publisher)));
-    }
-    if let Some(ref description) = book.description {
-        metadata.push_str(&format!("comment={}\n", escape_metadata_value(description)));
-    }
-    
-    // Add custom tags
-    if let Some(ref narrator) = book.narrator {
-        metadata.push_str(&format!("narrator={}\n", escape_metadata_value(narrator)));
-    }
-    if let Some(ref isbn) = book.isbn {
-        metadata.push_str(&format!("isbn={}\n", escape_metadata_value(isbn)));
-    }
-    if let Some(ref asin) = book.asin {
-        metadata.push_str(&format!("asin={}\n", escape_metadata_value(asin)));
-    }
-    if let Some(ref language) = book.language {
-        metadata.push_str(&format!("language={}\n", escape_metadata_value(language)));
+    // Tags as mapped for the output format (see services/tags.rs)
+    for (key, value) in crate::services::tags::ffmpeg_tags(book, format) {
+        metadata.push_str(&format!("{}={}\n", key, escape_metadata_value(&value)));
     }
     
     metadata.push('\n');
@@ -368,10 +894,54 @@ pub fn generate_chapters_from_files(files: &[String]) -> Result<Vec<Chapter>> {
 
 /// Write metadata file to temporary location
 pub fn write_metadata_file(metadata: &str, temp_dir: &Path) -> Result<PathBuf> {
-    let mut file = fs::File::create(temp_dir.join("metadata.txt"))?;
-    file.write_all(metadata.as_bytes())?;
-    file.sync_all()?;
-    Ok(temp_dir.join("metadata.txt"))
+    let path = temp_dir.join("metadata.txt");
+    write_file_synced(&path, metadata.as_bytes())?;
+    Ok(path)
+}
+
+/// Write `contents` to `path` and flush it to disk
+fn write_file_synced(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut file = fs::File::create(path).map_err(|e| LecternError::io(path, e))?;
+    file.write_all(contents).map_err(|e| LecternError::io(path, e))?;
+    file.sync_all().map_err(|e| LecternError::io(path, e))?;
+    Ok(())
+}
+
+/// Base64 FLAC picture block for the METADATA_BLOCK_PICTURE Vorbis comment (front cover).
+fn vorbis_picture_comment(cover_path: &Path) -> Result<String> {
+    let data = fs::read(cover_path).map_err(|e| LecternError::io(cover_path, e))?;
+    let mime = match image::guess_format(&data) {
+        Ok(image::ImageFormat::Png) => "image/png",
+        _ => "image/jpeg",
+    };
+    let (width, height) = image::image_dimensions(cover_path).unwrap_or((0, 0));
+    let mut block = Vec::with_capacity(data.len() + 64);
+    block.extend_from_slice(&3u32.to_be_bytes()); // Picture type: front cover
+    block.extend_from_slice(&(mime.len() as u32).to_be_bytes());
+    block.extend_from_slice(mime.as_bytes());
+    block.extend_from_slice(&0u32.to_be_bytes()); // Description length
+    block.extend_from_slice(&width.to_be_bytes());
+    block.extend_from_slice(&height.to_be_bytes());
+    block.extend_from_slice(&24u32.to_be_bytes()); // Color depth
+    block.extend_from_slice(&0u32.to_be_bytes()); // Indexed colors
+    block.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    block.extend_from_slice(&data);
+    Ok(base64_encode(&block))
+}
+
+/// Standard base64 with padding.
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    out
 }
 
 /// Escape filename for concat file format
@@ -390,15 +960,13 @@ pub fn create_concat_file(files: &[String], temp_dir: &Path) -> Result<PathBuf>
     }
     
     let concat_path = temp_dir.join("concat_list.txt");
-    let mut file = fs::File::create(&concat_path)?;
-    file.write_all(concat_content.as_bytes())?;
-    file.sync_all()?;
+    write_file_synced(&concat_path, concat_content.as_bytes())?;
     
     Ok(concat_path)
 }
 
 /// Create filter complex script for long file lists
-pub fn create_filter_complex_script(files: &[String], temp_dir: &Path, normalize_volume: bool) -> Result<PathBuf> {
+pub fn create_filter_complex_script(files: &[String], temp_dir: &Path, audio_filter: Option<&str>) -> Result<PathBuf> {
     let mut script = String::new();
     
     // Build concat filter: [0:a][1:a][2:a]...concat=n=N:v=0:a=1[outa]
@@ -410,55 +978,19 @@ pub fn create_filter_complex_script(files: &[String], temp_dir: &Path, normalize
     script.push_str(&inputs.join(""));
     script.push_str(&format!("concat=n={}:v=0:a=1", num_files));
     
-    if normalize_volume {
-        script.push_str(",speechnorm=e=6.5:r=0.0001:l=1");
+    if let Some(filter) = audio_filter {
+        script.push(',');
+        script.push_str(filter);
     }
     
     script.push_str("[outa]");
     
     let script_path = temp_dir.join("filter_script.txt");
-    let mut file = fs::File::create(&script_path)?;
-    file.write_all(script.as_bytes())?;
-    file.sync_all()?;
+    write_file_synced(&script_path, script.as_bytes())?;
     
     Ok(script_path)
 }
 
-/// Scale cover image if needed
-pub async fn scale_cover_image(input: &str, max_size: u32, temp_dir: &Path) -> Result<PathBuf> {
-    // Check if image needs scaling (would need image crate to get dimensions)
-    // For now, just copy to temp dir if max_size is set
-    let _input_path = Path::new(input);
-    let output_path = temp_dir.join("cover_scaled.jpg");
-    
-    if max_size > 0 {
-        // Use ffmpeg to scale
-        let status = TokioCommand::new("ffmpeg")
-            .arg("-i")
-            .arg(input)
-            .arg("-vf")
-            .arg(&format!("scale={}:{}:force_original_aspect_ratio=decrease", max_size, max_size))
-            .arg("-y") // Overwrite
-            .arg(&output_path)
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status()
-            .await?;
-        
-        if status.success() {
-            Ok(output_path)
-        } else {
-            // Fallback: copy original
-            fs::copy(input, &output_path)?;
-            Ok(output_path)
-        }
-    } else {
-        // No scaling needed, copy original
-        fs::copy(input, &output_path)?;
-        Ok(output_path)
-    }
-}
-
 // Phase 3: FFmpeg Command Construction
 
 /// Validate FFmpeg is installed
@@ -466,55 +998,34 @@ pub fn validate_ffmpeg_installed() -> Result<()> {
     let output = StdCommand::new("ffmpeg")
         .arg("-version")
         .output()
-        .map_err(|_| anyhow::anyhow!("ffmpeg not found in PATH. Please install FFmpeg."))?;
+        .map_err(|e| LecternError::spawn("ffmpeg", e))?;
     
     if output.status.success() {
         Ok(())
     } else {
-        Err(anyhow::anyhow!("ffmpeg command failed"))
+        Err(LecternError::Ffmpeg { exit_code: output.status.code(), stderr: String::from_utf8_lossy(&output.stderr).to_string() })
     }
 }
 
-/// Build FFmpeg command for conversion
-pub async fn build_ffmpeg_command(config: &ConversionConfig, temp_dir: &TempDir) -> Result<(TokioCommand, Option<ConcatMethod>)> {
-    validate_ffmpeg_installed()?;
-    
-    let mut cmd = TokioCommand::new("ffmpeg");
-    
-    // Determine input files
-    let input_type = detect_input_type(&config.input_path)?;
-    
-    // Store concat method for later use
-    let concat_method = match &input_type {
-        InputType::Directory(ref files) => {
-            Some(should_use_concat_demuxer(files, config.processing_options.normalize_volume)?)
-        }
-        _ => None,
-    };
-
-    // Probe first file to get default parameters
-    let first_file = match &input_type {
-        InputType::SingleM4B(path) | InputType::SingleAudioFile(path) => Some(path.clone()),
-        InputType::Directory(files) => files.first().cloned(),
-    };
-    let input_params = if let Some(path) = first_file {
-        probe_audio_file(&path).ok()
-    } else {
-        None
-    };
-    
+/// Add the audio inputs. Directories with FilterComplex get `audio_filter` appended to the concat
+/// chain (output label `[outa]`); for other inputs the caller applies it with `-af`.
+fn add_audio_inputs(
+    cmd: &mut TokioCommand,
+    input_type: &InputType,
+    concat_method: Option<&ConcatMethod>,
+    temp_dir: &Path,
+    audio_filter: Option<&str>,
+) -> Result<()> {
     match input_type {
-        InputType::SingleM4B(ref path) | InputType::SingleAudioFile(ref path) => {
+        InputType::SingleM4B(path) | InputType::SingleAudioFile(path) => {
             println!("[DEBUG] Using single file input: {}", path);
             cmd.arg("-i").arg(path);
         }
-        InputType::Directory(ref files) => {
+        InputType::Directory(files) => {
             println!("[DEBUG] Using directory input with {} files", files.len());
-            let method = concat_method.as_ref().unwrap();
-            
-            match method {
+            match concat_method.unwrap_or(&ConcatMethod::FilterComplex) {
                 ConcatMethod::Demuxer => {
-                    let concat_file = create_concat_file(files, temp_dir.path())?;
+                    let concat_file = create_concat_file(files, temp_dir)?;
                     println!("[DEBUG] Using concat demuxer with file: {:?}", concat_file);
                     cmd.arg("-f").arg("concat")
                         .arg("-safe").arg("0")
@@ -530,7 +1041,7 @@ pub async fn build_ffmpeg_command(config: &ConversionConfig, temp_dir: &TempDir)
                     
                     // Check if we need filter script (for long file lists)
                     if files.len() > 50 {
-                        let script_path = create_filter_complex_script(files, temp_dir.path(), config.processing_options.normalize_volume)?;
+                        let script_path = create_filter_complex_script(files, temp_dir, audio_filter)?;
                         cmd.arg("-filter_complex_script").arg(&script_path);
                     } else {
                         // Build inline filter_complex
@@ -540,8 +1051,9 @@ pub async fn build_ffmpeg_command(config: &ConversionConfig, temp_dir: &TempDir)
                         let mut filter = format!("{}concat=n={}:v=0:a=1", 
                             inputs.join(""), files.len());
                         
-                        if config.processing_options.normalize_volume {
-                            filter.push_str(",speechnorm=e=6.5:r=0.0001:l=1");
+                        if let Some(audio_filter) = audio_filter {
+                            filter.push(',');
+                            filter.push_str(audio_filter);
                         }
                         
                         filter.push_str("[outa]");
@@ -551,17 +1063,167 @@ pub async fn build_ffmpeg_command(config: &ConversionConfig, temp_dir: &TempDir)
             }
         }
     }
+    Ok(())
+}
+
+/// First loudnorm pass: decode the whole book through the same input chain and read the
+/// measured loudness that ffmpeg prints as JSON at the end of stderr.
+pub async fn measure_loudness(config: &ConversionConfig, temp_dir: &TempDir, trim: Option<&TrimRange>) -> Result<LoudnessMeasurement> {
+    let input_type = config_input_type(config)?;
+    // Normalization always decodes: join directories through filter_complex
+    let concat_method = match &input_type {
+        InputType::Directory(_) => Some(ConcatMethod::FilterComplex),
+        _ => None,
+    };
+    let loudnorm = format!("{}:print_format=json", config.processing_options.normalization.filter(None)
+        .unwrap_or_else(|| "loudnorm".to_string()));
+    let filter = audio_filter_chain(trim, Some(loudnorm)).unwrap_or_default();
+
+    let mut cmd = TokioCommand::new("ffmpeg");
+    cmd.arg("-hide_banner").arg("-nostats");
+    add_audio_inputs(&mut cmd, &input_type, concat_method.as_ref(), temp_dir.path(), Some(&filter))?;
+    if matches!(concat_method, Some(ConcatMethod::FilterComplex)) {
+        cmd.arg("-map").arg("[outa]");
+    } else {
+        cmd.arg("-map").arg("0:a").arg("-af").arg(&filter);
+    }
+    cmd.arg("-f").arg("null").arg("-");
+    cmd.stdout(std::process::Stdio::null());
+
+    println!("[DEBUG] Loudnorm pass 1: measuring {}", config.input_path);
+    let output = cmd.output().await.map_err(|e| LecternError::spawn("ffmpeg", e))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(LecternError::Ffmpeg { exit_code: output.status.code(), stderr: stderr.to_string() });
+    }
+    let measurement = parse_loudnorm_json(&stderr)?;
+    println!("[DEBUG] Loudnorm pass 1: {:?}", measurement);
+    Ok(measurement)
+}
+
+/// Parse the JSON block loudnorm prints with `print_format=json` (values are strings).
+fn parse_loudnorm_json(stderr: &str) -> Result<LoudnessMeasurement> {
+    let start = stderr.rfind('{');
+    let end = stderr.rfind('}');
+    let json: serde_json::Value = match (start, end) {
+        (Some(start), Some(end)) if start < end => serde_json::from_str(&stderr[start..=end])
+            .map_err(|e| LecternError::Parse(format!("Invalid loudnorm output: {}", e)))?,
+        _ => return Err(LecternError::Parse("No loudnorm measurement in ffmpeg output".to_string())),
+    };
+    let value = |key: &str| -> Result<f64> {
+        json.get(key)
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| LecternError::Parse(format!("Missing or invalid loudnorm value '{}' (is the audio silent?)", key)))
+    };
+    Ok(LoudnessMeasurement {
+        input_i: value("input_i")?,
+        input_tp: value("input_tp")?,
+        input_lra: value("input_lra")?,
+        input_thresh: value("input_thresh")?,
+        target_offset: value("target_offset")?,
+    })
+}
+
+/// Build FFmpeg command for conversion
+/// `loudness` is the first-pass measurement when two-pass loudnorm is selected; `trim` the
+/// resolved range to keep when trimming is enabled.
+pub async fn build_ffmpeg_command(
+    config: &ConversionConfig,
+    temp_dir: &TempDir,
+    loudness: Option<&LoudnessMeasurement>,
+    trim: Option<&TrimRange>,
+) -> Result<(TokioCommand, EncodePlan, Vec<String>)> {
+    validate_ffmpeg_installed()?;
     
-    // Add metadata file
-    let metadata_content = generate_ffmetadata(&config.book_metadata, &config.chapters);
+    let mut cmd = TokioCommand::new("ffmpeg");
+    
+    // Determine input files
+    let input_type = config_input_type(config)?;
+    
+    // Probe first file to get default parameters
+    let first_file = match &input_type {
+        InputType::SingleM4B(path) | InputType::SingleAudioFile(path) => Some(path.clone()),
+        InputType::Directory(files) => files.first().cloned(),
+    };
+    let input_params = if let Some(path) = first_file {
+        probe_audio_file(&path).ok()
+    } else {
+        None
+    };
+    
+    // Audio codec
+    let format = config.output_format;
+    let encoder = resolve_encoder(config, input_params.as_ref());
+    let mut ffmpeg_codec = encoder.codec;
+    let mut audio_filter = audio_filter_chain(trim, config.processing_options.normalization.filter(loudness));
+    
+    // Copy what can be copied, re-encode the rest
+    let plan = match &input_type {
+        InputType::SingleM4B(path) | InputType::SingleAudioFile(path) => plan_encode(std::slice::from_ref(path), ffmpeg_codec, encoder.profile, config)?,
+        InputType::Directory(files) => plan_encode(files, ffmpeg_codec, encoder.profile, config)?,
+    };
+    println!("[DEBUG] Encode plan: {}", plan);
+    if plan.is_copy() {
+        ffmpeg_codec = "copy";
+    }
+    
+    // Parallel strategy: encode every file to an AAC segment first, then only join them.
+    // Trimming applies to the whole book, so it needs the single-process chain.
+    let parallel = config.processing_options.encode_strategy == EncodeStrategy::ParallelFiles
+        && matches!(ffmpeg_codec, "aac" | "libfdk_aac")
+        && trim.is_none()
+        && matches!(&input_type, InputType::Directory(files) if files.len() > 1);
+    let (input_type, concat_method) = match (input_type, &plan) {
+        (InputType::Directory(files), EncodePlan::PartialCopy { codec, sample_rate, channels, reencode }) => {
+            // Encode the odd files to the parameters of the copied ones, then join all without re-encoding
+            let reference = params_for_copy(&files, reencode, codec, *sample_rate, *channels);
+            let segments = encode_segments_parallel(reencode, config, "aac", &[], Some(&reference), None, temp_dir.path()).await?;
+            let files = files.into_iter()
+                .map(|f| match reencode.iter().position(|r| *r == f) {
+                    Some(i) => segments[i].clone(),
+                    None => f,
+                })
+                .collect();
+            (InputType::Directory(files), Some(ConcatMethod::Demuxer))
+        }
+        (InputType::Directory(files), _) if parallel => {
+            let segments = encode_segments_parallel(&files, config, ffmpeg_codec, &encoder.args, input_params.as_ref(), audio_filter.as_deref(), temp_dir.path()).await?;
+            // Normalization and encoding already happened per segment
+            audio_filter = None;
+            ffmpeg_codec = "copy";
+            (InputType::Directory(segments), Some(ConcatMethod::Demuxer))
+        }
+        (InputType::Directory(files), _) => (InputType::Directory(files), Some(plan.concat_method())),
+        (other, _) => (other, None),
+    };
+    
+    add_audio_inputs(&mut cmd, &input_type, concat_method.as_ref(), temp_dir.path(), audio_filter.as_deref())?;
+    
+    // Crop/resize/recompress the cover as set on the Cover tab
+    let cover_path = match config.cover_image_path {
+        Some(ref cover_path) => Some(prepare_cover(cover_path, config.processing_options.cover_edit, temp_dir.path()).await?),
+        None => None,
+    };
+    let cover_stream = cover_path.is_some() && format.has_cover_stream();
+    
+    // Add metadata file (Ogg carries the cover as a Vorbis comment in it)
+    let mut metadata_content = generate_ffmetadata(&config.book_metadata, &config.chapters, format);
+    if let (Some(ref cover_path), false) = (&cover_path, format.has_cover_stream()) {
+        let picture = vorbis_picture_comment(cover_path)?;
+        metadata_content = metadata_content.replacen(
+            ";FFMETADATA1\n",
+            &format!(";FFMETADATA1\nMETADATA_BLOCK_PICTURE={}\n", escape_metadata_value(&picture)),
+            1,
+        );
+    }
     let metadata_path = write_metadata_file(&metadata_content, temp_dir.path())?;
     cmd.arg("-i").arg(&metadata_path);
     
     // Add cover image
-    if let Some(ref cover_path) = config.cover_image_path {
-        // Scale if needed
-        let cover_path = scale_cover_image(cover_path, config.processing_options.max_cover_size, temp_dir.path()).await?;
-        cmd.arg("-i").arg(&cover_path);
+    if let (Some(ref cover_path), true) = (&cover_path, cover_stream) {
+        cmd.arg("-i").arg(cover_path);
     }
     
     // Set mapping
@@ -577,7 +1239,7 @@ pub async fn build_ffmpeg_command(config: &ConversionConfig, temp_dir: &TempDir)
     }
     // Chapters are automatically embedded from the metadata file input
     // Cover image index: with filter_complex we have [0..N-1]=audio, N=metadata, N+1=cover
-    if config.cover_image_path.is_some() {
+    if cover_stream {
         let cover_index = if use_filter_complex {
             match &input_type {
                 InputType::Directory(files) => files.len() + 1, // audio files + metadata
@@ -590,52 +1252,63 @@ pub async fn build_ffmpeg_command(config: &ConversionConfig, temp_dir: &TempDir)
     }
     
     // Audio codec and bitrate
-    let ffmpeg_codec = match config.audio_codec.as_str() {
-        "copy" => "copy",
-        "opus" => "libopus",
-        _ => "aac",
-    };
     cmd.arg("-c:a").arg(ffmpeg_codec);
     
-    if ffmpeg_codec != "copy" {
-        if let Some(ref bitrate) = config.audio_bitrate {
-            cmd.arg("-b:a").arg(bitrate);
-        } else if let Some(ref params) = input_params {
-            // Match input bitrate if available, otherwise default to a sensible value
-            if let Some(br) = params.bitrate {
-                cmd.arg("-b:a").arg(br.to_string());
-            } else {
-                cmd.arg("-b:a").arg("128k");
-            }
-        } else {
-            cmd.arg("-b:a").arg("128k"); // Final fallback
-        }
-        
-        // Channels: use config, or match input, or default to stereo
-        if let Some(channels) = config.audio_channels {
-            cmd.arg("-ac").arg(channels.to_string());
-        } else if let Some(ref params) = input_params {
-            cmd.arg("-ac").arg(params.channels.to_string());
-        } else {
-            cmd.arg("-ac").arg("2");
-        }
+    if ffmpeg_codec == "flac" {
+        // Lossless: no bitrate, only channels
+        let channels = config.audio_channels.or(input_params.as_ref().map(|p| p.channels)).unwrap_or(2);
+        cmd.arg("-ac").arg(channels.to_string());
+    } else if ffmpeg_codec != "copy" {
+        cmd.args(audio_encode_args(config, input_params.as_ref()));
+        cmd.args(&encoder.args);
     }
     
     // Cover image options
-    if config.cover_image_path.is_some() {
+    if cover_stream {
         cmd.arg("-c:v").arg("copy");
         cmd.arg("-disposition:v").arg("attached_pic");
         // Ensure cover image doesn't impact bitrate calculations in some players
         cmd.arg("-b:v").arg("0");
+        if format == OutputFormat::Mp3 {
+            // APIC frame description/type
+            cmd.arg("-metadata:s:v").arg("title=Album cover");
+            cmd.arg("-metadata:s:v").arg("comment=Cover (front)");
+        }
+    }
+    
+    // Container options: ID3v2.3 is the most widely supported (CHAP/CTOC frames come from the chapters)
+    if format == OutputFormat::Mp3 {
+        cmd.arg("-id3v2_version").arg("3");
+    }
+    if let Some(muxer) = format.muxer() {
+        cmd.arg("-f").arg(muxer);
     }
     
     // Volume normalization (only if not using filter_complex)
-    if config.processing_options.normalize_volume && !use_filter_complex {
-        cmd.arg("-af").arg("speechnorm=e=6.5:r=0.0001:l=1");
+    if let Some(ref filter) = audio_filter {
+        if !use_filter_complex {
+            cmd.arg("-af").arg(filter);
+        }
+    }
+    
+    // Sample rate: the chosen one; after loudnorm (which resamples to 192 kHz) the source rate
+    if ffmpeg_codec != "copy" {
+        let loudnorm = audio_filter.is_some() && matches!(config.processing_options.normalization, Normalization::Loudnorm { .. });
+        let sample_rate = encoder.sample_rate.or_else(|| loudnorm.then(|| {
+            // Opus only encodes at 48 kHz (and lower fixed rates)
+            if ffmpeg_codec == "libopus" {
+                48000
+            } else {
+                input_params.as_ref().map(|p| p.sample_rate).unwrap_or(44100)
+            }
+        }));
+        if let Some(sample_rate) = sample_rate {
+            cmd.arg("-ar").arg(sample_rate.to_string());
+        }
     }
     
     // Add metadata flags (redundancy for compatibility)
-    add_metadata_args(&mut cmd, &config.book_metadata);
+    add_metadata_args(&mut cmd, &config.book_metadata, format);
     
     // Output path (use .tmp for atomic write)
     let output_path = if config.processing_options.atomic_write {
@@ -648,7 +1321,7 @@ pub async fn build_ffmpeg_command(config: &ConversionConfig, temp_dir: &TempDir)
     if let Some(parent) = std::path::Path::new(&output_path).parent() {
         if !parent.exists() {
             fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create output directory: {}", parent.display()))?;
+                .map_err(|e| LecternError::io(parent, e))?;
         }
     }
     
@@ -659,34 +1332,125 @@ pub async fn build_ffmpeg_command(config: &ConversionConfig, temp_dir: &TempDir)
     cmd.stdout(std::process::Stdio::null());
     cmd.stderr(std::process::Stdio::piped());
     
-    Ok((cmd, concat_method))
+    Ok((cmd, plan, encoder.warnings))
 }
 
-/// Add metadata flags to command
-fn add_metadata_args(cmd: &mut TokioCommand, book: &BookMetadata) {
-    if !book.title.is_empty() {
-        cmd.arg("-metadata").arg(&format!("title={}", book.title));
-    }
-    if !book.author.is_empty() {
-        cmd.arg("-metadata").arg(&format!("artist={}", book.author));
-    }
-    if let Some(ref series) = book.series {
-        cmd.arg("-metadata").arg(&format!("album={}", series));
-    }
-    if let Some(ref genre) = book.genre {
-        cmd.arg("-metadata").arg(&format!("genre={}", genre));
-    }
-    if let Some(ref year) = book.publish_year {
-        cmd.arg("-metadata").arg(&format!("date={}", year));
-    }
-    if let Some(ref publisher) = book.publisher {
-        cmd.arg("-metadata").arg(&format!("publisher={}", publisher));
+/// Encode parameters for files re-encoded to join copied ones: the bitrate of the first
+/// copied file, so the output stays uniform.
+fn params_for_copy(files: &[String], reencode: &[String], codec: &str, sample_rate: u32, channels: u32) -> AudioParams {
+    let bitrate = files.iter()
+        .find(|f| !reencode.contains(f))
+        .and_then(|f| probe_audio_file(f).ok())
+        .and_then(|p| p.bitrate);
+    AudioParams {
+        codec: codec.to_string(),
+        profile: None,
+        sample_rate,
+        channels,
+        bitrate,
+        duration_ms: 0,
     }
-    if let Some(ref description) = book.description {
-        cmd.arg("-metadata").arg(&format!("comment={}", description));
+}
+
+/// Bitrate and channel arguments: config values, else matched to the input, else defaults.
+fn audio_encode_args(config: &ConversionConfig, input_params: Option<&AudioParams>) -> Vec<String> {
+    let mut args = Vec::new();
+    args.push("-b:a".to_string());
+    if let Some(ref bitrate) = config.audio_bitrate {
+        args.push(bitrate.clone());
+    } else if let Some(br) = input_params.and_then(|p| p.bitrate) {
+        // Match input bitrate if available, otherwise default to a sensible value
+        args.push(br.to_string());
+    } else {
+        args.push("128k".to_string()); // Final fallback
     }
-    if let Some(ref narrator) = book.narrator {
-        cmd.arg("-metadata").arg(&format!("narrator={}", narrator));
+    
+    // Channels: use config, or match input, or default to stereo
+    args.push("-ac".to_string());
+    let channels = config.audio_channels.or(input_params.map(|p| p.channels)).unwrap_or(2);
+    args.push(channels.to_string());
+    args
+}
+
+/// Encode each input file to an AAC segment in the temp dir with `encoder` (aac or libfdk_aac plus
+/// `encoder_args`), one ffmpeg process per CPU core.
+/// Every segment gets the same sample rate and channel count so they can be joined with -c copy.
+/// Logs wall time against the summed per-file encode time (roughly what one process would take).
+async fn encode_segments_parallel(
+    files: &[String],
+    config: &ConversionConfig,
+    encoder: &str,
+    encoder_args: &[String],
+    input_params: Option<&AudioParams>,
+    audio_filter: Option<&str>,
+    temp_dir: &Path,
+) -> Result<Vec<String>> {
+    use futures::stream::{self, StreamExt, TryStreamExt};
+    
+    let workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4).min(files.len()).max(1);
+    let sample_rate = config.sample_rate.or(input_params.map(|p| p.sample_rate)).unwrap_or(44100);
+    let mut encode_args = audio_encode_args(config, input_params);
+    encode_args.extend_from_slice(encoder_args);
+    println!("[DEBUG] Parallel encode: {} files on {} workers", files.len(), workers);
+    
+    // Build every command up front so the stream owns its jobs
+    let jobs: Vec<(usize, String, String, TokioCommand)> = files.iter().enumerate()
+        .map(|(index, file)| {
+            let segment = temp_dir.join(format!("segment_{:04}.m4a", index)).to_string_lossy().to_string();
+            let mut cmd = TokioCommand::new("ffmpeg");
+            cmd.arg("-y").arg("-nostdin")
+                .arg("-i").arg(file)
+                .arg("-map").arg("0:a")
+                .arg("-vn");
+            if let Some(filter) = audio_filter {
+                cmd.arg("-af").arg(filter);
+            }
+            cmd.arg("-c:a").arg(encoder)
+                .args(&encode_args)
+                .arg("-ar").arg(sample_rate.to_string())
+                .arg(&segment);
+            cmd.stdout(std::process::Stdio::null());
+            (index, file.clone(), segment, cmd)
+        })
+        .collect();
+    
+    let started = std::time::Instant::now();
+    let results: Vec<(usize, String, std::time::Duration)> = stream::iter(jobs)
+        .map(|(index, file, segment, mut cmd)| async move {
+            let file_started = std::time::Instant::now();
+            let output = cmd.output().await.map_err(|e| LecternError::spawn("ffmpeg", e))?;
+            if !output.status.success() {
+                return Err(LecternError::Ffmpeg {
+                    exit_code: output.status.code(),
+                    stderr: format!("{}: {}", file, String::from_utf8_lossy(&output.stderr).trim()),
+                });
+            }
+            Ok((index, segment, file_started.elapsed()))
+        })
+        .buffer_unordered(workers)
+        .try_collect()
+        .await?;
+    let wall = started.elapsed();
+    
+    let sequential: std::time::Duration = results.iter().map(|(_, _, elapsed)| *elapsed).sum();
+    println!(
+        "[DEBUG] Parallel encode finished: {} files in {:.1}s wall vs {:.1}s summed per-file ({:.1}x, ~{:.1}s saved)",
+        results.len(),
+        wall.as_secs_f64(),
+        sequential.as_secs_f64(),
+        sequential.as_secs_f64() / wall.as_secs_f64().max(0.001),
+        sequential.saturating_sub(wall).as_secs_f64()
+    );
+    
+    let mut results = results;
+    results.sort_by_key(|(index, _, _)| *index);
+    Ok(results.into_iter().map(|(_, segment, _)| segment).collect())
+}
+
+/// Add metadata flags to command
+fn add_metadata_args(cmd: &mut TokioCommand, book: &BookMetadata, format: OutputFormat) {
+    for (key, value) in crate::services::tags::ffmpeg_tags(book, format) {
+        cmd.arg("-metadata").arg(format!("{}={}", key, value));
     }
 }
 
@@ -711,7 +1475,7 @@ pub async fn execute_ffmpeg(mut cmd: TokioCommand) -> Result<()> {
     cmd.stderr(std::process::Stdio::piped());
     
     let mut child = cmd.spawn()
-        .context("Failed to spawn ffmpeg process")?;
+        .map_err(|e| LecternError::spawn("ffmpeg", e))?;
     
     // Capture stderr output
     let stderr = child.stderr.take();
@@ -732,7 +1496,7 @@ pub async fn execute_ffmpeg(mut cmd: TokioCommand) -> Result<()> {
     };
     
     let status = child.wait().await
-        .context("Failed to wait for ffmpeg process")?;
+        .map_err(|e| LecternError::Other(format!("Failed to wait for ffmpeg process: {}", e)))?;
     
     let stderr_output = if let Some(handle) = stderr_handle {
         handle.await.unwrap_or_default()
@@ -740,20 +1504,23 @@ pub async fn execute_ffmpeg(mut cmd: TokioCommand) -> Result<()> {
         String::new()
     };
     
+    ffmpeg_exit_result(status, stderr_output)
+}
+
+/// Outcome of a finished ffmpeg run.
+fn ffmpeg_exit_result(status: std::process::ExitStatus, stderr: String) -> Result<()> {
     if status.success() {
         Ok(())
+    } else if status.code().is_none() {
+        // Terminated by a signal (process killed), not an ffmpeg failure
+        Err(LecternError::Cancelled)
     } else {
-        let error_msg = if !stderr_output.is_empty() {
-            format!("FFmpeg conversion failed with exit code: {:?}\nStderr:\n{}", status.code(), stderr_output)
-        } else {
-            format!("FFmpeg conversion failed with exit code: {:?}", status.code())
-        };
-        Err(anyhow::anyhow!("{}", error_msg))
+        Err(LecternError::Ffmpeg { exit_code: status.code(), stderr })
     }
 }
 
 /// Main conversion function
-pub async fn convert_to_m4b(config: ConversionConfig) -> Result<String> {
+pub async fn convert_to_m4b(mut config: ConversionConfig) -> Result<ConversionReport> {
     println!("[DEBUG] Starting conversion:");
     println!("[DEBUG]   Input: {}", config.input_path);
     println!("[DEBUG]   Output: {}", config.output_path);
@@ -762,11 +1529,41 @@ pub async fn convert_to_m4b(config: ConversionConfig) -> Result<String> {
     
     // Create temporary directory
     let temp_dir = TempDir::new()
-        .context("Failed to create temporary directory")?;
+        .map_err(|e| LecternError::io(std::env::temp_dir(), e))?;
     println!("[DEBUG] Temp directory: {:?}", temp_dir.path());
     
+    // When splitting, encode the whole book into the temp dir first and cut it afterwards
+    let split = config.processing_options.split;
+    let final_output = config.output_path.clone();
+    if split == SplitMode::None {
+        config.output_path = part_output_path(&final_output, None);
+    } else {
+        let ext = Path::new(&final_output).extension().and_then(|e| e.to_str()).unwrap_or("m4b");
+        config.output_path = temp_dir.path().join(format!("full.{}", ext)).to_string_lossy().to_string();
+        config.processing_options.atomic_write = false;
+    }
+    
+    // Trim: resolve the kept range (silence detection) and move the chapters with it
+    let trim = if config.processing_options.trim.is_enabled() {
+        validate_ffmpeg_installed()?;
+        let range = resolve_trim(&config).await?;
+        println!("[DEBUG] Trim: keeping {}..{} of {} ms", range.start_ms, range.end_ms, range.total_ms);
+        config.chapters = trim_chapters(&config.chapters, range.start_ms, range.end_ms);
+        Some(range)
+    } else {
+        None
+    };
+    
+    // Two-pass loudnorm: measure first, then encode with linear gain
+    let loudness = if matches!(config.processing_options.normalization, Normalization::Loudnorm { .. }) {
+        validate_ffmpeg_installed()?;
+        Some(measure_loudness(&config, &temp_dir, trim.as_ref()).await?)
+    } else {
+        None
+    };
+    
     // Build command
-    let (cmd, _concat_method) = build_ffmpeg_command(&config, &temp_dir).await?;
+    let (cmd, plan, warnings) = build_ffmpeg_command(&config, &temp_dir, loudness.as_ref(), trim.as_ref()).await?;
     
     // Debug: Print the command being executed (approximate)
     println!("[DEBUG] FFmpeg command built (check stderr for full command)");
@@ -778,21 +1575,444 @@ pub async fn convert_to_m4b(config: ConversionConfig) -> Result<String> {
     if config.processing_options.atomic_write {
         let tmp_path = get_atomic_temp_path(&config.output_path);
         fs::rename(&tmp_path, &config.output_path)
-            .with_context(|| format!("Failed to rename {} to {}", tmp_path, config.output_path))?;
+            .map_err(|e| LecternError::io(&config.output_path, e))?;
     }
     
     // Validate output
     let output_path = Path::new(&config.output_path);
     if !output_path.exists() {
-        return Err(anyhow::anyhow!("Output file was not created: {}", config.output_path));
+        return Err(LecternError::Other(format!("Output file was not created: {}", config.output_path)));
     }
     
-    let metadata = fs::metadata(&output_path)
-        .context("Failed to get output file metadata")?;
+    let metadata = fs::metadata(output_path)
+        .map_err(|e| LecternError::io(output_path, e))?;
     if metadata.len() == 0 {
-        return Err(anyhow::anyhow!("Output file is empty: {}", config.output_path));
+        return Err(LecternError::Other(format!("Output file is empty: {}", config.output_path)));
+    }
+    
+    let output_paths = if split == SplitMode::None {
+        vec![config.output_path.clone()]
+    } else {
+        split_into_parts(&config, &final_output, metadata.len(), temp_dir.path()).await?
+    };
+    
+    // Atoms ffmpeg's MP4 muxer cannot write (series movement, freeform ASIN/ISBN/...)
+    if config.output_format == OutputFormat::M4b {
+        let atoms = crate::services::tags::mp4_atoms(&config.book_metadata);
+        for path in &output_paths {
+            crate::services::mp4_tags::write_mp4_atoms(path, &atoms)?;
+        }
     }
     
+    // Sidecars for Audiobookshelf (lists and fields the tags cannot hold)
+    let sidecars = match Path::new(&output_paths[0]).parent() {
+        Some(dir) if config.processing_options.write_sidecars => {
+            crate::services::abs_sidecar::write_sidecars(dir, &config.book_metadata, &config.chapters)?
+        }
+        _ => Vec::new(),
+    };
+    
+    // Verify the result against what was asked for (reported, not fatal)
+    let expected = crate::services::verify::ExpectedOutput {
+        duration_ms: expected_duration_ms(&config, trim.as_ref()),
+        chapters: config.chapters.clone(),
+        title: config.book_metadata.title.clone(),
+        author: config.book_metadata.author_display(),
+        cover: config.cover_image_path.is_some(),
+    };
+    let verification = crate::services::verify::verify_output(&expected, &output_paths).await;
+    println!("[DEBUG] Verification: {}", if verification.passed() { "passed" } else { "FAILED" });
+    
     // Temp dir will be cleaned up automatically on drop
-    Ok(config.output_path)
+    Ok(ConversionReport {
+        output_paths,
+        normalization: config.processing_options.normalization,
+        loudness,
+        plan,
+        trim,
+        warnings,
+        verification,
+        sidecars,
+    })
+}
+
+/// Length the output should have: the kept range when trimming, otherwise the summed inputs.
+fn expected_duration_ms(config: &ConversionConfig, trim: Option<&TrimRange>) -> Option<u64> {
+    if let Some(range) = trim {
+        return Some(range.end_ms.saturating_sub(range.start_ms));
+    }
+    match config_input_type(config).ok()? {
+        InputType::Directory(files) => get_total_duration(&files).ok(),
+        InputType::SingleM4B(path) | InputType::SingleAudioFile(path) => probe_audio_file(&path).ok().map(|p| p.duration_ms),
+    }
+}
+
+// Phase 4b: Trimming
+
+/// How much of the ends is searched for silence.
+const SILENCE_WINDOW_MS: u64 = 60_000;
+/// Audio quieter than this counts as silence.
+const SILENCE_THRESHOLD: &str = "-50dB";
+
+/// Resolve the trim options into the kept range: the fixed start/end trim, then (optionally)
+/// silence found right after the start and right before the end. Silence is only searched in
+/// the first and last input file, within `SILENCE_WINDOW_MS` of the trim points.
+pub async fn resolve_trim(config: &ConversionConfig) -> Result<TrimRange> {
+    let files = match config_input_type(config)? {
+        InputType::SingleM4B(path) | InputType::SingleAudioFile(path) => vec![path],
+        InputType::Directory(files) => files,
+    };
+    let durations = files.iter().map(|f| probe_audio_file(f).map(|p| p.duration_ms)).collect::<Result<Vec<_>>>()?;
+    let total_ms: u64 = durations.iter().sum();
+    let options = config.processing_options.trim;
+    let mut start_ms = options.start_ms;
+    let mut end_ms = total_ms.saturating_sub(options.end_ms);
+    if start_ms >= end_ms {
+        return Err(LecternError::Other(format!(
+            "Trimming {} ms from the start and {} ms from the end leaves nothing of a {} ms book",
+            options.start_ms, options.end_ms, total_ms
+        )));
+    }
+
+    if options.silence {
+        // Leading: silence starting right at the trim point in the first file
+        if start_ms < durations[0] {
+            let window = SILENCE_WINDOW_MS.min(durations[0] - start_ms);
+            let silences = detect_silence(&files[0], start_ms, window).await?;
+            if let Some((_, end)) = silences.first().filter(|(start, _)| *start <= 50) {
+                start_ms += end.unwrap_or(window);
+            }
+        }
+        // Trailing: silence running into the trim point in the last file
+        let last_index = files.len() - 1;
+        let last_duration = durations[last_index];
+        let last_offset = total_ms - last_duration;
+        if end_ms > last_offset {
+            let local_end = end_ms - last_offset;
+            let window = SILENCE_WINDOW_MS.min(local_end);
+            let silences = detect_silence(&files[last_index], local_end - window, window).await?;
+            if let Some((start, _)) = silences.last().filter(|(_, end)| end.is_none_or(|e| e + 50 >= window)) {
+                end_ms = end_ms - window + start;
+            }
+        }
+        if start_ms >= end_ms {
+            return Err(LecternError::Other("The book is silent after trimming".to_string()));
+        }
+    }
+
+    Ok(TrimRange { start_ms, end_ms, total_ms })
+}
+
+/// Silent intervals (start, end) in ms relative to `from_ms`, within `len_ms` of `file`.
+/// `end` is None when the silence lasts to the end of the window.
+async fn detect_silence(file: &str, from_ms: u64, len_ms: u64) -> Result<Vec<(u64, Option<u64>)>> {
+    let output = TokioCommand::new("ffmpeg")
+        .arg("-hide_banner").arg("-nostats")
+        .arg("-ss").arg(format!("{:.3}", from_ms as f64 / 1000.0))
+        .arg("-t").arg(format!("{:.3}", len_ms as f64 / 1000.0))
+        .arg("-i").arg(file)
+        .arg("-map").arg("0:a")
+        .arg("-af").arg(format!("silencedetect=n={}:d=0.3", SILENCE_THRESHOLD))
+        .arg("-f").arg("null").arg("-")
+        .stdout(std::process::Stdio::null())
+        .output()
+        .await
+        .map_err(|e| LecternError::spawn("ffmpeg", e))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(LecternError::Ffmpeg { exit_code: output.status.code(), stderr: stderr.to_string() });
+    }
+    Ok(parse_silencedetect(&stderr))
+}
+
+/// Parse `silence_start: 1.23` / `silence_end: 4.56 | silence_duration: ...` lines.
+fn parse_silencedetect(stderr: &str) -> Vec<(u64, Option<u64>)> {
+    let value = |line: &str, key: &str| -> Option<u64> {
+        let rest = &line[line.find(key)? + key.len()..];
+        let number = rest.split_whitespace().next()?;
+        number.parse::<f64>().ok().map(|s| (s.max(0.0) * 1000.0).round() as u64)
+    };
+    let mut silences: Vec<(u64, Option<u64>)> = Vec::new();
+    for line in stderr.lines() {
+        if let Some(start) = value(line, "silence_start:") {
+            silences.push((start, None));
+        } else if let Some(end) = value(line, "silence_end:") {
+            if let Some(last) = silences.last_mut().filter(|(_, e)| e.is_none()) {
+                last.1 = Some(end);
+            }
+        }
+    }
+    silences
+}
+
+/// Chapters overlapping [start_ms, end_ms), shifted by -start_ms. A chapter cut by the start
+/// begins at 0; the last one ends at the new end. Chapters entirely trimmed away are dropped.
+/// Chapters without a duration run to the next chapter start.
+pub fn trim_chapters(chapters: &[Chapter], start_ms: u64, end_ms: u64) -> Vec<Chapter> {
+    chapters
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| {
+            let original_end = if c.duration > 0 {
+                c.start_time + c.duration
+            } else {
+                chapters.get(i + 1).map(|next| next.start_time).unwrap_or(end_ms)
+            };
+            if original_end <= start_ms || c.start_time >= end_ms {
+                return None;
+            }
+            let chapter_start = c.start_time.max(start_ms);
+            let chapter_end = original_end.min(end_ms);
+            let mut chapter = c.clone();
+            chapter.start_time = chapter_start - start_ms;
+            chapter.duration = chapter_end.saturating_sub(chapter_start);
+            Some(chapter)
+        })
+        .collect()
+}
+
+/// Chapters for the book without the `skipped` inputs. `files` are all inputs in concatenation
+/// order with their durations (ms). Each skipped file's time range is cut out and later chapters
+/// move back by its length; chapters that only covered skipped audio are dropped.
+pub fn remove_skipped_ranges(chapters: &[Chapter], files: &[(String, u64)], skipped: &[String]) -> Vec<Chapter> {
+    let mut removed = Vec::new();
+    let mut file_start = 0u64;
+    for (path, duration_ms) in files {
+        if skipped.contains(path) {
+            removed.push((file_start, file_start + duration_ms));
+        }
+        file_start += duration_ms;
+    }
+    let total_ms = file_start;
+    // Position of `t` once the removed ranges before it are gone
+    let shift = |t: u64| -> u64 {
+        let cut: u64 = removed.iter().map(|(start, end)| t.min(*end).saturating_sub(*start)).sum();
+        t - cut
+    };
+    chapters
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| {
+            let original_end = if c.duration > 0 {
+                c.start_time + c.duration
+            } else {
+                chapters.get(i + 1).map(|next| next.start_time).unwrap_or(total_ms)
+            };
+            let start = shift(c.start_time);
+            let end = shift(original_end.max(c.start_time));
+            if end <= start {
+                return None;
+            }
+            let mut chapter = c.clone();
+            chapter.start_time = start;
+            chapter.duration = end - start;
+            Some(chapter)
+        })
+        .collect()
+}
+
+// Phase 5: Splitting into parts
+
+/// Output path for `part` = (number, count). `{Part}` in the path becomes "Part N" (zero-padded
+/// to the part count); without the placeholder " - Part N" is appended to the file name. With
+/// no part the placeholder is removed along with the separator left in front of it.
+pub fn part_output_path(output_path: &str, part: Option<(usize, usize)>) -> String {
+    let label = part
+        .map(|(n, count)| format!("Part {:0width$}", n, width = count.to_string().len()))
+        .unwrap_or_default();
+    let path = Path::new(output_path);
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or(output_path);
+    let (stem, ext) = match file_name.rfind('.') {
+        Some(i) if i > 0 => (&file_name[..i], &file_name[i..]),
+        _ => (file_name, ""),
+    };
+    let stem = if stem.contains("{Part}") {
+        let replaced = stem.replace("{Part}", &label);
+        replaced.trim().trim_end_matches(['-', '_', ',']).trim().to_string()
+    } else if !label.is_empty() {
+        format!("{} - {}", stem, label)
+    } else {
+        stem.to_string()
+    };
+    // Directories may use the placeholder too (e.g. one folder per part)
+    let parent = path.parent().map(|p| p.to_string_lossy().replace("{Part}", &label)).unwrap_or_default();
+    let name = format!("{}{}", stem, ext);
+    if parent.is_empty() {
+        name
+    } else {
+        Path::new(&parent).join(name).to_string_lossy().to_string()
+    }
+}
+
+/// Part boundaries (ms) for a book of `total_ms`: starts at 0, ends at `total_ms`, and every cut is a
+/// chapter start. A part only exceeds `max_part_ms` when a single chapter is longer than that.
+pub fn plan_parts(chapters: &[Chapter], total_ms: u64, max_part_ms: u64) -> Vec<u64> {
+    let mut starts: Vec<u64> = chapters.iter().map(|c| c.start_time).filter(|s| *s < total_ms).collect();
+    starts.sort_unstable();
+    starts.dedup();
+
+    let mut bounds = vec![0];
+    let mut part_start = 0;
+    for (i, start) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).copied().unwrap_or(total_ms);
+        if end.saturating_sub(part_start) > max_part_ms && *start > part_start {
+            bounds.push(*start);
+            part_start = *start;
+        }
+    }
+    bounds.push(total_ms);
+    bounds
+}
+
+/// Chapters inside [start_ms, end_ms), shifted so the part starts at 0 and clipped to the part.
+fn part_chapters(chapters: &[Chapter], start_ms: u64, end_ms: u64) -> Vec<Chapter> {
+    chapters
+        .iter()
+        .filter(|c| c.start_time >= start_ms && c.start_time < end_ms)
+        .map(|c| {
+            let chapter_end = (c.start_time + c.duration).min(end_ms);
+            let mut chapter = c.clone();
+            chapter.start_time = c.start_time - start_ms;
+            chapter.duration = chapter_end.saturating_sub(c.start_time);
+            chapter
+        })
+        .collect()
+}
+
+/// Cut the encoded book (config.output_path) into parts on chapter boundaries (stream copy) and
+/// write each part with its own chapter list. Returns the written paths.
+async fn split_into_parts(config: &ConversionConfig, final_output: &str, total_bytes: u64, temp_dir: &Path) -> Result<Vec<String>> {
+    let total_ms = probe_audio_file(&config.output_path)?.duration_ms;
+    let max_part_ms = config.processing_options.split.max_part_ms(total_ms, total_bytes).unwrap_or(total_ms);
+    if config.chapters.is_empty() && total_ms > max_part_ms {
+        return Err(LecternError::Other("Splitting into parts needs chapters to cut on".to_string()));
+    }
+    let bounds = plan_parts(&config.chapters, total_ms, max_part_ms);
+    let count = bounds.len() - 1;
+    println!("[DEBUG] Splitting {} ms into {} part(s) (max {} ms per part)", total_ms, count, max_part_ms);
+
+    // Fits in one part: keep the single file
+    if count == 1 {
+        let output = part_output_path(final_output, None);
+        move_file(&config.output_path, &output)?;
+        return Ok(vec![output]);
+    }
+
+    let mut outputs = Vec::new();
+    for (i, window) in bounds.windows(2).enumerate() {
+        let (start_ms, end_ms) = (window[0], window[1]);
+        let number = i + 1;
+        let output = part_output_path(final_output, Some((number, count)));
+        if let Some(parent) = Path::new(&output).parent() {
+            fs::create_dir_all(parent).map_err(|e| LecternError::io(parent, e))?;
+        }
+
+        // Per-part chapters offset to the part start; tags (and the Ogg cover comment) come from the encoded book
+        let chapters = part_chapters(&config.chapters, start_ms, end_ms);
+        let metadata = generate_ffmetadata(&config.book_metadata, &chapters, config.output_format);
+        let metadata_path = temp_dir.join(format!("metadata_part{}.txt", number));
+        write_file_synced(&metadata_path, metadata.as_bytes())?;
+
+        let tmp_output = get_atomic_temp_path(&output);
+        let mut cmd = TokioCommand::new("ffmpeg");
+        cmd.arg("-y")
+            .arg("-ss").arg(format!("{:.3}", start_ms as f64 / 1000.0))
+            .arg("-t").arg(format!("{:.3}", (end_ms - start_ms) as f64 / 1000.0))
+            .arg("-i").arg(&config.output_path)
+            .arg("-i").arg(&metadata_path)
+            .arg("-map").arg("0:a");
+        if config.cover_image_path.is_some() && config.output_format.has_cover_stream() {
+            cmd.arg("-map").arg("0:v?").arg("-disposition:v").arg("attached_pic");
+        }
+        cmd.arg("-map_metadata").arg("0")
+            .arg("-map_chapters").arg("1")
+            .arg("-metadata").arg(format!("track={}/{}", number, count))
+            .arg("-c").arg("copy");
+        if config.output_format == OutputFormat::Mp3 {
+            cmd.arg("-id3v2_version").arg("3");
+        }
+        if let Some(muxer) = config.output_format.muxer() {
+            cmd.arg("-f").arg(muxer);
+        }
+        cmd.arg(&tmp_output);
+        cmd.stdout(std::process::Stdio::null());
+
+        println!("[DEBUG] Writing part {}/{}: {} - {} ms -> {}", number, count, start_ms, end_ms, output);
+        execute_ffmpeg(cmd).await?;
+        fs::rename(&tmp_output, &output).map_err(|e| LecternError::io(&output, e))?;
+        outputs.push(output);
+    }
+    Ok(outputs)
+}
+
+/// Rename, falling back to copy + delete when the temp dir is on another file system.
+fn move_file(from: &str, to: &str) -> Result<()> {
+    if let Some(parent) = Path::new(to).parent() {
+        fs::create_dir_all(parent).map_err(|e| LecternError::io(parent, e))?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to).map_err(|e| LecternError::io(to, e))?;
+    let _ = fs::remove_file(from);
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    #[test]
+    fn killed_ffmpeg_is_cancelled() {
+        let killed = ExitStatus::from_raw(9); // SIGKILL, no exit code
+        assert!(matches!(ffmpeg_exit_result(killed, String::new()), Err(LecternError::Cancelled)));
+    }
+
+    #[test]
+    fn failed_ffmpeg_keeps_exit_code_and_stderr() {
+        let failed = ExitStatus::from_raw(1 << 8);
+        match ffmpeg_exit_result(failed, "Invalid argument".to_string()) {
+            Err(LecternError::Ffmpeg { exit_code, stderr }) => {
+                assert_eq!(exit_code, Some(1));
+                assert_eq!(stderr, "Invalid argument");
+            }
+            other => panic!("expected Ffmpeg, got {:?}", other),
+        }
+        assert!(ffmpeg_exit_result(ExitStatus::from_raw(0), String::new()).is_ok());
+    }
+
+    fn inputs() -> Vec<(String, u64)> {
+        vec![("01.mp3".to_string(), 1_000), ("02.mp3".to_string(), 2_000), ("03.mp3".to_string(), 3_000)]
+    }
+
+    fn spans(chapters: &[Chapter]) -> Vec<(&str, u64, u64)> {
+        chapters.iter().map(|c| (c.title.as_str(), c.start_time, c.duration)).collect()
+    }
+
+    #[test]
+    fn skipped_file_chapter_is_dropped_and_later_ones_move_back() {
+        let chapters = vec![
+            Chapter::new("One".to_string(), 0, 1_000),
+            Chapter::new("Two".to_string(), 1_000, 2_000),
+            Chapter::new("Three".to_string(), 3_000, 3_000),
+        ];
+        let kept = remove_skipped_ranges(&chapters, &inputs(), &["02.mp3".to_string()]);
+        assert_eq!(spans(&kept), vec![("One", 0, 1_000), ("Three", 1_000, 3_000)]);
+    }
+
+    #[test]
+    fn chapters_spanning_a_skipped_file_are_shortened() {
+        // One chapter runs from the middle of the first file into the third
+        let chapters = vec![
+            Chapter::new("Intro".to_string(), 0, 500),
+            Chapter::new("Long".to_string(), 500, 4_000),
+            Chapter::new("End".to_string(), 4_500, 0),
+        ];
+        let kept = remove_skipped_ranges(&chapters, &inputs(), &["02.mp3".to_string()]);
+        assert_eq!(spans(&kept), vec![("Intro", 0, 500), ("Long", 500, 2_000), ("End", 2_500, 1_500)]);
+        let unchanged = remove_skipped_ranges(&chapters, &inputs(), &[]);
+        assert_eq!(spans(&unchanged), vec![("Intro", 0, 500), ("Long", 500, 4_000), ("End", 4_500, 1_500)]);
+    }
 }
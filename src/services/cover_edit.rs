@@ -0,0 +1,271 @@
+// Cover editing
+// Crops, pads, resizes and recompresses the cover in-process with the `image` crate. The same
+// code renders the preview on the Cover tab and the file embedded during conversion, so what is
+// previewed is what ends up in the book. Output is always JPEG; transparency is flattened onto
+// white (or onto the blurred background when padding).
+
+use crate::error::{LecternError, Result};
+use image::imageops::FilterType;
+use image::{DynamicImage, Rgba, RgbaImage};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Blur radius of the padded background, as a fraction of the cover side.
+const PAD_BLUR_FRACTION: f32 = 0.04;
+/// Smallest crop box side, as a fraction of the image.
+const MIN_CROP_FRACTION: f32 = 0.05;
+
+/// How the cover is made square (or not).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverCrop {
+    /// Keep the aspect ratio.
+    Keep,
+    /// Largest centered square.
+    CenterSquare,
+    /// Whole image centered on a square of its own blurred, enlarged copy.
+    PadBlur,
+    /// The crop box.
+    Manual,
+}
+
+impl CoverCrop {
+    pub const ALL: [CoverCrop; 4] = [CoverCrop::Keep, CoverCrop::CenterSquare, CoverCrop::PadBlur, CoverCrop::Manual];
+}
+
+impl fmt::Display for CoverCrop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoverCrop::Keep => write!(f, "Keep aspect ratio"),
+            CoverCrop::CenterSquare => write!(f, "Crop to square (center)"),
+            CoverCrop::PadBlur => write!(f, "Pad to square (blurred)"),
+            CoverCrop::Manual => write!(f, "Manual crop"),
+        }
+    }
+}
+
+/// Manual crop box as fractions (0.0-1.0) of the image, so it survives a cover change.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CropBox {
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for CropBox {
+    fn default() -> Self {
+        Self { left: 0.0, top: 0.0, width: 1.0, height: 1.0 }
+    }
+}
+
+impl CropBox {
+    /// Pixel rectangle (x, y, width, height) inside a `width` x `height` image.
+    fn to_pixels(self, width: u32, height: u32) -> (u32, u32, u32, u32) {
+        let left = self.left.clamp(0.0, 1.0 - MIN_CROP_FRACTION);
+        let top = self.top.clamp(0.0, 1.0 - MIN_CROP_FRACTION);
+        let w = self.width.clamp(MIN_CROP_FRACTION, 1.0 - left);
+        let h = self.height.clamp(MIN_CROP_FRACTION, 1.0 - top);
+        let px = |fraction: f32, size: u32| ((fraction * size as f32).round() as u32).min(size);
+        // Rounding can push the origin onto the edge of a small image; keep one pixel inside it
+        let x = px(left, width).min(width.saturating_sub(1));
+        let y = px(top, height).min(height.saturating_sub(1));
+        (x, y, px(w, width).clamp(1, (width - x).max(1)), px(h, height).clamp(1, (height - y).max(1)))
+    }
+}
+
+/// Everything applied to the cover before it is embedded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoverEdit {
+    pub crop: CoverCrop,
+    pub crop_box: CropBox,
+    /// Longest side in pixels; 0 keeps the size.
+    pub max_size: u32,
+    /// JPEG quality, 1-100.
+    pub jpeg_quality: u8,
+}
+
+impl Default for CoverEdit {
+    fn default() -> Self {
+        Self { crop: CoverCrop::Keep, crop_box: CropBox::default(), max_size: 1000, jpeg_quality: 90 }
+    }
+}
+
+/// An edited cover, encoded.
+#[derive(Debug, Clone)]
+pub struct EditedCover {
+    pub jpeg: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    /// Size of the source image.
+    pub source_width: u32,
+    pub source_height: u32,
+}
+
+/// Target size for an image of `width` x `height` that fits in `max_size` (never upscaled).
+fn fit_size(width: u32, height: u32, max_size: u32) -> (u32, u32) {
+    let longest = width.max(height);
+    if max_size == 0 || longest <= max_size {
+        return (width, height);
+    }
+    let scale = max_size as f64 / longest as f64;
+    (
+        ((width as f64 * scale).round() as u32).max(1),
+        ((height as f64 * scale).round() as u32).max(1),
+    )
+}
+
+/// Crop/pad and resize. The result may still have transparency.
+pub fn apply_edit(image: &DynamicImage, edit: &CoverEdit) -> RgbaImage {
+    let (width, height) = (image.width(), image.height());
+    let cropped = match edit.crop {
+        CoverCrop::Keep | CoverCrop::PadBlur => image.clone(),
+        CoverCrop::CenterSquare => {
+            let side = width.min(height);
+            image.crop_imm((width - side) / 2, (height - side) / 2, side, side)
+        }
+        CoverCrop::Manual => {
+            let (x, y, w, h) = edit.crop_box.to_pixels(width, height);
+            image.crop_imm(x, y, w, h)
+        }
+    };
+
+    if edit.crop == CoverCrop::PadBlur {
+        // Work at the output size: blurring a full-size scan is slow and the detail is lost anyway
+        let (side, _) = fit_size(width.max(height), width.max(height), edit.max_size);
+        let mut background = cropped
+            .resize_to_fill(side, side, FilterType::Triangle)
+            .blur(side as f32 * PAD_BLUR_FRACTION)
+            .to_rgba8();
+        let foreground = cropped.resize(side, side, FilterType::Lanczos3).to_rgba8();
+        let x = (side - foreground.width()) / 2;
+        let y = (side - foreground.height()) / 2;
+        image::imageops::overlay(&mut background, &foreground, x as i64, y as i64);
+        return background;
+    }
+
+    let (w, h) = fit_size(cropped.width(), cropped.height(), edit.max_size);
+    if (w, h) == (cropped.width(), cropped.height()) {
+        cropped.to_rgba8()
+    } else {
+        cropped.resize_exact(w, h, FilterType::Lanczos3).to_rgba8()
+    }
+}
+
+/// Flatten onto white and encode as JPEG.
+pub fn encode_jpeg(image: &RgbaImage, quality: u8) -> Result<Vec<u8>> {
+    let mut flat = RgbaImage::from_pixel(image.width(), image.height(), Rgba([255, 255, 255, 255]));
+    image::imageops::overlay(&mut flat, image, 0, 0);
+    let rgb = DynamicImage::ImageRgba8(flat).to_rgb8();
+    let mut jpeg = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, quality.clamp(1, 100))
+        .encode_image(&rgb)
+        .map_err(|e| LecternError::Other(format!("Failed to encode cover: {}", e)))?;
+    Ok(jpeg)
+}
+
+/// Decode `bytes`, apply `edit` and encode the result.
+pub fn render_cover(bytes: &[u8], edit: &CoverEdit) -> Result<EditedCover> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| LecternError::Other(format!("Failed to decode cover image: {}", e)))?;
+    let edited = apply_edit(&image, edit);
+    Ok(EditedCover {
+        jpeg: encode_jpeg(&edited, edit.jpeg_quality)?,
+        width: edited.width(),
+        height: edited.height(),
+        source_width: image.width(),
+        source_height: image.height(),
+    })
+}
+
+/// Cover bytes from a local path or an http(s) URL.
+pub async fn load_cover_bytes(source: &str) -> Result<Vec<u8>> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        crate::services::http_get(source, std::time::Duration::from_secs(15)).await
+    } else {
+        fs::read(source).map_err(|e| LecternError::io(source, e))
+    }
+}
+
+/// Edit the cover for embedding and write it to `temp_dir`. When the image cannot be decoded
+/// the original bytes are written unchanged and ffmpeg gets to try.
+pub async fn prepare_cover(source: &str, edit: CoverEdit, temp_dir: &Path) -> Result<PathBuf> {
+    let bytes = load_cover_bytes(source).await?;
+    let output_path = temp_dir.join("cover.jpg");
+    let rendered = tokio::task::spawn_blocking(move || render_cover(&bytes, &edit).map_err(|e| (e, bytes)))
+        .await
+        .map_err(|e| LecternError::Other(format!("Cover edit task failed: {}", e)))?;
+    let data = match rendered {
+        Ok(cover) => {
+            println!(
+                "[DEBUG] Cover {}x{} -> {}x{} JPEG ({} bytes)",
+                cover.source_width, cover.source_height, cover.width, cover.height, cover.jpeg.len()
+            );
+            cover.jpeg
+        }
+        Err((e, original)) => {
+            println!("[WARNING] {}; embedding the original image", e);
+            original
+        }
+    };
+    fs::write(&output_path, data).map_err(|e| LecternError::io(&output_path, e))?;
+    Ok(output_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_size_never_upscales() {
+        assert_eq!(fit_size(2000, 1000, 1000), (1000, 500));
+        assert_eq!(fit_size(600, 900, 1000), (600, 900));
+        assert_eq!(fit_size(3000, 2000, 0), (3000, 2000));
+        assert_eq!(fit_size(5000, 1, 100), (100, 1));
+    }
+
+    #[test]
+    fn crop_box_stays_inside_the_image() {
+        let fractions = [0.0, 0.3, 0.5, 0.95, 1.0];
+        for (width, height) in [(1, 1), (2, 7), (10, 10), (37, 5), (1000, 1500)] {
+            for left in fractions {
+                for top in fractions {
+                    for size in [0.0, 0.5, 1.0] {
+                        let crop = CropBox { left, top, width: size, height: size };
+                        let (x, y, w, h) = crop.to_pixels(width, height);
+                        assert!(w >= 1 && h >= 1, "{:?} in {}x{}", crop, width, height);
+                        assert!(x + w <= width && y + h <= height, "{:?} in {}x{}", crop, width, height);
+                    }
+                }
+            }
+        }
+        assert_eq!(CropBox::default().to_pixels(800, 600), (0, 0, 800, 600));
+        assert_eq!(CropBox { left: 0.25, top: 0.5, width: 0.5, height: 0.25 }.to_pixels(800, 600), (200, 300, 400, 150));
+    }
+
+    fn edited_size(width: u32, height: u32, crop: CoverCrop, max_size: u32) -> (u32, u32) {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_pixel(width, height, Rgba([10, 20, 30, 255])));
+        let edit = CoverEdit { crop, max_size, ..CoverEdit::default() };
+        apply_edit(&image, &edit).dimensions()
+    }
+
+    #[test]
+    fn square_modes_produce_squares() {
+        assert_eq!(edited_size(300, 200, CoverCrop::CenterSquare, 0), (200, 200));
+        assert_eq!(edited_size(300, 200, CoverCrop::CenterSquare, 100), (100, 100));
+        assert_eq!(edited_size(120, 400, CoverCrop::PadBlur, 0), (400, 400));
+        assert_eq!(edited_size(120, 400, CoverCrop::PadBlur, 200), (200, 200));
+        assert_eq!(edited_size(300, 200, CoverCrop::Keep, 150), (150, 100));
+    }
+
+    #[test]
+    fn rendered_cover_is_jpeg() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_pixel(64, 32, Rgba([0, 0, 0, 0])));
+        let mut png = Vec::new();
+        image.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png).expect("png");
+        let edit = CoverEdit { crop: CoverCrop::CenterSquare, ..CoverEdit::default() };
+        let cover = render_cover(&png, &edit).expect("rendered");
+        assert_eq!((cover.width, cover.height, cover.source_width, cover.source_height), (32, 32, 64, 32));
+        assert_eq!(image::guess_format(&cover.jpeg).expect("format"), image::ImageFormat::Jpeg);
+    }
+}
@@ -0,0 +1,101 @@
+// Embedded cover art
+// Pulls the attached picture (MP4 `covr`, ID3 APIC, FLAC/Vorbis picture) out of the source files
+// with ffmpeg so it can be reused as the cover. Each distinct image is written once to the cache
+// directory (named by content hash) and offered on the Cover tab.
+
+use crate::error::{LecternError, Result};
+use crate::utils::hash::fnv1a;
+use std::path::{Path, PathBuf};
+use tokio::process::Command as TokioCommand;
+
+/// One distinct embedded picture.
+#[derive(Debug, Clone)]
+pub struct EmbeddedCover {
+    /// Extracted image file (in the cache directory).
+    pub path: String,
+    /// Source files that carry this picture.
+    pub files: Vec<String>,
+    pub width: u32,
+    pub height: u32,
+}
+
+fn covers_dir() -> PathBuf {
+    crate::config::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("embedded_covers")
+}
+
+/// The first attached picture of `path`, or None when it has none.
+pub async fn extract_cover_bytes(path: &str) -> Result<Option<Vec<u8>>> {
+    let output = TokioCommand::new("ffmpeg")
+        .args(["-v", "error", "-i", path, "-map", "0:v:0?", "-frames:v", "1", "-c:v", "copy", "-f", "image2pipe", "pipe:1"])
+        .output()
+        .await
+        .map_err(|e| LecternError::spawn("ffmpeg", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(LecternError::Other(format!("ffmpeg could not read the cover of {}: {}", path, stderr.trim())));
+    }
+    Ok(Some(output.stdout).filter(|bytes| !bytes.is_empty()))
+}
+
+fn image_extension(bytes: &[u8]) -> &'static str {
+    match ::image::guess_format(bytes) {
+        Ok(::image::ImageFormat::Png) => "png",
+        Ok(::image::ImageFormat::Gif) => "gif",
+        Ok(::image::ImageFormat::WebP) => "webp",
+        Ok(::image::ImageFormat::Bmp) => "bmp",
+        _ => "jpg",
+    }
+}
+
+/// Extract the embedded pictures of `files`; one entry per distinct image, in file order.
+/// Files without a picture (or that ffmpeg cannot read) are skipped.
+pub async fn extract_embedded_covers(files: &[String]) -> Vec<EmbeddedCover> {
+    let dir = covers_dir();
+    let mut covers: Vec<(u64, EmbeddedCover)> = Vec::new();
+    for file in files {
+        let bytes = match extract_cover_bytes(file).await {
+            Ok(Some(bytes)) => bytes,
+            Ok(None) => continue,
+            Err(e) => {
+                println!("[DEBUG] {}", e);
+                continue;
+            }
+        };
+        // Identical pictures in every track collapse into one
+        let hash = fnv1a(&bytes);
+        if let Some((_, cover)) = covers.iter_mut().find(|(h, _)| *h == hash) {
+            cover.files.push(file.clone());
+            continue;
+        }
+        let Ok(image) = ::image::load_from_memory(&bytes) else {
+            println!("[DEBUG] Embedded picture in {} is not a readable image", file);
+            continue;
+        };
+        let path = dir.join(format!("{:016x}.{}", hash, image_extension(&bytes)));
+        if let Err(e) = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, &bytes)) {
+            println!("[DEBUG] Could not save embedded cover {}: {}", path.display(), e);
+            continue;
+        }
+        covers.push((
+            hash,
+            EmbeddedCover {
+                path: path.to_string_lossy().to_string(),
+                files: vec![file.clone()],
+                width: image.width(),
+                height: image.height(),
+            },
+        ));
+    }
+    println!("[DEBUG] Found {} distinct embedded cover(s) in {} file(s)", covers.len(), files.len());
+    covers.into_iter().map(|(_, cover)| cover).collect()
+}
+
+/// Display name of a source file.
+pub fn file_label(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
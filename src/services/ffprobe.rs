@@ -1,7 +1,18 @@
 use std::process::Command;
 use crate::models::chapters::Chapter;
 use std::path::Path;
-use anyhow::{Result, anyhow};
+use crate::error::{LecternError, Result};
+
+/// JSON printed by a finished ffprobe run on `file_path`; a failed run or unreadable output is
+/// an `Ffprobe` error.
+fn ffprobe_json(file_path: &str, output: &std::process::Output) -> Result<serde_json::Value> {
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(LecternError::Ffprobe { path: file_path.to_string(), message: stderr.trim().to_string() });
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| LecternError::Ffprobe { path: file_path.to_string(), message: format!("invalid JSON output: {}", e) })
+}
 
 // Get audio file duration using ffprobe (returns milliseconds)
 pub fn get_audio_file_duration(file_path: &str) -> Result<u64> {
@@ -13,25 +24,18 @@ pub fn get_audio_file_duration(file_path: &str) -> Result<u64> {
             file_path,
         ])
         .output()
-        .map_err(|e| anyhow!("Failed to execute ffprobe: {}. Is ffprobe installed?", e))?;
-    
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("ffprobe failed: {}", stderr));
-    }
-    
-    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
-        .map_err(|e| anyhow!("Failed to parse ffprobe JSON: {}", e))?;
+        .map_err(|e| LecternError::spawn("ffprobe", e))?;
+    let json = ffprobe_json(file_path, &output)?;
     
     // Get duration from format.duration (decimal seconds)
     let duration_sec = json.get("format")
         .and_then(|f| f.get("duration"))
         .and_then(|d| d.as_str())
-        .ok_or_else(|| anyhow!("No duration field in ffprobe output"))?;
+        .ok_or_else(|| LecternError::Ffprobe { path: file_path.to_string(), message: "no duration field in output".to_string() })?;
     
     // Convert decimal seconds to milliseconds
     let duration_sec_f64: f64 = duration_sec.parse()
-        .map_err(|e| anyhow!("Failed to parse duration '{}': {}", duration_sec, e))?;
+        .map_err(|e| LecternError::Parse(format!("Failed to parse duration '{}': {}", duration_sec, e)))?;
     
     let duration_ms = (duration_sec_f64 * 1000.0).round() as u64;
     
@@ -48,19 +52,12 @@ pub fn extract_chapters_from_file(file_path: &str) -> Result<Vec<Chapter>> {
             file_path,
         ])
         .output()
-        .map_err(|e| anyhow!("Failed to execute ffprobe: {}. Is ffprobe installed?", e))?;
-    
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("ffprobe failed: {}", stderr));
-    }
-    
-    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
-        .map_err(|e| anyhow!("Failed to parse ffprobe JSON: {}", e))?;
+        .map_err(|e| LecternError::spawn("ffprobe", e))?;
+    let json = ffprobe_json(file_path, &output)?;
     
     let chapters_array = json.get("chapters")
         .and_then(|c| c.as_array())
-        .ok_or_else(|| anyhow!("No chapters found in file"))?;
+        .ok_or_else(|| LecternError::NotFound("No chapters found in file".to_string()))?;
     
     let mut chapters = Vec::new();
     
@@ -129,3 +126,45 @@ pub fn generate_chapters_from_files(files: &[String]) -> Result<Vec<Chapter>> {
     
     Ok(chapters)
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::{ExitStatus, Output};
+
+    fn output(exit_code: i32, stdout: &str, stderr: &str) -> Output {
+        Output {
+            status: ExitStatus::from_raw(exit_code << 8),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: stderr.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn failed_run_is_ffprobe_error() {
+        let error = ffprobe_json("book.mp3", &output(1, "", "book.mp3: Invalid data found\n")).unwrap_err();
+        match error {
+            LecternError::Ffprobe { path, message } => {
+                assert_eq!(path, "book.mp3");
+                assert_eq!(message, "book.mp3: Invalid data found");
+            }
+            other => panic!("expected Ffprobe, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unreadable_output_is_ffprobe_error() {
+        let error = ffprobe_json("book.mp3", &output(0, "not json", "")).unwrap_err();
+        assert!(matches!(error, LecternError::Ffprobe { .. }));
+        assert!(ffprobe_json("book.mp3", &output(0, "{\"format\": {}}", "")).is_ok());
+    }
+
+    #[test]
+    fn missing_binary_is_ffmpeg_missing() {
+        let error = LecternError::spawn("ffprobe", std::io::Error::from(std::io::ErrorKind::NotFound));
+        assert!(error.is_ffmpeg_missing());
+        let error = LecternError::spawn("ffprobe", std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+        assert!(matches!(error, LecternError::Other(_)));
+    }
+}
@@ -0,0 +1,202 @@
+// On-disk cache for provider responses and cover downloads
+// Used by the shared request layer (services::http_get). Successful GET responses are stored under the XDG cache dir (~/.cache/lectern/http), keyed by URL.
+// Entries expire after the policy TTL; the oldest entries are pruned when the size limit is exceeded.
+// In offline mode only cached entries are served (expired ones included).
+
+use crate::error::{LecternError, Result};
+use crate::utils::hash::fnv1a;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Cache behaviour, set from Settings at startup and whenever it changes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CachePolicy {
+    pub enabled: bool,
+    pub offline: bool,
+    pub ttl_secs: u64,
+    pub max_bytes: u64,
+}
+
+impl CachePolicy {
+    pub const DEFAULT: CachePolicy = CachePolicy {
+        enabled: true,
+        offline: false,
+        ttl_secs: 7 * 24 * 60 * 60, // 7 days
+        max_bytes: 200 * 1024 * 1024, // 200 MB
+    };
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static POLICY: RwLock<CachePolicy> = RwLock::new(CachePolicy::DEFAULT);
+/// Running total of body bytes on disk; None until the first store scans the directory.
+/// Keeps `store` from listing every entry on each write.
+static TOTAL_BYTES: Mutex<Option<u64>> = Mutex::new(None);
+
+pub fn set_policy(policy: CachePolicy) {
+    if let Ok(mut p) = POLICY.write() {
+        *p = policy;
+    }
+}
+
+pub fn policy() -> CachePolicy {
+    POLICY.read().map(|p| *p).unwrap_or_default()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EntryMeta {
+    url: String,
+    fetched_at: u64, // Unix seconds
+    size: u64,
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    let dir = crate::config::cache_dir()?.join("http");
+    let _ = std::fs::create_dir_all(&dir);
+    Some(dir)
+}
+
+fn url_key(url: &str) -> String {
+    format!("{:016x}", fnv1a(url.as_bytes()))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn entry_paths(url: &str) -> Option<(PathBuf, PathBuf)> {
+    let dir = cache_dir()?;
+    let key = url_key(url);
+    Some((dir.join(format!("{}.json", key)), dir.join(format!("{}.bin", key))))
+}
+
+/// Cached body for `url`, if present and (unless `allow_stale`) not expired.
+pub fn lookup(url: &str, allow_stale: bool) -> Option<Vec<u8>> {
+    let policy = policy();
+    if !policy.enabled && !policy.offline {
+        return None;
+    }
+    let (meta_path, body_path) = entry_paths(url)?;
+    let meta: EntryMeta = serde_json::from_str(&std::fs::read_to_string(meta_path).ok()?).ok()?;
+    if meta.url != url {
+        return None; // Hash collision
+    }
+    if !allow_stale && now_secs().saturating_sub(meta.fetched_at) > policy.ttl_secs {
+        return None;
+    }
+    std::fs::read(body_path).ok()
+}
+
+/// Store a successful response body. Failures are ignored (the cache is best-effort).
+pub fn store(url: &str, body: &[u8]) {
+    let policy = policy();
+    if !policy.enabled || body.len() as u64 > policy.max_bytes {
+        return;
+    }
+    let Some((meta_path, body_path)) = entry_paths(url) else {
+        return;
+    };
+    // An entry being replaced no longer counts towards the total
+    let replaced = std::fs::read_to_string(&meta_path)
+        .ok()
+        .and_then(|json| serde_json::from_str::<EntryMeta>(&json).ok())
+        .map(|m| m.size)
+        .unwrap_or(0);
+    let meta = EntryMeta {
+        url: url.to_string(),
+        fetched_at: now_secs(),
+        size: body.len() as u64,
+    };
+    if std::fs::write(&body_path, body).is_err() {
+        return;
+    }
+    if let Ok(json) = serde_json::to_string(&meta) {
+        let _ = std::fs::write(&meta_path, json);
+    }
+    let total = {
+        let Ok(mut total) = TOTAL_BYTES.lock() else {
+            return;
+        };
+        // First store: the scan already sees the new entry, so back it out to apply the delta below
+        let current = total.get_or_insert_with(|| stats().1.saturating_sub(meta.size).saturating_add(replaced));
+        *current = current.saturating_sub(replaced).saturating_add(meta.size);
+        *current
+    };
+    if total > policy.max_bytes {
+        prune(policy.max_bytes);
+    }
+}
+
+/// (entry count, total bytes) currently on disk.
+pub fn stats() -> (usize, u64) {
+    let entries = list_entries();
+    (entries.len(), entries.iter().map(|(_, _, size)| size).sum())
+}
+
+/// Remove every cached entry. Returns the number of bytes freed.
+pub fn clear() -> Result<u64> {
+    let dir = cache_dir().ok_or_else(|| LecternError::Other("No cache directory".to_string()))?;
+    let (_, bytes) = stats();
+    std::fs::remove_dir_all(&dir).map_err(|e| LecternError::io(&dir, e))?;
+    let _ = std::fs::create_dir_all(&dir);
+    set_total(0);
+    Ok(bytes)
+}
+
+/// (key, fetched_at, body size) for every entry.
+fn list_entries() -> Vec<(String, u64, u64)> {
+    let Some(dir) = cache_dir() else {
+        return Vec::new();
+    };
+    let Ok(read_dir) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    read_dir
+        .flatten()
+        .filter_map(|e| {
+            let path = e.path();
+            if path.extension().and_then(|x| x.to_str()) != Some("json") {
+                return None;
+            }
+            let meta: EntryMeta = serde_json::from_str(&std::fs::read_to_string(&path).ok()?).ok()?;
+            let key = path.file_stem()?.to_string_lossy().to_string();
+            Some((key, meta.fetched_at, meta.size))
+        })
+        .collect()
+}
+
+fn set_total(bytes: u64) {
+    if let Ok(mut total) = TOTAL_BYTES.lock() {
+        *total = Some(bytes);
+    }
+}
+
+/// Delete oldest entries until the total size is within `max_bytes`. Only runs when the running
+/// total goes over the limit, so the full directory scan is rare.
+fn prune(max_bytes: u64) {
+    let mut entries = list_entries();
+    let mut total: u64 = entries.iter().map(|(_, _, size)| size).sum();
+    if total <= max_bytes {
+        set_total(total);
+        return;
+    }
+    let Some(dir) = cache_dir() else {
+        return;
+    };
+    entries.sort_by_key(|(_, fetched_at, _)| *fetched_at);
+    for (key, _, size) in entries {
+        if total <= max_bytes {
+            break;
+        }
+        let _ = std::fs::remove_file(dir.join(format!("{}.bin", key)));
+        let _ = std::fs::remove_file(dir.join(format!("{}.json", key)));
+        total = total.saturating_sub(size);
+    }
+    set_total(total);
+}
@@ -0,0 +1,235 @@
+// Pre-conversion input health check
+// Probes every input (probe_audio_file) and decodes it once with ffmpeg to find unreadable or
+// corrupt files, zero-length files, suspicious durations and files whose sample rate/channels
+// differ from the rest, so problems show up before a long conversion instead of at its end.
+
+use crate::error::LecternError;
+use crate::services::conversion::probe_audio_file;
+use tokio::process::Command as TokioCommand;
+
+/// Decoded duration may differ from the header by this much before it is reported.
+const DURATION_TOLERANCE_MS: u64 = 2_000;
+/// Files shorter than this are reported as suspicious.
+const MIN_DURATION_MS: u64 = 1_000;
+/// At most this many decode error lines are kept per file.
+const MAX_ERROR_LINES: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputIssue {
+    /// ffprobe could not read the file.
+    Unreadable(String),
+    /// The file has no bytes.
+    Empty,
+    /// ffmpeg reported errors while decoding (corrupt frames, truncated data).
+    DecodeErrors { count: usize, sample: String },
+    /// Duration is zero/very short, differs between header and decode, or is far below the others.
+    SuspiciousDuration(String),
+    /// Sample rate/channels differ from most inputs (converts fine, but forces a re-encode).
+    ParamsDiffer { sample_rate: u32, channels: u32 },
+}
+
+impl InputIssue {
+    /// Issues that will likely break the conversion or the result.
+    pub fn is_error(&self) -> bool {
+        matches!(self, InputIssue::Unreadable(_) | InputIssue::Empty | InputIssue::DecodeErrors { .. })
+    }
+}
+
+impl std::fmt::Display for InputIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputIssue::Unreadable(msg) => write!(f, "Unreadable: {}", msg),
+            InputIssue::Empty => write!(f, "Empty file"),
+            InputIssue::DecodeErrors { count, sample } => write!(f, "{} decode error(s): {}", count, sample),
+            InputIssue::SuspiciousDuration(msg) => write!(f, "{}", msg),
+            InputIssue::ParamsDiffer { sample_rate, channels } => {
+                write!(f, "{} Hz, {} ch differs from the other files", sample_rate, channels)
+            }
+        }
+    }
+}
+
+/// Result of checking one input file.
+#[derive(Debug, Clone)]
+pub struct InputCheck {
+    pub path: String,
+    pub size: u64,
+    pub codec: Option<String>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u32>,
+    pub duration_ms: Option<u64>,
+    pub issues: Vec<InputIssue>,
+}
+
+impl InputCheck {
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|i| i.is_error())
+    }
+}
+
+/// Check every input; files are probed and decoded in parallel (one ffmpeg per CPU core).
+/// Results keep the order of `files`.
+pub async fn check_inputs(files: Vec<String>) -> Vec<InputCheck> {
+    use futures::stream::{self, StreamExt};
+
+    let workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4).max(1);
+    println!("[DEBUG] Checking {} input file(s) on {} workers", files.len(), workers);
+    let mut checks: Vec<(usize, InputCheck)> = stream::iter(files.into_iter().enumerate())
+        .map(|(index, path)| async move { (index, check_file(path).await) })
+        .buffer_unordered(workers)
+        .collect()
+        .await;
+    checks.sort_by_key(|(index, _)| *index);
+    let mut checks: Vec<InputCheck> = checks.into_iter().map(|(_, check)| check).collect();
+    flag_outliers(&mut checks);
+    checks
+}
+
+async fn check_file(path: String) -> InputCheck {
+    let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    let mut check = InputCheck {
+        path: path.clone(),
+        size,
+        codec: None,
+        sample_rate: None,
+        channels: None,
+        duration_ms: None,
+        issues: Vec::new(),
+    };
+    if size == 0 {
+        check.issues.push(InputIssue::Empty);
+        return check;
+    }
+
+    let probe_path = path.clone();
+    let params = match tokio::task::spawn_blocking(move || probe_audio_file(&probe_path)).await {
+        Ok(Ok(params)) => params,
+        Ok(Err(e)) => {
+            check.issues.push(InputIssue::Unreadable(unreadable_reason(&e)));
+            return check;
+        }
+        Err(e) => {
+            check.issues.push(InputIssue::Unreadable(e.to_string()));
+            return check;
+        }
+    };
+    check.codec = Some(params.codec.clone());
+    check.sample_rate = Some(params.sample_rate);
+    check.channels = Some(params.channels);
+    check.duration_ms = Some(params.duration_ms);
+
+    match decode_file(&path).await {
+        Ok((errors, decoded_ms)) => {
+            if !errors.is_empty() {
+                check.issues.push(InputIssue::DecodeErrors {
+                    count: errors.len(),
+                    sample: errors.iter().take(MAX_ERROR_LINES).cloned().collect::<Vec<_>>().join("; "),
+                });
+            }
+            if let Some(decoded_ms) = decoded_ms {
+                if decoded_ms.abs_diff(params.duration_ms) > DURATION_TOLERANCE_MS {
+                    check.issues.push(InputIssue::SuspiciousDuration(format!(
+                        "Header says {}, decodes to {} (truncated?)",
+                        crate::utils::time::format_time(params.duration_ms, false),
+                        crate::utils::time::format_time(decoded_ms, false)
+                    )));
+                }
+            }
+        }
+        Err(e) => check.issues.push(InputIssue::Unreadable(e.to_string())),
+    }
+
+    if params.duration_ms < MIN_DURATION_MS {
+        check.issues.push(InputIssue::SuspiciousDuration(format!("Only {} ms long", params.duration_ms)));
+    }
+    check
+}
+
+fn unreadable_reason(e: &LecternError) -> String {
+    match e {
+        LecternError::Ffprobe { message, .. } if !message.is_empty() => message.clone(),
+        LecternError::Ffprobe { .. } => "not an audio file ffprobe understands".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Decode the audio to nowhere. Returns the error lines ffmpeg printed and the decoded length
+/// (last `time=` of the progress line).
+pub async fn decode_file(path: &str) -> crate::error::Result<(Vec<String>, Option<u64>)> {
+    let output = TokioCommand::new("ffmpeg")
+        .arg("-hide_banner").arg("-nostdin")
+        .arg("-v").arg("error")
+        .arg("-stats")
+        .arg("-i").arg(path)
+        .arg("-map").arg("0:a")
+        .arg("-f").arg("null").arg("-")
+        .stdout(std::process::Stdio::null())
+        .output()
+        .await
+        .map_err(|e| LecternError::spawn("ffmpeg", e))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut errors = Vec::new();
+    let mut decoded_ms = None;
+    // Progress lines are separated by \r, messages by \n
+    for line in stderr.split(['\r', '\n']).map(str::trim).filter(|l| !l.is_empty()) {
+        if line.starts_with("size=") || line.contains(" time=") {
+            if let Some(ms) = parse_progress_time(line) {
+                decoded_ms = Some(ms);
+            }
+        } else {
+            errors.push(line.to_string());
+        }
+    }
+    if !output.status.success() && errors.is_empty() {
+        errors.push(format!("ffmpeg exited with {:?}", output.status.code()));
+    }
+    Ok((errors, decoded_ms))
+}
+
+/// `time=HH:MM:SS.cc` from an ffmpeg progress line.
+fn parse_progress_time(line: &str) -> Option<u64> {
+    let value = line.split("time=").nth(1)?.split_whitespace().next()?;
+    let mut parts = value.split(':');
+    let hours: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    Some(((hours * 3600.0 + minutes * 60.0 + seconds) * 1000.0).round() as u64)
+}
+
+/// Flag files whose parameters differ from the most common ones, and files far shorter than the
+/// median (a likely cut-off download in a folder of chapters).
+fn flag_outliers(checks: &mut [InputCheck]) {
+    let mut counts: Vec<((u32, u32), usize)> = Vec::new();
+    for check in checks.iter() {
+        if let (Some(rate), Some(channels)) = (check.sample_rate, check.channels) {
+            match counts.iter_mut().find(|(k, _)| *k == (rate, channels)) {
+                Some((_, n)) => *n += 1,
+                None => counts.push(((rate, channels), 1)),
+            }
+        }
+    }
+    if let Some((common, _)) = counts.iter().max_by_key(|(_, n)| *n).copied() {
+        for check in checks.iter_mut() {
+            if let (Some(sample_rate), Some(channels)) = (check.sample_rate, check.channels) {
+                if (sample_rate, channels) != common {
+                    check.issues.push(InputIssue::ParamsDiffer { sample_rate, channels });
+                }
+            }
+        }
+    }
+
+    let mut durations: Vec<u64> = checks.iter().filter_map(|c| c.duration_ms).collect();
+    if durations.len() >= 4 {
+        durations.sort_unstable();
+        let median = durations[durations.len() / 2];
+        for check in checks.iter_mut() {
+            if let Some(duration) = check.duration_ms.filter(|d| *d >= MIN_DURATION_MS && *d * 10 < median) {
+                check.issues.push(InputIssue::SuspiciousDuration(format!(
+                    "{} is far shorter than the typical {}",
+                    crate::utils::time::format_time(duration, false),
+                    crate::utils::time::format_time(median, false)
+                )));
+            }
+        }
+    }
+}
@@ -0,0 +1,296 @@
+// Match scoring for metadata search results
+// Scores each provider result against what we already know about the local book,
+// merges duplicates returned by several providers and sorts best match first.
+
+use crate::models::BookMetadata;
+
+/// A single search result tagged with the provider that returned it.
+#[derive(Debug, Clone)]
+pub struct ProviderResult {
+    pub provider: String, // Display name, e.g. "Audible.com"
+    pub book: BookMetadata,
+}
+
+impl ProviderResult {
+    pub fn new(provider: &str, book: BookMetadata) -> Self {
+        Self {
+            provider: provider.to_string(),
+            book,
+        }
+    }
+}
+
+/// Tag every result of one provider call with the provider name.
+pub fn tag_results(provider: &str, books: Vec<BookMetadata>) -> Vec<ProviderResult> {
+    books.into_iter().map(|b| ProviderResult::new(provider, b)).collect()
+}
+
+/// What we know locally about the book being tagged. Empty strings / None mean "unknown"
+/// and the corresponding signal is left out of the score.
+#[derive(Debug, Clone, Default)]
+pub struct LocalMatchContext {
+    pub title: String,
+    pub query: String, // Search text as typed; compared against the title as a fallback
+    pub author: String,
+    pub asin: Option<String>,
+    pub isbn: Option<String>,
+    pub duration_ms: Option<u64>,
+    pub narrator: Option<String>,
+    pub series: Option<String>,
+}
+
+/// One entry in the ranked result list. Duplicates from several providers are merged into
+/// `book`; the untouched per-provider records are kept in `variants`.
+#[derive(Debug, Clone)]
+pub struct RankedResult {
+    pub book: BookMetadata,
+    pub score: u8, // 0-100
+    pub providers: Vec<String>,
+    pub variants: Vec<ProviderResult>,
+}
+
+// Signal weights (only signals known on both sides take part in the score)
+const WEIGHT_IDENTIFIER: f32 = 40.0;
+const WEIGHT_TITLE: f32 = 30.0;
+const WEIGHT_AUTHOR: f32 = 20.0;
+const WEIGHT_RUNTIME: f32 = 15.0;
+const WEIGHT_NARRATOR: f32 = 8.0;
+const WEIGHT_SERIES: f32 = 7.0;
+
+/// Score, merge and sort provider results. Best match first.
+pub fn rank_results(results: Vec<ProviderResult>, ctx: &LocalMatchContext) -> Vec<RankedResult> {
+    let mut ranked: Vec<RankedResult> = Vec::new();
+
+    for result in results {
+        if let Some(existing) = ranked.iter_mut().find(|r| is_same_book(&r.book, &result.book)) {
+            merge_missing_fields(&mut existing.book, &result.book);
+            if !existing.providers.contains(&result.provider) {
+                existing.providers.push(result.provider.clone());
+            }
+            existing.variants.push(result);
+        } else {
+            ranked.push(RankedResult {
+                book: result.book.clone(),
+                score: 0,
+                providers: vec![result.provider.clone()],
+                variants: vec![result],
+            });
+        }
+    }
+
+    for entry in &mut ranked {
+        entry.score = score_book(&entry.book, ctx);
+    }
+
+    // Stable sort keeps provider order for equal scores
+    ranked.sort_by_key(|r| std::cmp::Reverse(r.score));
+    ranked
+}
+
+/// Score a single candidate against the local context (0-100).
+pub fn score_book(book: &BookMetadata, ctx: &LocalMatchContext) -> u8 {
+    let mut total = 0.0f32;
+    let mut weight = 0.0f32;
+
+    // ASIN / ISBN equality
+    let asin_match = match (ctx.asin.as_deref(), book.asin.as_deref()) {
+        (Some(a), Some(b)) if !a.trim().is_empty() && !b.trim().is_empty() => {
+            Some(a.trim().eq_ignore_ascii_case(b.trim()))
+        }
+        _ => None,
+    };
+    let isbn_match = match (ctx.isbn.as_deref(), book.isbn.as_deref()) {
+        (Some(a), Some(b)) if !normalize_isbn(a).is_empty() && !normalize_isbn(b).is_empty() => {
+            Some(normalize_isbn(a) == normalize_isbn(b))
+        }
+        _ => None,
+    };
+    // Either identifier matching counts; only a known mismatch counts against the candidate
+    if asin_match.is_some() || isbn_match.is_some() {
+        let matched = asin_match == Some(true) || isbn_match == Some(true);
+        weight += WEIGHT_IDENTIFIER;
+        if matched {
+            total += WEIGHT_IDENTIFIER;
+        }
+    }
+
+    // Title: compare with the local title and the typed query, take the better one
+    let candidate_title = match book.subtitle.as_deref() {
+        Some(sub) if !sub.trim().is_empty() => format!("{} {}", book.title, sub),
+        _ => book.title.clone(),
+    };
+    let title_sim = [ctx.title.as_str(), ctx.query.as_str()]
+        .iter()
+        .filter(|t| !t.trim().is_empty())
+        .map(|t| text_similarity(t, &book.title).max(text_similarity(t, &candidate_title)))
+        .fold(None, |acc: Option<f32>, s| Some(acc.map_or(s, |a| a.max(s))));
+    if let Some(sim) = title_sim {
+        weight += WEIGHT_TITLE;
+        total += WEIGHT_TITLE * sim;
+    }
+
+    let book_authors = book.author_display();
+    if !ctx.author.trim().is_empty() && !book_authors.trim().is_empty() {
+        weight += WEIGHT_AUTHOR;
+        total += WEIGHT_AUTHOR * text_similarity(&ctx.author, &book_authors);
+    }
+
+    // Runtime vs the measured book duration
+    if let (Some(local_ms), Some(candidate_ms)) = (ctx.duration_ms, book.duration.as_deref().and_then(parse_duration_text)) {
+        if local_ms > 0 && candidate_ms > 0 {
+            weight += WEIGHT_RUNTIME;
+            total += WEIGHT_RUNTIME * runtime_similarity(local_ms, candidate_ms);
+        }
+    }
+
+    if let (Some(a), Some(b)) = (ctx.narrator.as_deref(), book.narrator_display().as_deref()) {
+        if !a.trim().is_empty() && !b.trim().is_empty() {
+            weight += WEIGHT_NARRATOR;
+            total += WEIGHT_NARRATOR * text_similarity(a, b);
+        }
+    }
+
+    if let (Some(a), Some(b)) = (ctx.series.as_deref(), book.series_name().as_deref()) {
+        if !a.trim().is_empty() && !b.trim().is_empty() {
+            weight += WEIGHT_SERIES;
+            total += WEIGHT_SERIES * text_similarity(a, b);
+        }
+    }
+
+    if weight == 0.0 {
+        return 0;
+    }
+    ((total / weight) * 100.0).round().clamp(0.0, 100.0) as u8
+}
+
+/// Two results describe the same book if they share an ASIN or ISBN, or have the same
+/// normalized title and overlapping authors.
+fn is_same_book(a: &BookMetadata, b: &BookMetadata) -> bool {
+    if let (Some(x), Some(y)) = (a.asin.as_deref(), b.asin.as_deref()) {
+        if !x.trim().is_empty() && x.trim().eq_ignore_ascii_case(y.trim()) {
+            return true;
+        }
+    }
+    if let (Some(x), Some(y)) = (a.isbn.as_deref(), b.isbn.as_deref()) {
+        let (x, y) = (normalize_isbn(x), normalize_isbn(y));
+        if !x.is_empty() && x == y {
+            return true;
+        }
+    }
+    let title_a = normalize_text(&a.title);
+    !title_a.is_empty()
+        && title_a == normalize_text(&b.title)
+        && text_similarity(&a.author_display(), &b.author_display()) >= 0.5
+}
+
+/// Fill empty fields of `target` from `other` (first provider wins on conflicts).
+fn merge_missing_fields(target: &mut BookMetadata, other: &BookMetadata) {
+    fn fill(target: &mut Option<String>, other: &Option<String>) {
+        let empty = target.as_deref().map(|s| s.trim().is_empty()).unwrap_or(true);
+        if empty {
+            if let Some(v) = other {
+                if !v.trim().is_empty() {
+                    *target = Some(v.clone());
+                }
+            }
+        }
+    }
+    fn fill_list<T: Clone>(target: &mut Vec<T>, other: &[T]) {
+        if target.is_empty() {
+            *target = other.to_vec();
+        }
+    }
+    fill_list(&mut target.authors, &other.authors);
+    fill(&mut target.subtitle, &other.subtitle);
+    fill(&mut target.isbn, &other.isbn);
+    fill(&mut target.asin, &other.asin);
+    fill(&mut target.description, &other.description);
+    fill(&mut target.cover_url, &other.cover_url);
+    fill(&mut target.duration, &other.duration);
+    fill_list(&mut target.narrators, &other.narrators);
+    fill(&mut target.publisher, &other.publisher);
+    fill(&mut target.publish_year, &other.publish_year);
+    fill_list(&mut target.series, &other.series);
+    fill_list(&mut target.genres, &other.genres);
+    fill_list(&mut target.tags, &other.tags);
+    fill(&mut target.language, &other.language);
+    if target.explicit.is_none() {
+        target.explicit = other.explicit;
+    }
+    if target.abridged.is_none() {
+        target.abridged = other.abridged;
+    }
+}
+
+/// Lowercase, strip punctuation and collapse whitespace.
+fn normalize_text(s: &str) -> String {
+    s.chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn normalize_isbn(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Token overlap (Dice coefficient) between two strings, 0.0..=1.0.
+/// Short filler words are ignored so "The Hobbit" matches "Hobbit".
+pub fn text_similarity(a: &str, b: &str) -> f32 {
+    const STOP_WORDS: [&str; 8] = ["the", "a", "an", "of", "and", "by", "unabridged", "audiobook"];
+    let tokens = |s: &str| -> Vec<String> {
+        normalize_text(s)
+            .split(' ')
+            .filter(|t| !t.is_empty() && !STOP_WORDS.contains(t))
+            .map(|t| t.to_string())
+            .collect()
+    };
+    let ta = tokens(a);
+    let tb = tokens(b);
+    if ta.is_empty() || tb.is_empty() {
+        return 0.0;
+    }
+    let common = ta.iter().filter(|t| tb.contains(t)).count();
+    (2.0 * common as f32) / (ta.len() + tb.len()) as f32
+}
+
+/// 1.0 when runtimes are within 2%, falling linearly to 0.0 at 20% difference.
+fn runtime_similarity(local_ms: u64, candidate_ms: u64) -> f32 {
+    let diff = (local_ms as f64 - candidate_ms as f64).abs() / local_ms.max(candidate_ms) as f64;
+    if diff <= 0.02 {
+        1.0
+    } else if diff >= 0.20 {
+        0.0
+    } else {
+        (1.0 - (diff - 0.02) / 0.18) as f32
+    }
+}
+
+/// Parse provider duration strings such as "10 hours 5 minutes" or "45 minutes" into ms.
+pub fn parse_duration_text(s: &str) -> Option<u64> {
+    let mut total_minutes = 0u64;
+    let mut found = false;
+    let words: Vec<&str> = s.split_whitespace().collect();
+    for pair in words.windows(2) {
+        if let Ok(n) = pair[0].parse::<u64>() {
+            let unit = pair[1].to_lowercase();
+            if unit.starts_with("hour") || unit.starts_with("hr") {
+                total_minutes += n * 60;
+                found = true;
+            } else if unit.starts_with("min") {
+                total_minutes += n;
+                found = true;
+            }
+        }
+    }
+    if found {
+        Some(total_minutes * 60_000)
+    } else {
+        None
+    }
+}
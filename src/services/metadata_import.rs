@@ -0,0 +1,479 @@
+// Metadata file import
+// Reads the metadata files that ship with audiobook folders: OPF (Calibre / Audiobookshelf),
+// Audiobookshelf's metadata.json and other tools' JSON (book.json), NFO files in the XML (Kodi)
+// or the "Key....: value" text layout of release NFOs, and plain description/info text.
+// Each file becomes one BookMetadata; the UI reviews them field by field in the merge view.
+
+use crate::error::{LecternError, Result};
+use crate::models::metadata::{split_list, split_names};
+use crate::models::{BookMetadata, SeriesEntry};
+use crate::services::abs_sidecar::{parse_opf, parse_series_label, read_sidecar, xml_unescape};
+use regex::Regex;
+use std::fs;
+use std::sync::OnceLock;
+
+/// Whether `name` (a file in the source folder) is one this module can import.
+pub fn is_metadata_file_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    matches!(
+        lower.as_str(),
+        "metadata.json" | "metadata.xml" | "book.json" | "description.txt" | "info.txt"
+    ) || lower.ends_with(".opf")
+        || lower.ends_with(".nfo")
+}
+
+/// Parse one metadata file. Ok(None) when it holds nothing usable.
+pub fn import_metadata_file(path: &str) -> Result<Option<BookMetadata>> {
+    let lower = path.to_lowercase();
+    if lower.ends_with(".opf") {
+        return Ok(read_sidecar(path)?.map(|import| import.metadata));
+    }
+    let bytes = fs::read(path).map_err(|e| LecternError::io(path, e))?;
+    // NFOs are often CP437/Latin-1; lossy keeps the readable part
+    let content = String::from_utf8_lossy(&bytes).trim_start_matches('\u{feff}').to_string();
+    let file_name = std::path::Path::new(&lower)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let book = match file_name.as_str() {
+        "metadata.json" | "book.json" => match read_sidecar(path)? {
+            Some(import) => import.metadata,
+            None => parse_json(&content)?,
+        },
+        "description.txt" => description_only(&content),
+        "info.txt" => {
+            let book = parse_text_nfo(&content);
+            if has_fields(&book) { book } else { description_only(&content) }
+        }
+        _ if content.trim_start().starts_with('<') => {
+            if content.contains("<dc:") {
+                parse_opf(&content)?
+            } else {
+                parse_xml_nfo(&content)?
+            }
+        }
+        _ => parse_text_nfo(&content),
+    };
+    Ok(Some(book).filter(has_fields))
+}
+
+fn has_fields(book: &BookMetadata) -> bool {
+    !book.title.is_empty()
+        || !book.authors.is_empty()
+        || !book.narrators.is_empty()
+        || book.description.is_some()
+        || !book.series.is_empty()
+}
+
+fn description_only(content: &str) -> BookMetadata {
+    BookMetadata { description: non_empty(content), ..Default::default() }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// First four-digit year in a date ("2019-05-02", "May 2019", "(P)2019 Audible").
+fn year_of(value: &str) -> Option<String> {
+    static YEAR: OnceLock<Regex> = OnceLock::new();
+    let re = YEAR.get_or_init(|| Regex::new(r"\b(1[5-9]\d\d|20\d\d)\b").expect("valid regex"));
+    re.captures(value).map(|c| c[1].to_string())
+}
+
+// --- JSON (book.json, non-Audiobookshelf metadata.json) ---
+
+/// Names from a string ("A, B"), an array of strings, or an array of `{ "name": ... }` objects.
+fn json_names(value: Option<&serde_json::Value>, split: fn(&str) -> Vec<String>) -> Vec<String> {
+    match value {
+        Some(serde_json::Value::String(s)) => split(s),
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| match item {
+                serde_json::Value::String(s) => non_empty(s),
+                other => other.get("name").and_then(|n| n.as_str()).and_then(non_empty),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn json_text(value: Option<&serde_json::Value>) -> Option<String> {
+    match value? {
+        serde_json::Value::String(s) => non_empty(s),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Common keys used by download tools and library managers.
+fn parse_json(content: &str) -> Result<BookMetadata> {
+    let json: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| LecternError::Parse(format!("Invalid JSON: {}", e)))?;
+    // Some tools wrap the book in an object
+    let json = ["book", "metadata", "product"]
+        .iter()
+        .find_map(|key| json.get(*key).filter(|v| v.is_object()))
+        .unwrap_or(&json);
+    let first = |keys: &[&str]| keys.iter().find_map(|k| json_text(json.get(*k)));
+    let names = |keys: &[&str], split: fn(&str) -> Vec<String>| {
+        keys.iter()
+            .map(|k| json_names(json.get(*k), split))
+            .find(|names| !names.is_empty())
+            .unwrap_or_default()
+    };
+
+    let number = first(&["series_number", "seriesNumber", "seriesSequence", "sequence", "volume", "book_number"]);
+    let series = match json.get("series") {
+        Some(serde_json::Value::String(name)) if number.is_some() => vec![SeriesEntry::new(name.clone(), number)],
+        Some(serde_json::Value::String(label)) => vec![parse_series_label(label)],
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| match item {
+                serde_json::Value::String(label) => Some(parse_series_label(label)),
+                other => {
+                    let name = json_text(other.get("name").or_else(|| other.get("title")))?;
+                    let number = json_text(other.get("sequence").or_else(|| other.get("position")).or_else(|| other.get("number")));
+                    Some(SeriesEntry::new(name, number))
+                }
+            })
+            .collect(),
+        Some(other) if other.is_object() => json_text(other.get("name"))
+            .map(|name| SeriesEntry::new(name, json_text(other.get("sequence").or_else(|| other.get("position")))))
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(BookMetadata {
+        title: first(&["title", "name"]).unwrap_or_default(),
+        subtitle: first(&["subtitle"]),
+        authors: names(&["authors", "author", "artist"], split_names),
+        narrators: names(&["narrators", "narrator", "narrated_by", "readers"], split_names),
+        series: series.into_iter().filter(|s| !s.name.is_empty()).collect(),
+        genres: names(&["genres", "genre", "categories"], split_list),
+        tags: names(&["tags", "keywords"], split_list),
+        description: first(&["description", "summary", "publisher_summary", "synopsis"]),
+        publisher: first(&["publisher", "publisher_name"]),
+        publish_year: first(&["publishedYear", "year", "published_date", "publishedDate", "release_date", "releaseDate"])
+            .and_then(|d| year_of(&d)),
+        isbn: first(&["isbn", "isbn13", "isbn_13", "isbn10"]),
+        asin: first(&["asin", "audible_asin"]),
+        language: first(&["language"]),
+        explicit: json.get("explicit").and_then(|v| v.as_bool()),
+        abridged: json.get("abridged").and_then(|v| v.as_bool()),
+        ..Default::default()
+    })
+}
+
+// --- NFO ---
+
+/// Which book field an NFO key ("Author", "Read By", "Genre", ...) fills.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NfoField {
+    Title,
+    Subtitle,
+    Author,
+    Narrator,
+    Series,
+    SeriesNumber,
+    Genre,
+    Year,
+    Publisher,
+    Isbn,
+    Asin,
+    Language,
+    Description,
+}
+
+fn nfo_field(key: &str) -> Option<NfoField> {
+    let key = key.trim().to_lowercase();
+    let field = match key.as_str() {
+        "title" | "book title" | "album" | "book" => NfoField::Title,
+        "subtitle" => NfoField::Subtitle,
+        "author" | "authors" | "written by" | "writer" | "artist" | "album artist" => NfoField::Author,
+        "narrator" | "narrators" | "narrated by" | "read by" | "reader" | "performer" => NfoField::Narrator,
+        "series" | "series name" | "set" => NfoField::Series,
+        "series number" | "series #" | "book number" | "volume" | "position" => NfoField::SeriesNumber,
+        "genre" | "genres" | "category" | "style" => NfoField::Genre,
+        "year" | "date" | "release date" | "released" | "publish date" | "published" | "copyright" | "premiered" => {
+            NfoField::Year
+        }
+        "publisher" | "label" | "studio" => NfoField::Publisher,
+        "isbn" => NfoField::Isbn,
+        "asin" => NfoField::Asin,
+        "language" => NfoField::Language,
+        "description" | "plot" | "summary" | "synopsis" | "review" | "publisher's summary" | "book description"
+        | "outline" | "comment" => NfoField::Description,
+        _ => return None,
+    };
+    Some(field)
+}
+
+/// Collects NFO values; later values for single fields do not overwrite earlier ones.
+#[derive(Default)]
+struct NfoBuilder {
+    book: BookMetadata,
+    series: Option<String>,
+    series_number: Option<String>,
+}
+
+impl NfoBuilder {
+    fn set(&mut self, field: NfoField, value: &str) {
+        let Some(value) = non_empty(value) else {
+            return;
+        };
+        let book = &mut self.book;
+        let keep_first = |slot: &mut Option<String>, value: String| {
+            if slot.is_none() {
+                *slot = Some(value);
+            }
+        };
+        match field {
+            NfoField::Title if book.title.is_empty() => book.title = value,
+            NfoField::Title => {}
+            NfoField::Subtitle => keep_first(&mut book.subtitle, value),
+            NfoField::Author => extend_unique(&mut book.authors, split_names(&value)),
+            NfoField::Narrator => extend_unique(&mut book.narrators, split_names(&value)),
+            NfoField::Series => keep_first(&mut self.series, value),
+            NfoField::SeriesNumber => keep_first(&mut self.series_number, value),
+            NfoField::Genre => extend_unique(&mut book.genres, split_list(&value.replace('/', ","))),
+            NfoField::Year => {
+                if let Some(year) = year_of(&value) {
+                    keep_first(&mut book.publish_year, year);
+                }
+            }
+            NfoField::Publisher => keep_first(&mut book.publisher, value),
+            NfoField::Isbn => keep_first(&mut book.isbn, value),
+            NfoField::Asin => keep_first(&mut book.asin, value),
+            NfoField::Language => keep_first(&mut book.language, value),
+            NfoField::Description => keep_first(&mut book.description, value),
+        }
+    }
+
+    fn finish(mut self) -> BookMetadata {
+        if let Some(series) = self.series {
+            // "Series Name, Book 3" / "Series Name #3" in one value
+            let entry = match self.series_number {
+                Some(number) => SeriesEntry::new(series, Some(number)),
+                None => series_with_number(&series),
+            };
+            self.book.series.push(entry);
+        }
+        self.book
+    }
+}
+
+fn extend_unique(list: &mut Vec<String>, values: Vec<String>) {
+    for value in values {
+        if !list.iter().any(|v| v.eq_ignore_ascii_case(&value)) {
+            list.push(value);
+        }
+    }
+}
+
+/// "Name #3", "Name, Book 3", "Name (Book 3)" → entry with number.
+fn series_with_number(value: &str) -> SeriesEntry {
+    static NUMBERED: OnceLock<Regex> = OnceLock::new();
+    let re = NUMBERED.get_or_init(|| {
+        Regex::new(r"(?i)^(.*?)[\s,(\[]*(?:#|book|vol\.?|volume|part)\s*(\d+(?:\.\d+)?)[)\]]?\s*$").expect("valid regex")
+    });
+    match re.captures(value) {
+        Some(c) if !c[1].trim().is_empty() => SeriesEntry::new(c[1].to_string(), Some(c[2].to_string())),
+        _ => parse_series_label(value),
+    }
+}
+
+/// XML NFO (Kodi `<album>`/`<audiobook>` style): every `<key>value</key>` with a known key.
+fn parse_xml_nfo(content: &str) -> Result<BookMetadata> {
+    static ELEMENT: OnceLock<Regex> = OnceLock::new();
+    let element_re = ELEMENT.get_or_init(|| {
+        Regex::new(r"(?s)<([A-Za-z][\w-]*)(?:\s[^>]*)?>([^<]*(?:<!\[CDATA\[.*?\]\]>)?[^<]*)</([A-Za-z][\w-]*)>")
+            .expect("valid regex")
+    });
+    let mut builder = NfoBuilder::default();
+    for captures in element_re.captures_iter(content) {
+        if !captures[1].eq_ignore_ascii_case(&captures[3]) {
+            continue;
+        }
+        if let Some(field) = nfo_field(&captures[1].replace(['_', '-'], " ")) {
+            builder.set(field, &xml_unescape(&captures[2]));
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Text NFO: "Key: value" / "Key.........: value" lines, and a description either on its
+/// key line or in the block below a "Description"-style heading.
+fn parse_text_nfo(content: &str) -> BookMetadata {
+    static KEY: OnceLock<Regex> = OnceLock::new();
+    static HEADING: OnceLock<Regex> = OnceLock::new();
+    let key_re = KEY.get_or_init(|| Regex::new(r"^\s*([A-Za-z][A-Za-z' #/]*?)[\s.]*[:=]\s*(.*?)\s*$").expect("valid regex"));
+    let heading_re = HEADING
+        .get_or_init(|| Regex::new(r"^\s*[\[(<\-=*~ ]*([A-Za-z][A-Za-z' ]*?)[\])>\-=*~ ]*:?\s*$").expect("valid regex"));
+    let separator = |line: &str| {
+        let line = line.trim();
+        line.len() >= 3 && line.chars().all(|c| !c.is_alphanumeric() && !c.is_whitespace())
+    };
+
+    let mut builder = NfoBuilder::default();
+    let mut description: Option<Vec<&str>> = None;
+    for line in content.lines() {
+        if let Some(ref mut lines) = description {
+            if separator(line) && !lines.is_empty() {
+                break;
+            }
+            if !separator(line) {
+                lines.push(line.trim_end());
+            }
+            continue;
+        }
+        if let Some(c) = key_re.captures(line) {
+            if let Some(field) = nfo_field(&c[1]) {
+                if field == NfoField::Description && c[2].is_empty() {
+                    description = Some(Vec::new());
+                } else {
+                    builder.set(field, &c[2]);
+                }
+                continue;
+            }
+        }
+        if let Some(c) = heading_re.captures(line) {
+            if nfo_field(&c[1]) == Some(NfoField::Description) {
+                description = Some(Vec::new());
+            }
+        }
+    }
+    if let Some(lines) = description {
+        builder.set(NfoField::Description, &lines.join("\n"));
+    }
+    builder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_nfo_keys_and_bracketed_description() {
+        let content = "\
+=====================================
+        The Book - Release Info
+=====================================
+Title..........: The Book
+Author.........: Ann Author, Bob Author
+Read By........: Nora Narrator
+Series.........: Saga, Book 2
+Genre..........: Fantasy / Adventure
+Copyright......: (P)2019 Audible Studios
+ASIN...........: B000000000
+
+[ Description ]
+First paragraph.
+
+Second paragraph.
+=====================================
+Ripper.........: someone
+";
+        let book = parse_text_nfo(content);
+        assert_eq!(book.title, "The Book");
+        assert_eq!(book.authors, vec!["Ann Author", "Bob Author"]);
+        assert_eq!(book.narrators, vec!["Nora Narrator"]);
+        assert_eq!(book.series, vec![SeriesEntry::new("Saga", Some("2".to_string()))]);
+        assert_eq!(book.genres, vec!["Fantasy", "Adventure"]);
+        assert_eq!(book.publish_year.as_deref(), Some("2019"));
+        assert_eq!(book.asin.as_deref(), Some("B000000000"));
+        assert_eq!(book.description.as_deref(), Some("First paragraph.\n\nSecond paragraph."));
+    }
+
+    #[test]
+    fn text_nfo_description_on_its_key_line() {
+        let book = parse_text_nfo("Author: Ann Author\nDescription: A short blurb.\nNarrator = Nora Narrator\n");
+        assert_eq!(book.description.as_deref(), Some("A short blurb."));
+        assert_eq!(book.narrators, vec!["Nora Narrator"]);
+    }
+
+    #[test]
+    fn xml_nfo_with_cdata() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<audiobook>
+  <title>The Book &amp; More</title>
+  <author>Ann Author</author>
+  <narrator>Nora Narrator; Ned Narrator</narrator>
+  <series_name>Saga</series_name>
+  <series_number>3</series_number>
+  <plot><![CDATA[A <b>bold</b> plot & more.]]></plot>
+  <year>2018</year>
+  <unknown>ignored</unknown>
+</audiobook>"#;
+        let book = parse_xml_nfo(content).expect("valid nfo");
+        assert_eq!(book.title, "The Book & More");
+        assert_eq!(book.authors, vec!["Ann Author"]);
+        assert_eq!(book.narrators, vec!["Nora Narrator", "Ned Narrator"]);
+        assert_eq!(book.series, vec![SeriesEntry::new("Saga", Some("3".to_string()))]);
+        assert_eq!(book.description.as_deref(), Some("A <b>bold</b> plot & more."));
+        assert_eq!(book.publish_year.as_deref(), Some("2018"));
+    }
+
+    #[test]
+    fn book_json_with_list_and_string_authors() {
+        let listed = r#"{"book": {
+            "title": "The Book",
+            "authors": [{"name": "Ann Author"}, "Bob Author"],
+            "narrators": ["Nora Narrator"],
+            "series": [{"name": "Saga", "sequence": "2.5"}],
+            "genres": "Fantasy, Adventure",
+            "release_date": "2020-02-03",
+            "explicit": true
+        }}"#;
+        let book = parse_json(listed).expect("valid json");
+        assert_eq!(book.title, "The Book");
+        assert_eq!(book.authors, vec!["Ann Author", "Bob Author"]);
+        assert_eq!(book.narrators, vec!["Nora Narrator"]);
+        assert_eq!(book.series, vec![SeriesEntry::new("Saga", Some("2.5".to_string()))]);
+        assert_eq!(book.genres, vec!["Fantasy", "Adventure"]);
+        assert_eq!(book.publish_year.as_deref(), Some("2020"));
+        assert_eq!(book.explicit, Some(true));
+
+        let plain = r#"{"title": "The Book", "author": "Ann Author & Bob Author", "narrator": "Nora Narrator",
+            "series": "Saga", "series_number": 4, "year": 2021}"#;
+        let book = parse_json(plain).expect("valid json");
+        assert_eq!(book.authors, vec!["Ann Author", "Bob Author"]);
+        assert_eq!(book.narrators, vec!["Nora Narrator"]);
+        assert_eq!(book.series, vec![SeriesEntry::new("Saga", Some("4".to_string()))]);
+        assert_eq!(book.publish_year.as_deref(), Some("2021"));
+        assert!(parse_json("not json").is_err());
+    }
+
+    #[test]
+    fn series_numbers_in_the_name() {
+        assert_eq!(series_with_number("Series #3"), SeriesEntry::new("Series", Some("3".to_string())));
+        assert_eq!(series_with_number("Series, Book 2.5"), SeriesEntry::new("Series", Some("2.5".to_string())));
+        assert_eq!(series_with_number("Series (Vol. 4)"), SeriesEntry::new("Series", Some("4".to_string())));
+        assert_eq!(series_with_number("Series"), SeriesEntry::new("Series", None));
+    }
+
+    #[test]
+    fn description_and_info_files() {
+        let dir = std::env::temp_dir().join(format!("lectern-import-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("temp dir");
+        let description = dir.join("description.txt");
+        fs::write(&description, "\u{feff}  Just a blurb: with a colon.\n").expect("write");
+        let book = import_metadata_file(&description.to_string_lossy()).expect("readable").expect("has fields");
+        assert_eq!(book.description.as_deref(), Some("Just a blurb: with a colon."));
+        assert!(book.title.is_empty());
+
+        // info.txt without known keys is a description
+        let info = dir.join("info.txt");
+        fs::write(&info, "Recorded in one take.").expect("write");
+        let book = import_metadata_file(&info.to_string_lossy()).expect("readable").expect("has fields");
+        assert_eq!(book.description.as_deref(), Some("Recorded in one take."));
+
+        let empty = dir.join("empty.nfo");
+        fs::write(&empty, "nothing useful here").expect("write");
+        assert!(import_metadata_file(&empty.to_string_lossy()).expect("readable").is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,312 @@
+// MP4 metadata atoms
+// Writes iTunes-style atoms into moov/udta/meta/ilst of a finished M4B for the tags ffmpeg's
+// muxer cannot write (movement, freeform `----` atoms). The sample data is never moved: when moov
+// is the last box it is rewritten in place, otherwise the old one is turned into a `free` box and
+// the new moov is appended, so the chunk offsets into mdat stay valid either way.
+
+use crate::error::{LecternError, Result};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// `data` atom type codes.
+const DATA_TYPE_UTF8: u32 = 1;
+const DATA_TYPE_INTEGER: u32 = 21;
+/// Namespace of freeform atoms.
+const FREEFORM_MEAN: &str = "com.apple.iTunes";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mp4Value {
+    Text(String),
+    Int8(u8),
+    Int16(u16),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mp4Key {
+    /// Four-character atom, e.g. `©mvn`.
+    Standard([u8; 4]),
+    /// `----:com.apple.iTunes:<name>`.
+    Freeform(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mp4Atom {
+    pub key: Mp4Key,
+    pub value: Mp4Value,
+}
+
+impl Mp4Atom {
+    pub fn standard(kind: [u8; 4], value: Mp4Value) -> Self {
+        Self { key: Mp4Key::Standard(kind), value }
+    }
+
+    pub fn freeform(name: &str, value: String) -> Self {
+        Self { key: Mp4Key::Freeform(name.to_string()), value: Mp4Value::Text(value) }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let (data_type, payload) = match &self.value {
+            Mp4Value::Text(text) => (DATA_TYPE_UTF8, text.as_bytes().to_vec()),
+            Mp4Value::Int8(n) => (DATA_TYPE_INTEGER, vec![*n]),
+            Mp4Value::Int16(n) => (DATA_TYPE_INTEGER, n.to_be_bytes().to_vec()),
+        };
+        let mut data = Vec::with_capacity(8 + payload.len());
+        data.extend_from_slice(&data_type.to_be_bytes());
+        data.extend_from_slice(&[0; 4]); // locale
+        data.extend_from_slice(&payload);
+        let data = make_box(*b"data", &data);
+        match &self.key {
+            Mp4Key::Standard(kind) => make_box(*kind, &data),
+            Mp4Key::Freeform(name) => {
+                let mut body = make_box(*b"mean", &full_box_body(FREEFORM_MEAN.as_bytes()));
+                body.extend(make_box(*b"name", &full_box_body(name.as_bytes())));
+                body.extend(data);
+                make_box(*b"----", &body)
+            }
+        }
+    }
+}
+
+/// A box inside a buffer: `start` is the header, `body` the content after the header.
+#[derive(Debug, Clone, Copy)]
+struct BoxRef {
+    kind: [u8; 4],
+    start: usize,
+    body: usize,
+    end: usize,
+}
+
+fn make_box(kind: [u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(8 + body.len());
+    out.extend_from_slice(&((8 + body.len()) as u32).to_be_bytes());
+    out.extend_from_slice(&kind);
+    out.extend_from_slice(body);
+    out
+}
+
+/// Version/flags word followed by `body`.
+fn full_box_body(body: &[u8]) -> Vec<u8> {
+    let mut out = vec![0; 4];
+    out.extend_from_slice(body);
+    out
+}
+
+fn invalid(path: &Path, message: &str) -> LecternError {
+    LecternError::Other(format!("Cannot write MP4 tags to {}: {}", path.display(), message))
+}
+
+/// Boxes in `data[start..end]`.
+fn child_boxes(data: &[u8], start: usize, end: usize) -> Option<Vec<BoxRef>> {
+    let mut boxes = Vec::new();
+    let mut pos = start;
+    while pos + 8 <= end {
+        let size = u32::from_be_bytes(data[pos..pos + 4].try_into().ok()?) as usize;
+        let kind: [u8; 4] = data[pos + 4..pos + 8].try_into().ok()?;
+        let (body, box_end) = match size {
+            0 => (pos + 8, end),
+            1 => {
+                let large = u64::from_be_bytes(data.get(pos + 8..pos + 16)?.try_into().ok()?) as usize;
+                (pos + 16, pos.checked_add(large)?)
+            }
+            n => (pos + 8, pos.checked_add(n)?),
+        };
+        if box_end > end || box_end < body {
+            return None;
+        }
+        boxes.push(BoxRef { kind, start: pos, body, end: box_end });
+        pos = box_end;
+    }
+    Some(boxes)
+}
+
+/// Name of a freeform (`----`) atom.
+fn freeform_name(data: &[u8], atom: &BoxRef) -> Option<String> {
+    let name = child_boxes(data, atom.body, atom.end)?.into_iter().find(|b| &b.kind == b"name")?;
+    data.get(name.body + 4..name.end).map(|n| String::from_utf8_lossy(n).to_string())
+}
+
+/// New ilst body: existing atoms minus the ones being replaced, then `atoms`.
+fn rebuild_ilst(data: &[u8], ilst: Option<&BoxRef>, atoms: &[Mp4Atom]) -> Option<Vec<u8>> {
+    let mut body = Vec::new();
+    if let Some(ilst) = ilst {
+        for child in child_boxes(data, ilst.body, ilst.end)? {
+            let replaced = atoms.iter().any(|atom| match &atom.key {
+                Mp4Key::Standard(kind) => *kind == child.kind,
+                Mp4Key::Freeform(name) => {
+                    &child.kind == b"----"
+                        && freeform_name(data, &child).is_some_and(|n| n.eq_ignore_ascii_case(name))
+                }
+            });
+            if !replaced {
+                body.extend_from_slice(&data[child.start..child.end]);
+            }
+        }
+    }
+    for atom in atoms {
+        body.extend(atom.encode());
+    }
+    Some(body)
+}
+
+/// Rebuild `moov` with the atoms merged into udta/meta/ilst (created when missing).
+fn rebuild_moov(moov: &[u8], atoms: &[Mp4Atom]) -> Option<Vec<u8>> {
+    let top = child_boxes(moov, 0, moov.len())?;
+    let moov_box = top.first().filter(|b| &b.kind == b"moov")?;
+    let children = child_boxes(moov, moov_box.body, moov_box.end)?;
+
+    let udta = children.iter().find(|b| &b.kind == b"udta");
+    let udta_children = match udta {
+        Some(udta) => child_boxes(moov, udta.body, udta.end)?,
+        None => Vec::new(),
+    };
+    let meta = udta_children.iter().find(|b| &b.kind == b"meta");
+    // meta is a full box: version/flags before its children
+    let meta_children = match meta {
+        Some(meta) => child_boxes(moov, meta.body + 4, meta.end)?,
+        None => Vec::new(),
+    };
+    let ilst = meta_children.iter().find(|b| &b.kind == b"ilst");
+
+    let mut meta_body = vec![0; 4];
+    if !meta_children.iter().any(|b| &b.kind == b"hdlr") {
+        let mut hdlr = vec![0; 8]; // version/flags, pre_defined
+        hdlr.extend_from_slice(b"mdirappl");
+        hdlr.extend_from_slice(&[0; 9]); // reserved, empty name
+        meta_body.extend(make_box(*b"hdlr", &hdlr));
+    }
+    for child in meta_children.iter().filter(|b| &b.kind != b"ilst") {
+        meta_body.extend_from_slice(&moov[child.start..child.end]);
+    }
+    meta_body.extend(make_box(*b"ilst", &rebuild_ilst(moov, ilst, atoms)?));
+
+    let mut udta_body = Vec::new();
+    for child in udta_children.iter().filter(|b| &b.kind != b"meta") {
+        udta_body.extend_from_slice(&moov[child.start..child.end]);
+    }
+    udta_body.extend(make_box(*b"meta", &meta_body));
+
+    let mut moov_body = Vec::new();
+    for child in children.iter().filter(|b| &b.kind != b"udta") {
+        moov_body.extend_from_slice(&moov[child.start..child.end]);
+    }
+    moov_body.extend(make_box(*b"udta", &udta_body));
+    Some(make_box(*b"moov", &moov_body))
+}
+
+/// Read the top-level box headers of the file: (kind, offset, size).
+fn top_level_boxes(file: &mut fs::File, len: u64) -> std::io::Result<Vec<([u8; 4], u64, u64)>> {
+    let mut boxes = Vec::new();
+    let mut pos = 0u64;
+    while pos + 8 <= len {
+        let mut header = [0u8; 16];
+        file.seek(SeekFrom::Start(pos))?;
+        let available = (len - pos).min(16) as usize;
+        file.read_exact(&mut header[..available])?;
+        let kind: [u8; 4] = [header[4], header[5], header[6], header[7]];
+        let size = match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+            0 => len - pos,
+            1 if available == 16 => u64::from_be_bytes(header[8..16].try_into().unwrap_or_default()),
+            n => n as u64,
+        };
+        if size < 8 {
+            break;
+        }
+        boxes.push((kind, pos, size));
+        pos = pos.saturating_add(size);
+    }
+    Ok(boxes)
+}
+
+/// Merge `atoms` into the file's ilst, replacing atoms with the same key.
+pub fn write_mp4_atoms(path: &str, atoms: &[Mp4Atom]) -> Result<()> {
+    if atoms.is_empty() {
+        return Ok(());
+    }
+    let file_path = Path::new(path);
+    let mut file = OpenOptions::new().read(true).write(true).open(file_path)
+        .map_err(|e| LecternError::io(file_path, e))?;
+    let len = file.metadata().map_err(|e| LecternError::io(file_path, e))?.len();
+    let boxes = top_level_boxes(&mut file, len).map_err(|e| LecternError::io(file_path, e))?;
+    let (_, moov_offset, moov_size) = *boxes.iter().find(|(kind, _, _)| kind == b"moov")
+        .ok_or_else(|| invalid(file_path, "no moov box"))?;
+
+    let mut moov = vec![0u8; moov_size as usize];
+    file.seek(SeekFrom::Start(moov_offset)).map_err(|e| LecternError::io(file_path, e))?;
+    file.read_exact(&mut moov).map_err(|e| LecternError::io(file_path, e))?;
+    let new_moov = rebuild_moov(&moov, atoms).ok_or_else(|| invalid(file_path, "malformed moov box"))?;
+
+    let io = |e| LecternError::io(file_path, e);
+    if moov_offset + moov_size == len {
+        // moov is last (ffmpeg's default layout): replace it in place
+        file.set_len(moov_offset).map_err(io)?;
+        file.seek(SeekFrom::Start(moov_offset)).map_err(io)?;
+    } else {
+        // moov before mdat: keep the offsets by freeing the old box and appending the new one
+        file.seek(SeekFrom::Start(moov_offset + 4)).map_err(io)?;
+        file.write_all(b"free").map_err(io)?;
+        file.seek(SeekFrom::End(0)).map_err(io)?;
+    }
+    file.write_all(&new_moov).map_err(io)?;
+    file.sync_all().map_err(io)?;
+    println!("[DEBUG] Wrote {} MP4 atom(s) to {}", atoms.len(), path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The box at `path` below the top of `data` (meta's version/flags are skipped).
+    fn find_box(data: &[u8], path: &[&[u8; 4]]) -> Option<BoxRef> {
+        let mut boxes = child_boxes(data, 0, data.len())?;
+        let mut found = None;
+        for kind in path {
+            let current = boxes.into_iter().find(|b| &b.kind == *kind)?;
+            let body = if &current.kind == b"meta" { current.body + 4 } else { current.body };
+            boxes = child_boxes(data, body, current.end)?;
+            found = Some(current);
+        }
+        found
+    }
+
+    fn ilst_atoms(moov: &[u8]) -> Vec<BoxRef> {
+        let ilst = find_box(moov, &[b"moov", b"udta", b"meta", b"ilst"]).expect("ilst");
+        child_boxes(moov, ilst.body, ilst.end).expect("ilst children")
+    }
+
+    #[test]
+    fn missing_udta_meta_and_ilst_are_created() {
+        let moov = make_box(*b"moov", &make_box(*b"mvhd", &[0; 8]));
+        let atoms = [Mp4Atom::standard(*b"\xa9mvn", Mp4Value::Text("Saga".to_string()))];
+        let rebuilt = rebuild_moov(&moov, &atoms).expect("valid moov");
+
+        assert!(find_box(&rebuilt, &[b"moov", b"mvhd"]).is_some());
+        let hdlr = find_box(&rebuilt, &[b"moov", b"udta", b"meta", b"hdlr"]).expect("hdlr");
+        assert_eq!(&rebuilt[hdlr.body + 8..hdlr.body + 16], b"mdirappl");
+        let children = ilst_atoms(&rebuilt);
+        assert_eq!(children.len(), 1);
+        assert_eq!(&rebuilt[children[0].start..children[0].end], atoms[0].encode().as_slice());
+    }
+
+    #[test]
+    fn freeform_atoms_are_replaced_case_insensitively() {
+        let existing = [
+            Mp4Atom::freeform("asin", "OLD".to_string()),
+            Mp4Atom::freeform("ISBN", "9780000000000".to_string()),
+            Mp4Atom::standard(*b"\xa9nam", Mp4Value::Text("Title".to_string())),
+        ];
+        let moov = rebuild_moov(&make_box(*b"moov", &make_box(*b"mvhd", &[0; 8])), &existing).expect("valid moov");
+        let rebuilt = rebuild_moov(&moov, &[Mp4Atom::freeform("ASIN", "NEW".to_string())]).expect("valid moov");
+
+        let children = ilst_atoms(&rebuilt);
+        let names: Vec<Option<String>> = children.iter().map(|b| freeform_name(&rebuilt, b)).collect();
+        assert_eq!(names, vec![Some("ISBN".to_string()), None, Some("ASIN".to_string())]);
+        let last = children.last().expect("new atom");
+        assert!(rebuilt[last.start..last.end].ends_with(b"NEW"));
+        // Still one hdlr after the second pass
+        let meta = find_box(&rebuilt, &[b"moov", b"udta", b"meta"]).expect("meta");
+        let meta_children = child_boxes(&rebuilt, meta.body + 4, meta.end).expect("meta children");
+        assert_eq!(meta_children.iter().filter(|b| &b.kind == b"hdlr").count(), 1);
+    }
+}
@@ -1,6 +1,6 @@
 use std::path::Path;
 use tokio::process::Command as TokioCommand;
-use anyhow::{Result, anyhow};
+use crate::error::{LecternError, Result};
 use crate::services::ffprobe::get_audio_file_duration;
 
 // Play a chapter from an audio file (headless, no window)
@@ -10,7 +10,7 @@ pub async fn play_chapter_headless(file_path: &str, start_time_ms: u64, duration
     let path = Path::new(file_path);
     
     if !path.exists() {
-        return Err(anyhow!("Audio file not found: {}", file_path));
+        return Err(LecternError::io(path, std::io::Error::new(std::io::ErrorKind::NotFound, "audio file not found")));
     }
     
     // Convert milliseconds to seconds for player
@@ -93,7 +93,7 @@ pub async fn play_chapter_headless(file_path: &str, start_time_ms: u64, duration
     
     match ffmpeg_cmd.spawn() {
         Ok(child) => Ok(child),
-        Err(e) => Err(anyhow!("No headless audio player found and ffmpeg fallback failed: {}", e)),
+        Err(e) => Err(LecternError::spawn("ffmpeg", e)),
     }
 }
 
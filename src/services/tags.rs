@@ -0,0 +1,286 @@
+// Tag mapping
+// Which book field goes into which tag, per output format. Everything that writes tags
+// (the ffmetadata file, the -metadata flags and the MP4 atom pass) reads from here so the
+// formats stay consistent. The table is documented in docs/tag_mapping.md; keep both in sync.
+
+use crate::models::BookMetadata;
+use crate::services::conversion::OutputFormat;
+use crate::services::mp4_tags::{Mp4Atom, Mp4Value};
+
+/// Apple's `desc` atom is shown truncated; the full text goes in `ldes`.
+const SHORT_DESCRIPTION_CHARS: usize = 255;
+/// `stik` value for audiobooks.
+const MEDIA_KIND_AUDIOBOOK: u8 = 2;
+
+fn non_empty(value: Option<&String>) -> Option<&str> {
+    value.map(|v| v.trim()).filter(|v| !v.is_empty())
+}
+
+/// Keys ffmpeg maps to real tags for the given format (ffmetadata / `-metadata`).
+/// MP4: the iTunes atoms ffmpeg's muxer knows (©nam, ©alb, ©ART, aART, ©wrt, ©gen, ©day, desc,
+/// ldes, stik, rtng); the rest is written by `mp4_atoms`. MP3/Ogg/FLAC: ID3 frames or Vorbis
+/// comments, with Audiobookshelf's names for the fields that have no standard frame.
+pub fn ffmpeg_tags(book: &BookMetadata, format: OutputFormat) -> Vec<(&'static str, String)> {
+    let mut tags = Vec::new();
+    let title = book.title.trim();
+    let author = book.author_display();
+    if !title.is_empty() {
+        tags.push(("title", title.to_string()));
+        tags.push(("album", title.to_string()));
+    }
+    if !author.is_empty() {
+        tags.push(("artist", author.clone()));
+        tags.push(("album_artist", author));
+    }
+    if let Some(narrators) = book.narrator_display() {
+        tags.push(("composer", narrators));
+    }
+    if let Some(genres) = book.genre_display() {
+        tags.push(("genre", genres));
+    }
+    if let Some(year) = non_empty(book.publish_year.as_ref()) {
+        tags.push(("date", year.to_string()));
+    }
+
+    if format == OutputFormat::M4b {
+        if let Some(description) = non_empty(book.description.as_ref()) {
+            tags.push(("description", description.chars().take(SHORT_DESCRIPTION_CHARS).collect()));
+            tags.push(("synopsis", description.to_string()));
+        }
+        tags.push(("media_type", MEDIA_KIND_AUDIOBOOK.to_string()));
+        if let Some(explicit) = book.explicit {
+            tags.push(("rating", if explicit { "1" } else { "0" }.to_string()));
+        }
+        return tags;
+    }
+
+    if let Some(description) = non_empty(book.description.as_ref()) {
+        tags.push(("comment", description.to_string()));
+    }
+    let (series, series_part, tag_list) = (book.series_name(), book.series_number(), book.tags_display());
+    let text_fields = [
+        ("subtitle", book.subtitle.as_ref()),
+        ("publisher", book.publisher.as_ref()),
+        ("series", series.as_ref()),
+        ("series-part", series_part.as_ref()),
+        ("language", book.language.as_ref()),
+        ("isbn", book.isbn.as_ref()),
+        ("asin", book.asin.as_ref()),
+        ("tags", tag_list.as_ref()),
+    ];
+    for (key, value) in text_fields {
+        if let Some(value) = non_empty(value) {
+            tags.push((key, value.to_string()));
+        }
+    }
+    if let Some(explicit) = book.explicit {
+        tags.push(("explicit", bool_flag(explicit)));
+    }
+    if let Some(abridged) = book.abridged {
+        tags.push(("abridged", bool_flag(abridged)));
+    }
+    tags
+}
+
+/// MP4 atoms ffmpeg cannot write: movement (primary series), freeform `----:com.apple.iTunes:*` fields.
+pub fn mp4_atoms(book: &BookMetadata) -> Vec<Mp4Atom> {
+    let mut atoms = Vec::new();
+    let (series, series_part, tag_list) = (book.series_name(), book.series_number(), book.tags_display());
+    if let Some(series) = non_empty(series.as_ref()) {
+        atoms.push(Mp4Atom::standard(*b"\xa9mvn", Mp4Value::Text(series.to_string())));
+        // ©mvi is an integer; "2.5" style numbers only make it into the freeform tag
+        if let Some(number) = non_empty(series_part.as_ref()).and_then(|n| n.parse::<u16>().ok()) {
+            atoms.push(Mp4Atom::standard(*b"\xa9mvi", Mp4Value::Int16(number)));
+        }
+        atoms.push(Mp4Atom::standard(*b"shwm", Mp4Value::Int8(1)));
+    }
+    let freeform = [
+        ("SUBTITLE", book.subtitle.as_ref()),
+        ("PUBLISHER", book.publisher.as_ref()),
+        ("SERIES", series.as_ref()),
+        ("SERIES-PART", series_part.as_ref()),
+        ("LANGUAGE", book.language.as_ref()),
+        ("ISBN", book.isbn.as_ref()),
+        ("ASIN", book.asin.as_ref()),
+        ("TAGS", tag_list.as_ref()),
+    ];
+    for (name, value) in freeform {
+        if let Some(value) = non_empty(value) {
+            atoms.push(Mp4Atom::freeform(name, value.to_string()));
+        }
+    }
+    if let Some(abridged) = book.abridged {
+        atoms.push(Mp4Atom::freeform("ABRIDGED", bool_flag(abridged)));
+    }
+    atoms
+}
+
+fn bool_flag(value: bool) -> String {
+    if value { "1" } else { "0" }.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SeriesEntry;
+    use crate::services::mp4_tags::Mp4Key;
+
+    const TAG_MAPPING_DOC: &str = include_str!("../../docs/tag_mapping.md");
+
+    fn full_book() -> BookMetadata {
+        BookMetadata {
+            title: "The Book".to_string(),
+            subtitle: Some("A Subtitle".to_string()),
+            authors: vec!["Ann Author".to_string(), "Bob Author".to_string()],
+            isbn: Some("9780000000000".to_string()),
+            asin: Some("B000000000".to_string()),
+            description: Some("x".repeat(300)),
+            narrators: vec!["Nora Narrator".to_string()],
+            publisher: Some("Publisher".to_string()),
+            publish_year: Some("2020".to_string()),
+            series: vec![SeriesEntry::new("Saga", Some("3".to_string()))],
+            genres: vec!["Fantasy".to_string()],
+            tags: vec!["dragons".to_string()],
+            language: Some("English".to_string()),
+            explicit: Some(true),
+            abridged: Some(false),
+            ..BookMetadata::default()
+        }
+    }
+
+    fn value<'a>(tags: &'a [(&'static str, String)], key: &str) -> Option<&'a str> {
+        tags.iter().find(|(k, _)| *k == key).map(|(_, v)| v.as_str())
+    }
+
+    fn keys(tags: &[(&'static str, String)]) -> Vec<&'static str> {
+        tags.iter().map(|(k, _)| *k).collect()
+    }
+
+    /// Atom name as written in the docs (`©mvn`, `----:com.apple.iTunes:ASIN`).
+    fn atom_name(atom: &Mp4Atom) -> String {
+        match &atom.key {
+            Mp4Key::Standard(kind) if kind[0] == 0xa9 => format!("©{}", String::from_utf8_lossy(&kind[1..])),
+            Mp4Key::Standard(kind) => String::from_utf8_lossy(kind).to_string(),
+            Mp4Key::Freeform(name) => format!("----:com.apple.iTunes:{}", name),
+        }
+    }
+
+    #[test]
+    fn m4b_description_is_truncated_with_full_text_in_synopsis() {
+        let tags = ffmpeg_tags(&full_book(), OutputFormat::M4b);
+        assert_eq!(value(&tags, "description").map(|d| d.chars().count()), Some(SHORT_DESCRIPTION_CHARS));
+        assert_eq!(value(&tags, "synopsis").map(|d| d.chars().count()), Some(300));
+        assert_eq!(value(&tags, "media_type"), Some("2"));
+        assert_eq!(value(&tags, "comment"), None);
+    }
+
+    #[test]
+    fn m4b_rating_only_when_explicit_is_set() {
+        let mut book = full_book();
+        assert_eq!(value(&ffmpeg_tags(&book, OutputFormat::M4b), "rating"), Some("1"));
+        book.explicit = Some(false);
+        assert_eq!(value(&ffmpeg_tags(&book, OutputFormat::M4b), "rating"), Some("0"));
+        book.explicit = None;
+        assert_eq!(value(&ffmpeg_tags(&book, OutputFormat::M4b), "rating"), None);
+    }
+
+    #[test]
+    fn other_formats_use_txxx_style_keys() {
+        for format in [OutputFormat::Mp3, OutputFormat::OggOpus, OutputFormat::Flac] {
+            let tags = ffmpeg_tags(&full_book(), format);
+            assert_eq!(value(&tags, "series"), Some("Saga"));
+            assert_eq!(value(&tags, "series-part"), Some("3"));
+            assert_eq!(value(&tags, "asin"), Some("B000000000"));
+            assert_eq!(value(&tags, "explicit"), Some("1"));
+            assert_eq!(value(&tags, "comment").map(|d| d.len()), Some(300));
+            for m4b_only in ["description", "synopsis", "media_type", "rating"] {
+                assert_eq!(value(&tags, m4b_only), None, "{} in {:?}", m4b_only, format);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_fields_are_skipped() {
+        let book = BookMetadata {
+            title: "  ".to_string(),
+            subtitle: Some(" ".to_string()),
+            description: Some(String::new()),
+            series: vec![SeriesEntry::new("", None)],
+            ..BookMetadata::default()
+        };
+        assert_eq!(keys(&ffmpeg_tags(&book, OutputFormat::M4b)), vec!["media_type"]);
+        assert!(ffmpeg_tags(&book, OutputFormat::Mp3).is_empty());
+        assert!(mp4_atoms(&book).is_empty());
+    }
+
+    #[test]
+    fn movement_number_only_for_whole_numbers() {
+        let mut book = full_book();
+        let atoms = mp4_atoms(&book);
+        assert!(atoms.contains(&Mp4Atom::standard(*b"\xa9mvi", Mp4Value::Int16(3))));
+        assert!(atoms.contains(&Mp4Atom::standard(*b"shwm", Mp4Value::Int8(1))));
+
+        book.series = vec![SeriesEntry::new("Saga", Some("2.5".to_string()))];
+        let atoms = mp4_atoms(&book);
+        assert!(!atoms.iter().any(|a| a.key == Mp4Key::Standard(*b"\xa9mvi")));
+        assert!(atoms.contains(&Mp4Atom::freeform("SERIES-PART", "2.5".to_string())));
+    }
+
+    /// Rows of the markdown tables in the section whose heading starts with `heading`.
+    fn doc_rows(heading: &str) -> Vec<Vec<String>> {
+        let section = TAG_MAPPING_DOC
+            .split("\n## ")
+            .find(|s| s.starts_with(heading))
+            .unwrap_or_else(|| panic!("no '{}' section in docs/tag_mapping.md", heading));
+        section
+            .lines()
+            .filter(|line| line.starts_with('|') && !line.starts_with("|--"))
+            .skip(1) // header
+            .map(|line| line.trim_matches('|').split('|').map(|cell| cell.trim().to_string()).collect())
+            .collect()
+    }
+
+    /// Text between the first pair of backticks.
+    fn code(cell: &str) -> Option<&str> {
+        cell.split('`').nth(1)
+    }
+
+    #[test]
+    fn docs_match_the_mapping() {
+        let book = full_book();
+
+        let rows = doc_rows("M4B");
+        let mut documented: Vec<&str> = rows.iter()
+            .filter_map(|row| row[2].strip_prefix("ffmpeg ").and_then(code))
+            .collect();
+        let mut written = keys(&ffmpeg_tags(&book, OutputFormat::M4b));
+        documented.sort_unstable();
+        written.sort_unstable();
+        assert_eq!(documented, written, "M4B ffmpeg keys in docs/tag_mapping.md");
+
+        let lectern_rows: Vec<&Vec<String>> = rows.iter().filter(|row| row[2] == "Lectern").collect();
+        let mut documented: Vec<String> = lectern_rows.iter().filter_map(|row| code(&row[1]).map(str::to_string)).collect();
+        let mut written: Vec<String> = mp4_atoms(&book).iter().map(atom_name)
+            // shwm only appears in the notes of the movement row
+            .filter(|name| !lectern_rows.iter().any(|row| row[3].contains(&format!("`{}`", name))))
+            .collect();
+        documented.sort_unstable();
+        written.sort_unstable();
+        assert_eq!(documented, written, "M4B atoms in docs/tag_mapping.md");
+
+        let rows = doc_rows("MP3");
+        let mut documented: Vec<&str> = rows.iter().filter_map(|row| code(&row[1])).collect();
+        for row in &rows {
+            let (key, frame) = (code(&row[1]).unwrap_or_default(), code(&row[2]).unwrap_or_default());
+            if let Some(description) = frame.strip_prefix("TXXX:") {
+                assert_eq!(description, key, "TXXX description for {}", key);
+            }
+        }
+        for format in [OutputFormat::Mp3, OutputFormat::OggOpus, OutputFormat::Flac] {
+            let mut written = keys(&ffmpeg_tags(&book, format));
+            documented.sort_unstable();
+            written.sort_unstable();
+            assert_eq!(documented, written, "{:?} keys in docs/tag_mapping.md", format);
+        }
+    }
+}
@@ -0,0 +1,213 @@
+// Post-conversion verification
+// Probes the written file(s) and compares them with what was asked for: total duration against
+// the inputs, chapter count and start times, title/author tags and the cover, and decodes the
+// audio once so a file that only breaks halfway through is caught before it is uploaded.
+
+use crate::error::{LecternError, Result};
+use crate::models::chapters::Chapter;
+use tokio::process::Command as TokioCommand;
+
+/// Output duration may differ from the inputs by this much (plus 0.1% for long books).
+const DURATION_TOLERANCE_MS: u64 = 2_000;
+/// Chapter starts may move this much (AAC frame alignment, keyframe cuts between parts).
+const CHAPTER_TOLERANCE_MS: u64 = 1_000;
+
+/// What the conversion was asked to produce.
+#[derive(Debug, Clone)]
+pub struct ExpectedOutput {
+    /// Summed input duration (after trimming); None when the inputs could not be probed.
+    pub duration_ms: Option<u64>,
+    pub chapters: Vec<Chapter>,
+    pub title: String,
+    pub author: String,
+    pub cover: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifyCheck {
+    pub name: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VerificationReport {
+    pub checks: Vec<VerifyCheck>,
+}
+
+impl VerificationReport {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.passed)
+    }
+
+    fn push(&mut self, name: &'static str, passed: bool, detail: String) {
+        if !passed {
+            println!("[DEBUG] Verification failed: {}: {}", name, detail);
+        }
+        self.checks.push(VerifyCheck { name, passed, detail });
+    }
+}
+
+/// Probed facts about one output file.
+struct ProbedOutput {
+    duration_ms: u64,
+    chapter_starts: Vec<u64>,
+    tags: Vec<(String, String)>,
+    has_picture: bool,
+}
+
+impl ProbedOutput {
+    fn tag(&self, key: &str) -> Option<&str> {
+        self.tags.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, v)| v.as_str())
+    }
+}
+
+/// Verify the written file(s). Parts are treated as one book: durations add up and chapter
+/// starts are offset by the length of the preceding parts.
+pub async fn verify_output(expected: &ExpectedOutput, output_paths: &[String]) -> VerificationReport {
+    let mut report = VerificationReport::default();
+    let mut probed = Vec::new();
+    for path in output_paths {
+        match probe_output(path).await {
+            Ok(output) => probed.push(output),
+            Err(e) => report.push("Readable", false, format!("{}: {}", file_name(path), e)),
+        }
+    }
+    if probed.len() != output_paths.len() {
+        return report;
+    }
+
+    // Duration
+    let total_ms: u64 = probed.iter().map(|p| p.duration_ms).sum();
+    match expected.duration_ms {
+        Some(expected_ms) => {
+            let tolerance = DURATION_TOLERANCE_MS + expected_ms / 1000;
+            report.push(
+                "Duration",
+                total_ms.abs_diff(expected_ms) <= tolerance,
+                format!("{} (inputs: {})", format_ms(total_ms), format_ms(expected_ms)),
+            );
+        }
+        None => report.push("Duration", total_ms > 0, format!("{} (input length unknown)", format_ms(total_ms))),
+    }
+
+    // Chapters
+    let mut starts = Vec::new();
+    let mut offset = 0;
+    for output in &probed {
+        starts.extend(output.chapter_starts.iter().map(|s| s + offset));
+        offset += output.duration_ms;
+    }
+    if starts.len() != expected.chapters.len() {
+        report.push(
+            "Chapters",
+            false,
+            format!("{} chapter(s), expected {}", starts.len(), expected.chapters.len()),
+        );
+    } else {
+        let moved = expected.chapters.iter().zip(&starts).enumerate()
+            .find(|(_, (chapter, start))| chapter.start_time.abs_diff(**start) > CHAPTER_TOLERANCE_MS);
+        match moved {
+            Some((i, (chapter, start))) => report.push(
+                "Chapters",
+                false,
+                format!(
+                    "Chapter {} starts at {}, expected {}",
+                    i + 1,
+                    format_ms(*start),
+                    format_ms(chapter.start_time)
+                ),
+            ),
+            None => report.push("Chapters", true, format!("{} chapter(s), start times match", starts.len())),
+        }
+    }
+
+    // Tags and cover (every part carries them)
+    let tag_checks: [(&'static str, &str, &[&str]); 2] = [
+        ("Title", expected.title.as_str(), &["title"]),
+        ("Author", expected.author.as_str(), &["artist", "album_artist"]),
+    ];
+    for (name, value, keys) in tag_checks {
+        if value.is_empty() {
+            continue;
+        }
+        let missing = probed.iter().zip(output_paths)
+            .find(|(output, _)| !keys.iter().any(|k| output.tag(k).is_some_and(|v| !v.trim().is_empty())));
+        match missing {
+            Some((_, path)) => report.push(name, false, format!("No {} tag in {}", name.to_lowercase(), file_name(path))),
+            None => report.push(name, true, value.to_string()),
+        }
+    }
+    if expected.cover {
+        match probed.iter().zip(output_paths).find(|(output, _)| !output.has_picture) {
+            Some((_, path)) => report.push("Cover", false, format!("No embedded cover in {}", file_name(path))),
+            None => report.push("Cover", true, "Embedded".to_string()),
+        }
+    }
+
+    // Full decode
+    let mut decode_errors = Vec::new();
+    for path in output_paths {
+        match crate::services::input_check::decode_file(path).await {
+            Ok((errors, _)) if errors.is_empty() => {}
+            Ok((errors, _)) => decode_errors.push(format!("{}: {}", file_name(path), errors[0])),
+            Err(e) => decode_errors.push(format!("{}: {}", file_name(path), e)),
+        }
+    }
+    match decode_errors.first() {
+        Some(first) => report.push("Decodes", false, format!("{} file(s) with errors; {}", decode_errors.len(), first)),
+        None => report.push("Decodes", true, "No decode errors".to_string()),
+    }
+    report
+}
+
+/// Format, stream and chapter info of an output file in one ffprobe call.
+async fn probe_output(path: &str) -> Result<ProbedOutput> {
+    let output = TokioCommand::new("ffprobe")
+        .args(["-v", "quiet", "-print_format", "json", "-show_format", "-show_streams", "-show_chapters", path])
+        .output()
+        .await
+        .map_err(|e| LecternError::spawn("ffprobe", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(LecternError::Ffprobe { path: path.to_string(), message: stderr.trim().to_string() });
+    }
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| LecternError::Ffprobe { path: path.to_string(), message: format!("invalid JSON output: {}", e) })?;
+
+    let seconds_ms = |value: Option<&serde_json::Value>| -> Option<u64> {
+        let seconds: f64 = value?.as_str()?.parse().ok()?;
+        Some((seconds * 1000.0).round() as u64)
+    };
+    let duration_ms = seconds_ms(json.pointer("/format/duration"))
+        .ok_or_else(|| LecternError::Ffprobe { path: path.to_string(), message: "no duration field in output".to_string() })?;
+    let chapter_starts = json.get("chapters")
+        .and_then(|c| c.as_array())
+        .map(|chapters| chapters.iter().filter_map(|c| seconds_ms(c.get("start_time"))).collect())
+        .unwrap_or_default();
+    let tags: Vec<(String, String)> = json.pointer("/format/tags")
+        .and_then(|t| t.as_object())
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    // MP4/MP3/FLAC carry the cover as an attached picture stream; Ogg as a Vorbis comment
+    // (which ffprobe also reports as an attached picture)
+    let has_picture = json.get("streams")
+        .and_then(|s| s.as_array())
+        .is_some_and(|streams| {
+            streams.iter().any(|s| s.pointer("/disposition/attached_pic").and_then(|v| v.as_i64()) == Some(1))
+        })
+        || tags.iter().any(|(k, _)| k.eq_ignore_ascii_case("METADATA_BLOCK_PICTURE"));
+    Ok(ProbedOutput { duration_ms, chapter_starts, tags, has_picture })
+}
+
+fn format_ms(ms: u64) -> String {
+    crate::utils::time::format_time(ms, false)
+}
+
+fn file_name(path: &str) -> &str {
+    std::path::Path::new(path).file_name().and_then(|n| n.to_str()).unwrap_or(path)
+}
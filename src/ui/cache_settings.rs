@@ -0,0 +1,27 @@
+//! Persist HTTP cache settings (enabled, offline mode, TTL, size limit). Load on startup, save on change.
+//! Uses XDG config on Linux: ~/.config/lectern/cache_settings.json
+use crate::config;
+use crate::services::http_cache::CachePolicy;
+use std::fs;
+
+fn config_path() -> Option<std::path::PathBuf> {
+    config::config_file("cache_settings.json")
+}
+
+/// Load cache policy from config file. Returns None on missing/error.
+pub fn load() -> Option<CachePolicy> {
+    let path = config_path()?;
+    let data = fs::read_to_string(&path).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+/// Save cache policy to config file.
+pub fn save(policy: &CachePolicy) {
+    let path = match config_path() {
+        Some(p) => p,
+        None => return,
+    };
+    if let Ok(json) = serde_json::to_string_pretty(policy) {
+        let _ = fs::write(&path, json);
+    }
+}
@@ -1,34 +1,141 @@
+use crate::services::{http_get, http_get_json};
+use crate::services::cover_edit::load_cover_bytes;
+use std::path::Path;
+
+const COVER_SEARCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Perceptual hashes at most this many bits apart are treated as the same artwork.
+const DUPLICATE_HASH_DISTANCE: u32 = 10;
+/// Images in a folder or on a page whose shorter side is below this are icons, logos or thumbnails.
+const MIN_COVER_SIDE: u32 = 200;
+/// Upper bound on candidates checked per folder or page.
+const MAX_SCANNED_IMAGES: usize = 40;
+/// How deep `search_cover_folder` descends below the chosen folder.
+const MAX_FOLDER_DEPTH: usize = 4;
+const IMAGE_EXTENSIONS: [&str; 6] = ["jpg", "jpeg", "png", "webp", "gif", "bmp"];
+
 #[derive(Debug, Clone)]
 pub struct CoverResult {
     pub url: String,
-    pub width: u32,
+    pub width: u32, // Measured after download; 0 when the image could not be read
     pub height: u32,
     pub source: String, // Provider name
+    /// Other providers that returned the same artwork (smaller or equal in size).
+    pub also_from: Vec<String>,
+}
+
+impl CoverResult {
+    fn new(url: impl Into<String>, source: &str) -> Self {
+        Self { url: url.into(), width: 0, height: 0, source: source.to_string(), also_from: Vec::new() }
+    }
+
+    /// Ranking: the shorter side, penalised by how far the image is from square (audiobook covers
+    /// are square, so a tall book jacket ranks below a square cover of similar size).
+    fn score(&self) -> f64 {
+        let (short, long) = (self.width.min(self.height) as f64, self.width.max(self.height) as f64);
+        if long == 0.0 {
+            return 0.0;
+        }
+        let squareness = short / long;
+        short * squareness * squareness
+    }
+}
+
+/// 64-bit difference hash: each bit says whether a pixel of the 9x8 grayscale thumbnail is
+/// brighter than its right neighbour. Survives rescaling and recompression.
+fn difference_hash(image: &::image::DynamicImage) -> u64 {
+    let small = image.resize_exact(9, 8, ::image::imageops::FilterType::Triangle).to_luma8();
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+/// Amazon image URLs carry a size modifier (`._SL500_`); without it the original is served.
+fn amazon_full_size(url: &str) -> String {
+    if !url.contains("media-amazon.com") && !url.contains("images-amazon.com") {
+        return url.to_string();
+    }
+    match (url.rfind("._"), url.rfind('.')) {
+        (Some(start), Some(ext)) if start < ext && url[start..ext].ends_with('_') => {
+            format!("{}{}", &url[..start], &url[ext..])
+        }
+        _ => url.to_string(),
+    }
+}
+
+/// Download (or read) every result, record its real size, merge results showing the same artwork
+/// (keeping the best copy) and sort best first.
+async fn measure_and_rank(results: Vec<CoverResult>) -> Vec<CoverResult> {
+    use futures::future::join_all;
+
+    let downloads = join_all(results.iter().map(|r| load_cover_bytes(&r.url))).await;
+    let unmeasured = results.clone();
+    let measured = tokio::task::spawn_blocking(move || {
+        results
+            .into_iter()
+            .zip(downloads)
+            .map(|(mut result, data)| {
+                let image = data.ok().and_then(|bytes| ::image::load_from_memory(&bytes).ok());
+                // Open Library answers unknown ids with a 1x1 placeholder
+                let hash = image.as_ref().filter(|img| img.width() > 1 && img.height() > 1).map(|img| {
+                    result.width = img.width();
+                    result.height = img.height();
+                    difference_hash(img)
+                });
+                if hash.is_none() {
+                    println!("[DEBUG] Could not read cover {}", result.url);
+                }
+                (result, hash)
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_else(|e| {
+        println!("[ERROR] Measuring cover results failed: {}", e);
+        unmeasured.into_iter().map(|result| (result, None)).collect()
+    });
+    rank_unique(measured)
+}
+
+/// Sort best first and merge results showing the same artwork (same URL or perceptual hash)
+/// into the best copy, noting the other providers in `also_from`.
+fn rank_unique(mut measured: Vec<(CoverResult, Option<u64>)>) -> Vec<CoverResult> {
+    measured.sort_by(|a, b| b.0.score().total_cmp(&a.0.score()));
+    let mut unique: Vec<(CoverResult, Option<u64>)> = Vec::new();
+    for (result, hash) in measured {
+        let duplicate = unique.iter_mut().find(|(kept, kept_hash)| {
+            kept.url == result.url
+                || matches!((hash, *kept_hash), (Some(a), Some(b)) if (a ^ b).count_ones() <= DUPLICATE_HASH_DISTANCE)
+        });
+        match duplicate {
+            Some((kept, _)) => {
+                if result.source != kept.source && !kept.also_from.contains(&result.source) {
+                    kept.also_from.push(result.source);
+                }
+            }
+            None => unique.push((result, hash)),
+        }
+    }
+    unique.into_iter().map(|(result, _)| result).collect()
 }
 
 // Download image from URL
 pub async fn download_image(url: &str) -> Result<(String, Vec<u8>), String> {
-    let client = reqwest::Client::builder()
-        .connect_timeout(std::time::Duration::from_secs(3))
-        .build()
-        .unwrap_or_else(|_| reqwest::Client::new());
     println!("[DEBUG] Downloading image from: {}", url);
     
-    let response = client.get(url)
-        .timeout(std::time::Duration::from_secs(12))
-        .send()
+    let bytes = http_get(url, std::time::Duration::from_secs(12))
         .await
         .map_err(|e| format!("Failed to download image: {}", e))?;
     
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
-    }
-    
-    let bytes = response.bytes().await
-        .map_err(|e| format!("Failed to read image data: {}", e))?;
-    
     println!("[DEBUG] Successfully downloaded {} bytes from {}", bytes.len(), url);
-    Ok((url.to_string(), bytes.to_vec()))
+    Ok((url.to_string(), bytes))
 }
 
 // Download multiple images in parallel (non-blocking, runs in background thread)
@@ -39,36 +146,14 @@ pub fn download_images_parallel_threaded(urls: Vec<String>) -> std::thread::Join
         // Create a new Tokio runtime in this background thread
         let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
         
-        // Create a single HTTP client with connection pooling
-        let client = reqwest::Client::builder()
-            .connect_timeout(std::time::Duration::from_secs(2))
-            .timeout(std::time::Duration::from_secs(5))
-            .build()
-            .unwrap_or_else(|_| reqwest::Client::new());
-        
         let mut tasks = Vec::new();
         
         for url in urls {
-            let client_clone = client.clone();
             let task = async move {
-                let url_clone = url.clone();
-                let result = async {
-                    let response = client_clone.get(&url)
-                        .send()
-                        .await
-                        .map_err(|e| format!("Failed to download image: {}", e))?;
-                    
-                    if !response.status().is_success() {
-                        return Err(format!("HTTP error: {}", response.status()));
-                    }
-                    
-                    let bytes = response.bytes().await
-                        .map_err(|e| format!("Failed to read image data: {}", e))?;
-                    
-                    Ok(bytes.to_vec())
-                }.await;
-                
-                (url_clone, result)
+                let result = http_get(&url, std::time::Duration::from_secs(5))
+                    .await
+                    .map_err(|e| format!("Failed to download image: {}", e));
+                (url, result)
             };
             tasks.push(task);
         }
@@ -101,6 +186,14 @@ pub async fn search_cover_art(
         }
     }
     
+    // iTunes serves its audiobook artwork at any requested size up to the original
+    if !title.is_empty() {
+        let query = format!("{} {}", title, author);
+        if let Ok(covers) = search_itunes_covers(&query).await {
+            results.extend(covers);
+        }
+    }
+    
     // Try using ASIN/ISBN for more specific results
     if let Some(asin_val) = asin {
         if let Ok(covers) = search_audnexus_cover(asin_val).await {
@@ -108,6 +201,7 @@ pub async fn search_cover_art(
         }
     }
     
+    let results = measure_and_rank(results).await;
     if results.is_empty() {
         Err("No cover art found".to_string())
     } else {
@@ -115,34 +209,171 @@ pub async fn search_cover_art(
     }
 }
 
+/// Every image in `dir` and its subfolders, measured and ranked; unreadable and tiny images are dropped.
+pub async fn search_cover_folder(dir: &str) -> Result<Vec<CoverResult>, String> {
+    let root = Path::new(dir);
+    if !root.is_dir() {
+        return Err(format!("Not a folder: {}", dir));
+    }
+    let mut images = Vec::new();
+    collect_images(root, root, 0, &mut images);
+    println!("[DEBUG] Found {} image files under {}", images.len(), dir);
+    if images.is_empty() {
+        return Err("No images found in this folder".to_string());
+    }
+    let results = keep_cover_sized(measure_and_rank(images).await);
+    if results.is_empty() {
+        Err("No usable cover images found in this folder".to_string())
+    } else {
+        Ok(results)
+    }
+}
+
+fn collect_images(root: &Path, dir: &Path, depth: usize, images: &mut Vec<CoverResult>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        if images.len() >= MAX_SCANNED_IMAGES {
+            return;
+        }
+        if path.is_dir() {
+            if depth < MAX_FOLDER_DEPTH {
+                collect_images(root, &path, depth + 1, images);
+            }
+            continue;
+        }
+        let is_image = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+            .unwrap_or(false);
+        if is_image {
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().to_string();
+            images.push(CoverResult::new(path.to_string_lossy(), &format!("Folder: {}", relative)));
+        }
+    }
+}
+
+/// The page's og:image / twitter:image / image_src and its `<img>` images, measured and ranked.
+pub async fn search_cover_page(page_url: &str) -> Result<Vec<CoverResult>, String> {
+    let base = url::Url::parse(page_url.trim()).map_err(|e| format!("Invalid page URL: {}", e))?;
+    let html = http_get(base.as_str(), COVER_SEARCH_TIMEOUT)
+        .await
+        .map_err(|e| format!("Failed to load page: {}", e))?;
+    let candidates = page_image_candidates(&String::from_utf8_lossy(&html), &base);
+    println!("[DEBUG] Found {} image candidates on {}", candidates.len(), base);
+    if candidates.is_empty() {
+        return Err("No images found on this page".to_string());
+    }
+    let results = keep_cover_sized(measure_and_rank(candidates).await);
+    if results.is_empty() {
+        Err("No cover-sized images found on this page".to_string())
+    } else {
+        Ok(results)
+    }
+}
+
+fn keep_cover_sized(results: Vec<CoverResult>) -> Vec<CoverResult> {
+    results.into_iter().filter(|r| r.width.min(r.height) >= MIN_COVER_SIDE).collect()
+}
+
+/// URL of the widest (`800w`) or densest (`2x`) `srcset` entry; entries without a descriptor count as 1x.
+fn largest_srcset_entry(srcset: &str) -> Option<&str> {
+    srcset
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split_whitespace();
+            let url = parts.next()?;
+            let size = parts
+                .next()
+                .and_then(|d| d.trim_end_matches(['w', 'x', 'W', 'X']).parse::<f64>().ok())
+                .unwrap_or(1.0);
+            Some((url, size))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(url, _)| url)
+}
+
+/// Image URLs referenced by `html`: declared share images first, then `<img>` tags (largest
+/// `srcset` entry when present), resolved against `base`.
+fn page_image_candidates(html: &str, base: &url::Url) -> Vec<CoverResult> {
+    static TAG: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    static ATTRIBUTE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let tag_re = TAG.get_or_init(|| regex::Regex::new(r"(?is)<(meta|link|img)\b[^>]*>").expect("valid regex"));
+    let attr_re = ATTRIBUTE
+        .get_or_init(|| regex::Regex::new(r#"(?is)([a-z:_-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("valid regex"));
+
+    let mut candidates: Vec<CoverResult> = Vec::new();
+    let push = |src: &str, source: &str, candidates: &mut Vec<CoverResult>| {
+        let src = src.trim().replace("&amp;", "&");
+        if src.is_empty() || src.starts_with("data:") || src.to_lowercase().ends_with(".svg") {
+            return;
+        }
+        if let Ok(resolved) = base.join(&src) {
+            let resolved = resolved.to_string();
+            if candidates.len() < MAX_SCANNED_IMAGES && !candidates.iter().any(|c| c.url == resolved) {
+                candidates.push(CoverResult::new(resolved, source));
+            }
+        }
+    };
+
+    let mut images = Vec::new();
+    for tag in tag_re.captures_iter(html) {
+        let attrs: Vec<(String, String)> = attr_re
+            .captures_iter(&tag[0])
+            .map(|a| {
+                let value = a.get(2).or_else(|| a.get(3)).map(|v| v.as_str()).unwrap_or("");
+                (a[1].to_lowercase(), value.to_string())
+            })
+            .collect();
+        let attr = |name: &str| attrs.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str());
+        match tag[1].to_lowercase().as_str() {
+            "meta" => {
+                let key = attr("property").or_else(|| attr("name")).unwrap_or("").to_lowercase();
+                if matches!(key.as_str(), "og:image" | "og:image:url" | "og:image:secure_url" | "twitter:image") {
+                    if let Some(content) = attr("content") {
+                        push(content, "Page (share image)", &mut candidates);
+                    }
+                }
+            }
+            "link" => {
+                if attr("rel").map(|r| r.eq_ignore_ascii_case("image_src")).unwrap_or(false) {
+                    if let Some(href) = attr("href") {
+                        push(href, "Page (share image)", &mut candidates);
+                    }
+                }
+            }
+            _ => {
+                let largest_srcset = attr("srcset").or_else(|| attr("data-srcset")).and_then(largest_srcset_entry);
+                if let Some(src) = largest_srcset.or_else(|| attr("data-src")).or_else(|| attr("src")) {
+                    images.push(src.to_string());
+                }
+            }
+        }
+    }
+    for src in images {
+        push(&src, "Page image", &mut candidates);
+    }
+    candidates
+}
+
 async fn search_open_library_covers(query: &str) -> Result<Vec<CoverResult>, String> {
-    let client = reqwest::Client::new();
     let url = format!("https://openlibrary.org/search.json?q={}&limit=5", 
                      urlencoding::encode(query));
     
-    let response = client.get(&url)
-        .send()
+    let json = http_get_json(&url, COVER_SEARCH_TIMEOUT)
         .await
-        .map_err(|e| format!("Open Library request failed: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(format!("Open Library returned status: {}", response.status()));
-    }
-    
-    let json: serde_json::Value = response.json().await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+        .map_err(|e| e.to_string())?;
     
     let mut covers = Vec::new();
     if let Some(docs) = json.get("docs").and_then(|d| d.as_array()) {
         for doc in docs.iter().take(5) {
             if let Some(cover_id) = doc.get("cover_i").and_then(|c| c.as_i64()) {
                 let cover_url = format!("https://covers.openlibrary.org/b/id/{}-L.jpg", cover_id);
-                covers.push(CoverResult {
-                    url: cover_url,
-                    width: 500,
-                    height: 500,
-                    source: "Open Library".to_string(),
-                });
+                covers.push(CoverResult::new(cover_url, "Open Library"));
             }
         }
     }
@@ -151,21 +382,12 @@ async fn search_open_library_covers(query: &str) -> Result<Vec<CoverResult>, Str
 }
 
 async fn search_google_books_covers(query: &str) -> Result<Vec<CoverResult>, String> {
-    let client = reqwest::Client::new();
     let url = format!("https://www.googleapis.com/books/v1/volumes?q={}&maxResults=5", 
                      urlencoding::encode(query));
     
-    let response = client.get(&url)
-        .send()
+    let json = http_get_json(&url, COVER_SEARCH_TIMEOUT)
         .await
-        .map_err(|e| format!("Google Books request failed: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(format!("Google Books returned status: {}", response.status()));
-    }
-    
-    let json: serde_json::Value = response.json().await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+        .map_err(|e| e.to_string())?;
     
     let mut covers = Vec::new();
     if let Some(items) = json.get("items").and_then(|i| i.as_array()) {
@@ -175,12 +397,7 @@ async fn search_google_books_covers(query: &str) -> Result<Vec<CoverResult>, Str
                     if let Some(thumbnail) = image_links.get("thumbnail").and_then(|t| t.as_str()) {
                         // Replace thumbnail size with large size
                         let large_url = thumbnail.replace("zoom=1", "zoom=5").replace("&edge=curl", "");
-                        covers.push(CoverResult {
-                            url: large_url.to_string(),
-                            width: 1280,
-                            height: 1280,
-                            source: "Google Books".to_string(),
-                        });
+                        covers.push(CoverResult::new(large_url, "Google Books"));
                     }
                 }
             }
@@ -191,29 +408,156 @@ async fn search_google_books_covers(query: &str) -> Result<Vec<CoverResult>, Str
 }
 
 async fn search_audnexus_cover(asin: &str) -> Result<Vec<CoverResult>, String> {
-    let client = reqwest::Client::new();
     let url = format!("https://api.audnex.us/books/{}", asin);
     
-    let response = client.get(&url)
-        .send()
+    let json = http_get_json(&url, COVER_SEARCH_TIMEOUT)
         .await
-        .map_err(|e| format!("Audnexus request failed: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(format!("Audnexus returned status: {}", response.status()));
-    }
-    
-    let json: serde_json::Value = response.json().await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+        .map_err(|e| e.to_string())?;
     
     if let Some(image_url) = json.get("image").and_then(|i| i.as_str()) {
-        Ok(vec![CoverResult {
-            url: image_url.to_string(),
-            width: 500,
-            height: 500,
-            source: "Audnexus".to_string(),
-        }])
+        Ok(vec![CoverResult::new(amazon_full_size(image_url), "Audnexus")])
     } else {
         Ok(Vec::new())
     }
 }
+
+async fn search_itunes_covers(query: &str) -> Result<Vec<CoverResult>, String> {
+    let url = format!("https://itunes.apple.com/search?term={}&media=audiobook&limit=5",
+                     urlencoding::encode(query));
+    
+    let json = http_get_json(&url, COVER_SEARCH_TIMEOUT)
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    let mut covers = Vec::new();
+    if let Some(items) = json.get("results").and_then(|r| r.as_array()) {
+        for item in items.iter().take(5) {
+            if let Some(artwork) = item.get("artworkUrl100").and_then(|a| a.as_str()) {
+                // ".../100x100bb.jpg": the size in the file name is what gets rendered
+                let large_url = artwork.replace("100x100bb", "3000x3000bb");
+                covers.push(CoverResult::new(large_url, "iTunes"));
+            }
+        }
+    }
+    
+    Ok(covers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::image::{DynamicImage, Rgb, RgbImage};
+
+    fn sized(url: &str, source: &str, width: u32, height: u32) -> CoverResult {
+        CoverResult { width, height, ..CoverResult::new(url, source) }
+    }
+
+    /// A 9x8 grid of gray cells whose pattern depends on `seed`; every size shows the same picture.
+    fn artwork(side: u32, seed: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(side, side, |x, y| {
+            let (cell_x, cell_y) = (x * 9 / side, y * 8 / side);
+            let value = ((cell_x * 7 + cell_y * 13 + seed * 29) % 17 * 15) as u8;
+            Rgb([value, value, value])
+        }))
+    }
+
+    #[test]
+    fn score_prefers_large_square_covers() {
+        let square = sized("a", "A", 1000, 1000);
+        let jacket = sized("b", "B", 1000, 1500);
+        let small_square = sized("c", "C", 500, 500);
+        assert!(square.score() > jacket.score());
+        assert!(square.score() > small_square.score());
+        assert_eq!(square.score(), 1000.0);
+        assert_eq!(sized("d", "D", 0, 0).score(), 0.0);
+    }
+
+    #[test]
+    fn difference_hash_survives_rescaling() {
+        let large = difference_hash(&artwork(900, 1));
+        let small = difference_hash(&artwork(180, 1));
+        let other = difference_hash(&artwork(900, 2));
+        assert!((large ^ small).count_ones() <= DUPLICATE_HASH_DISTANCE);
+        assert!((large ^ other).count_ones() > DUPLICATE_HASH_DISTANCE);
+    }
+
+    #[test]
+    fn amazon_size_suffix_is_removed() {
+        assert_eq!(
+            amazon_full_size("https://m.media-amazon.com/images/I/51abcDEF._SL500_.jpg"),
+            "https://m.media-amazon.com/images/I/51abcDEF.jpg"
+        );
+        assert_eq!(
+            amazon_full_size("https://images-na.ssl-images-amazon.com/images/I/51abc._SX342_SY445_QL70_.jpg"),
+            "https://images-na.ssl-images-amazon.com/images/I/51abc.jpg"
+        );
+        let plain = "https://m.media-amazon.com/images/I/51abcDEF.jpg";
+        assert_eq!(amazon_full_size(plain), plain);
+        let elsewhere = "https://example.com/cover._SL500_.jpg";
+        assert_eq!(amazon_full_size(elsewhere), elsewhere);
+    }
+
+    fn candidate_urls(html: &str) -> Vec<(String, String)> {
+        let base = url::Url::parse("https://example.com/books/the-book/").expect("valid url");
+        page_image_candidates(html, &base).into_iter().map(|c| (c.url, c.source)).collect()
+    }
+
+    #[test]
+    fn share_images_come_first() {
+        let html = r#"<html><head>
+            <meta property="og:image" content="https://cdn.example.com/og.jpg">
+            <meta name='twitter:image' content='/twitter.jpg?w=1&amp;h=1'>
+            <link rel="image_src" href="cover-large.png">
+            <meta property="og:title" content="not an image">
+        </head><body><img src="inline.jpg"></body></html>"#;
+        let share = "Page (share image)".to_string();
+        assert_eq!(
+            candidate_urls(html),
+            vec![
+                ("https://cdn.example.com/og.jpg".to_string(), share.clone()),
+                ("https://example.com/twitter.jpg?w=1&h=1".to_string(), share.clone()),
+                ("https://example.com/books/the-book/cover-large.png".to_string(), share),
+                ("https://example.com/books/the-book/inline.jpg".to_string(), "Page image".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn largest_srcset_entry_wins() {
+        let html = r#"<img src="small.jpg" srcset="big.jpg 1200w, small.jpg 300w, medium.jpg 600w">
+            <img data-src="lazy.jpg" data-srcset="lazy-1x.jpg, lazy-3x.jpg 3x, lazy-2x.jpg 2x">"#;
+        let urls: Vec<String> = candidate_urls(html).into_iter().map(|(url, _)| url).collect();
+        assert_eq!(
+            urls,
+            vec!["https://example.com/books/the-book/big.jpg", "https://example.com/books/the-book/lazy-3x.jpg"]
+        );
+        assert_eq!(largest_srcset_entry("only.jpg"), Some("only.jpg"));
+        assert_eq!(largest_srcset_entry(" "), None);
+    }
+
+    #[test]
+    fn relative_urls_resolve_and_inline_or_vector_images_are_skipped() {
+        let html = r#"<img src="../../img/cover.webp"><img src="//static.example.org/c.jpg">
+            <img src="data:image/png;base64,AAAA"><img src="/logo.SVG"><img src="../../img/cover.webp">"#;
+        let urls: Vec<String> = candidate_urls(html).into_iter().map(|(url, _)| url).collect();
+        assert_eq!(urls, vec!["https://example.com/img/cover.webp", "https://static.example.org/c.jpg"]);
+    }
+
+    #[test]
+    fn duplicates_merge_into_the_best_copy() {
+        let hash = difference_hash(&artwork(900, 1));
+        let near = hash ^ 0b111; // recompressed copy
+        let distinct = difference_hash(&artwork(900, 2));
+        let ranked = rank_unique(vec![
+            (sized("small", "Open Library", 300, 300), Some(near)),
+            (sized("big", "Audible", 2400, 2400), Some(hash)),
+            (sized("other", "Google Books", 800, 1200), Some(distinct)),
+            (sized("big", "iTunes", 2400, 2400), None),
+            (sized("unread", "Amazon", 0, 0), None),
+        ]);
+        let urls: Vec<&str> = ranked.iter().map(|r| r.url.as_str()).collect();
+        assert_eq!(urls, vec!["big", "other", "unread"]);
+        assert_eq!(ranked[0].also_from, vec!["iTunes", "Open Library"]);
+        assert!(ranked[1].also_from.is_empty());
+    }
+}
@@ -157,23 +157,28 @@ pub fn handle_chapters(app: &mut Lectern, message: Message) -> Option<Command<Me
             app.chapters.is_looking_up_chapters = true;
             app.chapters.lookup_error = None;
 
-            // Use manual input, then metadata ASIN, then selected book ASIN, then metadata ISBN, then selected book ISBN
-            let identifier = if !app.chapters.asin_input.trim().is_empty() {
+            // Use manual input, then metadata ASIN, then selected book ASIN
+            let asin = if !app.chapters.asin_input.trim().is_empty() {
                 Some(app.chapters.asin_input.trim().to_string())
             } else if !app.metadata.editing_asin.trim().is_empty() {
                 Some(app.metadata.editing_asin.trim().to_string())
             } else {
                 app.metadata.selected_book.as_ref().and_then(|b| b.asin.clone())
-                    .or_else(|| {
-                        if !app.metadata.editing_isbn.trim().is_empty() {
-                            Some(app.metadata.editing_isbn.trim().to_string())
-                        } else {
-                            app.metadata.selected_book.as_ref().and_then(|b| b.isbn.clone())
-                        }
-                    })
             };
+            // Audnexus keys on ASINs: without one, find the book's editions by title and let the
+            // user pick one; an ISBN is only tried when there is no title either
+            if asin.is_none() && !app.metadata.editing_title.trim().is_empty() {
+                return handle_chapters(app, Message::ChapterFindEditions);
+            }
+            let identifier = asin.or_else(|| {
+                if !app.metadata.editing_isbn.trim().is_empty() {
+                    Some(app.metadata.editing_isbn.trim().to_string())
+                } else {
+                    app.metadata.selected_book.as_ref().and_then(|b| b.isbn.clone())
+                }
+            });
 
-            let region = app.chapters.selected_region.to_string().to_lowercase();
+            let region = app.chapters.selected_region.code();
 
             if let Some(asin_val) = identifier {
                 println!("[DEBUG] Looking up chapters for ASIN/ISBN: {} (Region: {})", asin_val, region);
@@ -181,15 +186,60 @@ pub fn handle_chapters(app: &mut Lectern, message: Message) -> Option<Command<Me
                 return Some(Command::perform(
                     async move {
                         AudioService::fetch_chapters_by_asin(&asin_val, &region).await
+                            .map_err(|e| e.to_string())
                     },
                     move |result| Message::ChapterLookupCompleted(gen, result),
                 ));
             } else {
                 app.chapters.is_looking_up_chapters = false;
-                app.chapters.lookup_error = Some("No ASIN or ISBN available. Enter one in the lookup field above or fill in Metadata (ISBN/ASIN) first.".to_string());
+                app.chapters.lookup_error = Some("No ASIN, ISBN or title available. Enter an ASIN in the lookup field above or fill in Metadata first.".to_string());
             }
             Some(Command::none())
         }
+        Message::ChapterFindEditions => {
+            let title = app.metadata.editing_title.trim().to_string();
+            if title.is_empty() {
+                app.chapters.lookup_error = Some("Enter a title in Metadata to search for editions.".to_string());
+                return Some(Command::none());
+            }
+            let author = app.metadata.editing_authors.first().cloned().unwrap_or_default();
+            let region = app.chapters.selected_region.code();
+            let runtime_ms = app.chapters.book_duration_ms;
+            app.chapters.is_looking_up_chapters = true;
+            app.chapters.lookup_error = None;
+            app.chapters.edition_candidates.clear();
+            println!("[DEBUG] Finding editions for '{}' by '{}' (Region: {})", title, author, region);
+            let gen = app.chapters.load_generation;
+            Some(Command::perform(
+                async move {
+                    AudioService::find_chapter_editions(&title, &author, &region, runtime_ms).await
+                        .map_err(|e| e.to_string())
+                },
+                move |result| Message::ChapterEditionsFound(gen, result),
+            ))
+        }
+        Message::ChapterEditionsFound(gen, result) => {
+            if gen != app.chapters.load_generation {
+                return Some(Command::none());
+            }
+            app.chapters.is_looking_up_chapters = false;
+            match result {
+                Ok(editions) => {
+                    app.chapters.edition_candidates = editions;
+                    app.chapters.show_asin_input = true;
+                }
+                Err(e) => app.chapters.lookup_error = Some(e),
+            }
+            Some(Command::none())
+        }
+        Message::ChapterEditionChosen(index) => {
+            let Some(edition) = app.chapters.edition_candidates.get(index) else {
+                return Some(Command::none());
+            };
+            app.chapters.asin_input = edition.asin.clone();
+            app.chapters.edition_candidates.clear();
+            handle_chapters(app, Message::ChapterLookup)
+        }
         Message::ChapterToggleAsinInput => {
             app.chapters.show_asin_input = !app.chapters.show_asin_input;
             // When opening the lookup panel, pre-populate from metadata: ASIN first, then ISBN
@@ -212,6 +262,74 @@ pub fn handle_chapters(app: &mut Lectern, message: Message) -> Option<Command<Me
             app.chapters.selected_region = region;
             Some(Command::none())
         }
+        Message::ChapterTitleRulesToggled => {
+            app.chapters.show_title_rules = !app.chapters.show_title_rules;
+            app.chapters.title_rule_error = None;
+            Some(Command::none())
+        }
+        Message::TitleRuleStripPrefixToggled(strip) => {
+            app.chapters.title_rules.strip_prefix = strip;
+            Some(Command::none())
+        }
+        Message::TitleRuleFindChanged(find) => {
+            app.chapters.title_rules.find = find;
+            app.chapters.title_rule_error = None;
+            Some(Command::none())
+        }
+        Message::TitleRuleReplaceChanged(replace) => {
+            app.chapters.title_rules.replace = replace;
+            Some(Command::none())
+        }
+        Message::TitleRuleNumberingChanged(numbering) => {
+            app.chapters.title_rules.numbering = numbering;
+            Some(Command::none())
+        }
+        Message::TitleRuleTemplateChanged(template) => {
+            app.chapters.title_rules.template = template;
+            Some(Command::none())
+        }
+        Message::TitleRuleNumberWordsChanged(words) => {
+            app.chapters.title_rules.number_words = words;
+            Some(Command::none())
+        }
+        Message::TitleRuleCaseChanged(case) => {
+            app.chapters.title_rules.case = case;
+            Some(Command::none())
+        }
+        Message::TitleRuleScopeChanged(scope) => {
+            app.chapters.title_rule_scope = scope;
+            Some(Command::none())
+        }
+        Message::TitleRuleFromChanged(from) => {
+            app.chapters.title_rule_from = from;
+            Some(Command::none())
+        }
+        Message::TitleRuleToChanged(to) => {
+            app.chapters.title_rule_to = to;
+            Some(Command::none())
+        }
+        Message::ApplyTitleRules => {
+            let transformer = match app.chapters.title_rules.compile() {
+                Ok(transformer) => transformer,
+                Err(e) => {
+                    app.chapters.title_rule_error = Some(e);
+                    return Some(Command::none());
+                }
+            };
+            app.chapters.title_rule_error = None;
+            let targets = app.chapters.title_rule_targets();
+            let mut changed = 0;
+            for index in targets {
+                let chapter = &mut app.chapters.chapters[index];
+                let title = transformer.apply(&chapter.title, index + 1);
+                if title != chapter.title {
+                    chapter.title = title;
+                    changed += 1;
+                }
+            }
+            println!("[DEBUG] Title rules renamed {} chapters", changed);
+            Some(Command::none())
+        }
         Message::ChapterListViewportChanged { offset_y, viewport_height, content_height } => {
             app.chapters.chapter_list_viewport = Some((offset_y, viewport_height, content_height));
             Some(Command::none())
@@ -646,13 +764,16 @@ pub fn handle_chapters(app: &mut Lectern, message: Message) -> Option<Command<Me
             println!("[DEBUG] Chapter playback process exited");
             Some(Command::none())
         }
-        Message::ChapterLookupCompleted(gen, Ok(chapters)) => {
+        Message::ChapterLookupCompleted(gen, Ok(lookup)) => {
             app.chapters.is_looking_up_chapters = false;
             if gen != app.chapters.load_generation {
                 return Some(Command::none());
             }
             app.chapters.show_asin_input = false; // Hide lookup section after successful search
+            let chapters = lookup.chapters;
             let count = chapters.len();
+            app.chapters.lookup_brand_ms = Some((lookup.brand_intro_ms, lookup.brand_outro_ms))
+                .filter(|(intro, outro)| *intro > 0 || *outro > 0);
             if !chapters.is_empty() {
                 // Store as pending; user chooses Apply (replace) or Map titles only
                 app.chapters.lookup_result = Some(chapters.clone());
@@ -665,6 +786,7 @@ pub fn handle_chapters(app: &mut Lectern, message: Message) -> Option<Command<Me
         Message::ChapterLookupApply => {
             if let Some(chapters) = app.chapters.lookup_result.take() {
                 app.chapters.chapters = chapters;
+                app.chapters.brand_ms = app.chapters.lookup_brand_ms.take();
                 app.chapters.chapter_time_editing.clear();
                 println!("[DEBUG] Applied looked-up chapters (replaced)");
             }
@@ -672,6 +794,7 @@ pub fn handle_chapters(app: &mut Lectern, message: Message) -> Option<Command<Me
         }
         Message::MapChapterTitlesOnly => {
             if let Some(lookup) = app.chapters.lookup_result.take() {
+                app.chapters.brand_ms = app.chapters.lookup_brand_ms.take();
                 let n = lookup.len().min(app.chapters.chapters.len());
                 for i in 0..n {
                     if let Some(ch) = app.chapters.chapters.get_mut(i) {
@@ -683,8 +806,10 @@ pub fn handle_chapters(app: &mut Lectern, message: Message) -> Option<Command<Me
             Some(Command::none())
         }
         Message::ChapterLookupCancel => {
+            app.chapters.edition_candidates.clear();
             app.chapters.lookup_result = None;
             app.chapters.lookup_duration_ms = None;
+            app.chapters.lookup_brand_ms = None;
             Some(Command::none())
         }
         Message::ChapterLookupCompleted(gen, Err(e)) => {
@@ -1,39 +1,53 @@
-use crate::ui::{Lectern, Message};
+use crate::ui::{Lectern, Message, NormalizationParam};
 use crate::ui::views::ViewMode;
-use crate::services::conversion::{ConversionConfig, ProcessingOptions, convert_to_m4b};
+use crate::services::conversion::{ConversionConfig, ProcessingOptions, Normalization, NormalizationMode, SplitBy, SplitMode, TrimMode, TrimOptions, AacProfile, convert_to_m4b, detect_input_type, probe_audio_file, remove_skipped_ranges, InputType};
+use crate::models::chapters::Chapter;
+use crate::error::LecternError;
+use crate::ui::preset_settings::{ConversionPreset, NormalizationLevels};
 use iced::Command;
 
 pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Message>> {
     match message {
         Message::StartConversion => {
+            // Check the inputs first so broken files show up before a long encode
+            if app.input_checks.is_none() {
+                if app.is_checking_inputs {
+                    return Some(Command::none());
+                }
+                let files = files_to_check(app);
+                if !files.is_empty() {
+                    app.convert_after_check = true;
+                    return Some(start_input_check(app, files));
+                }
+            }
             let output_path = if let Some(ref lib_path) = app.local_library_path {
                 Some(crate::ui::helpers::apply_media_template(
                     &app.media_management_template,
                     lib_path,
-                    &app.metadata.editing_title,
-                    &app.metadata.editing_author,
-                    &app.metadata.editing_series,
-                    &app.metadata.editing_series_number,
-                    &app.metadata.editing_publish_year,
-                    &app.metadata.editing_genre,
-                    &app.metadata.editing_asin,
-                    &app.metadata.editing_language,
-                    &app.metadata.editing_tags,
+                    &app.metadata.current_book(),
                 ))
             } else {
                 app.output_path.clone()
             };
+            // The library template and browsed paths may still carry another format's extension
+            let format = app.conversion_format;
+            let output_path = output_path.map(|p| format.apply_extension(&p));
             
             if let Some(path) = output_path {
                 app.is_converting = true;
                 app.conversion_error = None;
                 app.source_size = 0;
                 app.output_size = 0;
+                app.conversion_report = None;
                 
                 let selected_book = app.metadata.selected_book.clone();
                 let cover_path = app.cover.cover_image_path.clone();
                 let mut chapters = app.chapters.chapters.clone();
-                let audio_files = app.file.audio_file_paths.clone();
+                let skip_files: Vec<String> = app.input_check_skipped.iter().cloned().collect();
+                let audio_files: Vec<String> = app.file.audio_file_paths.iter()
+                    .filter(|f| !app.input_check_skipped.contains(*f))
+                    .cloned()
+                    .collect();
                 let selected_file_path = app.file.selected_file_path.clone();
                 
                 // Determine input path
@@ -80,7 +94,7 @@ pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Mes
                     audio_files[0].clone()
                 } else {
                     return Some(Command::perform(
-                        async move { Err("No input file or audio files found".to_string()) },
+                        async move { Err(LecternError::Other("No input file or audio files found".to_string())) },
                         Message::ConversionCompleted,
                     ));
                 };
@@ -88,8 +102,41 @@ pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Mes
                 println!("[DEBUG] Determined input path: {}", input_path);
                 
                 // If rewrite_chapters is enabled and we have files but no chapters, generate them
-                let mut processing_options = ProcessingOptions::default();
-                processing_options.normalize_volume = app.conversion_normalize_volume;
+                let trim = match trim_from_inputs(app) {
+                    Ok(trim) => trim,
+                    Err(e) => {
+                        return Some(Command::perform(async move { Err(e) }, Message::ConversionCompleted));
+                    }
+                };
+                let split = match split_from_inputs(app) {
+                    Ok(split) => split,
+                    Err(e) => {
+                        return Some(Command::perform(async move { Err(e) }, Message::ConversionCompleted));
+                    }
+                };
+                let processing_options = ProcessingOptions {
+                    normalization: normalization_from_inputs(app),
+                    trim,
+                    split,
+                    encode_strategy: app.conversion_encode_strategy,
+                    skip_files,
+                    write_sidecars: app.conversion_write_sidecars,
+                    cover_edit: app.cover.edit,
+                    ..ProcessingOptions::default()
+                };
+                
+                // Chapters from the full file list still cover the skipped audio
+                if !processing_options.skip_files.is_empty() && !chapters.is_empty() {
+                    match chapters_without_skipped(app, &input_path, &chapters, &processing_options.skip_files) {
+                        Ok(kept) => {
+                            println!("[DEBUG] {} of {} chapters left after skipping files", kept.len(), chapters.len());
+                            chapters = kept;
+                        }
+                        Err(e) => {
+                            return Some(Command::perform(async move { Err(e) }, Message::ConversionCompleted));
+                        }
+                    }
+                }
                 
                 if processing_options.rewrite_chapters && !audio_files.is_empty() && chapters.is_empty() {
                     // Generate chapters from files
@@ -100,7 +147,7 @@ pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Mes
                         }
                         Err(e) => {
                             return Some(Command::perform(
-                                async move { Err(format!("Failed to generate chapters: {}", e)) },
+                                async move { Err(e) },
                                 Message::ConversionCompleted,
                             ));
                         }
@@ -109,7 +156,7 @@ pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Mes
                 
                 if selected_book.is_none() {
                     return Some(Command::perform(
-                        async move { Err("No book selected".to_string()) },
+                        async move { Err(LecternError::Other("No book selected".to_string())) },
                         Message::ConversionCompleted,
                     ));
                 }
@@ -127,6 +174,8 @@ pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Mes
                     _ => None,
                 };
 
+                let sample_rate = app.conversion_sample_rate.parse::<u32>().ok();
+
                 let config = ConversionConfig {
                     input_path,
                     output_path: path.clone(),
@@ -136,6 +185,9 @@ pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Mes
                     audio_bitrate,
                     audio_codec: app.conversion_codec.clone(),
                     audio_channels,
+                    sample_rate,
+                    aac_profile: app.conversion_aac_profile,
+                    output_format: format,
                     processing_options,
                 };
                 
@@ -156,13 +208,14 @@ pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Mes
                 return Some(Command::perform(
                     async move {
                         match convert_to_m4b(config).await {
-                            Ok(output) => {
-                                let output_size = std::fs::metadata(&output)
+                            Ok(report) => {
+                                let output_size = report.output_paths.iter()
+                                    .filter_map(|p| std::fs::metadata(p).ok())
                                     .map(|m| m.len())
-                                    .unwrap_or(0);
-                                Ok((output, source_size, output_size))
+                                    .sum();
+                                Ok((report, source_size, output_size))
                             },
-                            Err(e) => Err(format!("Conversion failed: {}", e)),
+                            Err(e) => Err(e),
                         }
                     },
                     Message::ConversionCompleted,
@@ -173,8 +226,8 @@ pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Mes
                     let (tx, rx) = futures::channel::oneshot::channel();
                     std::thread::spawn(move || {
                         let dialog = rfd::FileDialog::new()
-                            .add_filter("M4B Files", &["m4b"])
-                            .set_file_name("output.m4b");
+                            .add_filter(format.to_string(), &[format.extension()])
+                            .set_file_name(format!("output.{}", format.extension()));
                         let result = dialog.save_file()
                             .map(|p| p.to_string_lossy().to_string());
                         let _ = tx.send(result);
@@ -186,15 +239,16 @@ pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Mes
             }
         }
         Message::BrowseOutputPath => {
+            let format = app.conversion_format;
             let default_filename = app.metadata.selected_book.as_ref()
-                .map(|b| format!("{}.m4b", b.title.replace("/", "-")))
-                .unwrap_or_else(|| "output.m4b".to_string());
+                .map(|b| format!("{}.{}", b.title.replace("/", "-"), format.extension()))
+                .unwrap_or_else(|| format!("output.{}", format.extension()));
             Some(Command::perform(async move {
                 let (tx, rx) = futures::channel::oneshot::channel();
                 let filename = default_filename.clone();
                 std::thread::spawn(move || {
                     let dialog = rfd::FileDialog::new()
-                        .add_filter("M4B Files", &["m4b"])
+                        .add_filter(format.to_string(), &[format.extension()])
                         .set_file_name(&filename);
                     let result = dialog.save_file()
                         .map(|p| p.to_string_lossy().to_string());
@@ -214,15 +268,50 @@ pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Mes
             // User cancelled
             Some(Command::none())
         }
-        Message::ConversionCompleted(Ok((path, src_size, out_size))) => {
+        Message::ConversionCompleted(Ok((report, src_size, out_size))) => {
             app.is_converting = false;
             app.conversion_error = None;
             app.source_size = src_size;
             app.output_size = out_size;
-            println!("[DEBUG] Conversion completed: {} (Source: {} bytes, Output: {} bytes)", path, src_size, out_size);
+            println!("[DEBUG] Conversion completed: {} (Source: {} bytes, Output: {} bytes)", report.output_paths.join(", "), src_size, out_size);
+            if let Some(ref loudness) = report.loudness {
+                println!("[DEBUG] Loudness before normalization: {:?}", loudness);
+            }
+            app.conversion_report = Some(report);
             // TODO: Show success message, optionally upload to Audiobookshelf
             Some(Command::none())
         }
+        Message::CheckInputs => {
+            let files = files_to_check(app);
+            if files.is_empty() || app.is_checking_inputs {
+                return Some(Command::none());
+            }
+            app.convert_after_check = false;
+            Some(start_input_check(app, files))
+        }
+        Message::InputCheckCompleted(checks) => {
+            app.is_checking_inputs = false;
+            // Broken files are left out by default; the user can put them back
+            app.input_check_skipped = checks.iter()
+                .filter(|c| c.has_errors())
+                .map(|c| c.path.clone())
+                .collect();
+            let clean = checks.iter().all(|c| c.issues.is_empty());
+            println!("[DEBUG] Input check: {} file(s), {} with errors", checks.len(), app.input_check_skipped.len());
+            app.input_checks = Some(checks);
+            if std::mem::take(&mut app.convert_after_check) && clean {
+                return Some(Command::perform(async {}, |_| Message::StartConversion));
+            }
+            Some(Command::none())
+        }
+        Message::InputCheckSkipToggled(path, skip) => {
+            if skip {
+                app.input_check_skipped.insert(path);
+            } else {
+                app.input_check_skipped.remove(&path);
+            }
+            Some(Command::none())
+        }
         Message::ConversionCompleted(Err(e)) => {
             app.is_converting = false;
             app.conversion_error = Some(e);
@@ -232,22 +321,290 @@ pub fn handle_convert(app: &mut Lectern, message: Message) -> Option<Command<Mes
             app.view_mode = ViewMode::Convert;
             Some(Command::none())
         }
-        Message::ConversionNormalizeVolumeToggled(normalize) => {
-            app.conversion_normalize_volume = normalize;
+        Message::ConversionNormalizationChanged(mode) => {
+            app.conversion_normalization = mode;
+            app.conversion_preset = None;
+            Some(Command::none())
+        }
+        Message::ConversionSplitByChanged(split_by) => {
+            if split_by != app.conversion_split_by {
+                app.conversion_split_limit = match split_by {
+                    SplitBy::None => String::new(),
+                    SplitBy::Duration => "20".to_string(),
+                    SplitBy::Size => "2000".to_string(),
+                };
+            }
+            app.conversion_split_by = split_by;
+            Some(Command::none())
+        }
+        Message::ConversionFormatChanged(format) => {
+            app.conversion_format = format;
+            app.conversion_preset = None;
+            if let Some(ref path) = app.output_path {
+                app.output_path = Some(format.apply_extension(path));
+            }
+            Some(Command::none())
+        }
+        Message::ConversionEncodeStrategyChanged(strategy) => {
+            app.conversion_encode_strategy = strategy;
+            Some(Command::none())
+        }
+        Message::ConversionTrimModeChanged(mode) => {
+            if mode == TrimMode::AudibleBrand {
+                // Show the values that will be used
+                if let Some((intro, outro)) = app.chapters.brand_ms {
+                    app.conversion_trim_start = format!("{}", intro as f64 / 1000.0);
+                    app.conversion_trim_end = format!("{}", outro as f64 / 1000.0);
+                }
+            }
+            app.conversion_trim_mode = mode;
+            Some(Command::none())
+        }
+        Message::ConversionTrimStartChanged(value) => {
+            app.conversion_trim_start = value;
+            Some(Command::none())
+        }
+        Message::ConversionTrimEndChanged(value) => {
+            app.conversion_trim_end = value;
+            Some(Command::none())
+        }
+        Message::ConversionTrimSilenceToggled(silence) => {
+            app.conversion_trim_silence = silence;
+            Some(Command::none())
+        }
+        Message::ConversionWriteSidecarsToggled(write) => {
+            app.conversion_write_sidecars = write;
+            Some(Command::none())
+        }
+        Message::ConversionSplitLimitChanged(limit) => {
+            app.conversion_split_limit = limit;
+            Some(Command::none())
+        }
+        Message::ConversionNormalizationParamChanged(param, value) => {
+            let field = match param {
+                NormalizationParam::SpeechnormExpansion => &mut app.speechnorm_expansion,
+                NormalizationParam::SpeechnormRaise => &mut app.speechnorm_raise,
+                NormalizationParam::SpeechnormPeak => &mut app.speechnorm_peak,
+                NormalizationParam::LoudnormTarget => &mut app.loudnorm_target,
+                NormalizationParam::LoudnormTruePeak => &mut app.loudnorm_true_peak,
+                NormalizationParam::LoudnormRange => &mut app.loudnorm_range,
+            };
+            *field = value;
             Some(Command::none())
         }
         Message::ConversionBitrateChanged(bitrate) => {
             app.conversion_bitrate = bitrate;
+            app.conversion_preset = None;
             Some(Command::none())
         }
         Message::ConversionCodecChanged(codec) => {
             app.conversion_codec = codec;
+            app.conversion_preset = None;
             Some(Command::none())
         }
         Message::ConversionChannelsChanged(channels) => {
             app.conversion_channels = channels;
+            app.conversion_preset = None;
+            Some(Command::none())
+        }
+        Message::ConversionSampleRateChanged(sample_rate) => {
+            app.conversion_sample_rate = sample_rate;
+            app.conversion_preset = None;
+            Some(Command::none())
+        }
+        Message::ConversionAacProfileChanged(profile) => {
+            app.conversion_aac_profile = profile;
+            app.conversion_preset = None;
+            if profile != AacProfile::Lc && app.fdk_aac_available.is_none() {
+                app.fdk_aac_available = Some(crate::services::conversion::fdk_aac_available());
+            }
+            Some(Command::none())
+        }
+        Message::ConversionPresetSelected(name) => {
+            if let Some(preset) = crate::ui::preset_settings::find(&app.conversion_presets, &name).cloned() {
+                apply_preset(app, &preset);
+            }
+            Some(Command::none())
+        }
+        Message::ConversionPresetNameChanged(name) => {
+            app.conversion_preset_name = name;
+            Some(Command::none())
+        }
+        Message::SaveConversionPreset => {
+            let name = app.conversion_preset_name.trim().to_string();
+            if name.is_empty() {
+                app.conversion_preset_status = Some("Enter a name for the preset".to_string());
+                return Some(Command::none());
+            }
+            let preset = ConversionPreset {
+                name: name.clone(),
+                format: app.conversion_format,
+                codec: app.conversion_codec.clone(),
+                bitrate: app.conversion_bitrate.clone(),
+                channels: app.conversion_channels.clone(),
+                sample_rate: app.conversion_sample_rate.clone(),
+                aac_profile: app.conversion_aac_profile,
+                normalization: app.conversion_normalization,
+                levels: NormalizationLevels {
+                    speechnorm_expansion: app.speechnorm_expansion.clone(),
+                    speechnorm_raise: app.speechnorm_raise.clone(),
+                    speechnorm_peak: app.speechnorm_peak.clone(),
+                    loudnorm_target: app.loudnorm_target.clone(),
+                    loudnorm_true_peak: app.loudnorm_true_peak.clone(),
+                    loudnorm_range: app.loudnorm_range.clone(),
+                },
+            };
+            match crate::ui::preset_settings::save(preset) {
+                Ok(()) => {
+                    app.conversion_presets = crate::ui::preset_settings::load();
+                    app.conversion_preset = Some(name.clone());
+                    app.conversion_preset_name.clear();
+                    app.conversion_preset_status = Some(format!("Saved preset '{}'", name));
+                }
+                Err(e) => app.conversion_preset_status = Some(e),
+            }
             Some(Command::none())
         }
         _ => None,
     }
 }
+
+/// Normalization from the Convert view inputs. Unparsable values keep the defaults; values are
+/// clamped to the ranges ffmpeg accepts.
+fn normalization_from_inputs(app: &Lectern) -> Normalization {
+    fn parse_into(input: &str, value: &mut f64, min: f64, max: f64) {
+        if let Some(v) = input.trim().parse::<f64>().ok().filter(|v| v.is_finite()) {
+            *value = v.clamp(min, max);
+        }
+    }
+    let mut normalization = match app.conversion_normalization {
+        NormalizationMode::None => Normalization::None,
+        NormalizationMode::SpeechNorm => Normalization::SPEECHNORM_DEFAULT,
+        NormalizationMode::Loudnorm => Normalization::LOUDNORM_DEFAULT,
+    };
+    match &mut normalization {
+        Normalization::None => {}
+        Normalization::SpeechNorm { expansion, raise, peak } => {
+            parse_into(&app.speechnorm_expansion, expansion, 1.0, 50.0);
+            parse_into(&app.speechnorm_raise, raise, 0.0, 1.0);
+            parse_into(&app.speechnorm_peak, peak, 0.0, 1.0);
+        }
+        Normalization::Loudnorm { target_i, true_peak, lra } => {
+            parse_into(&app.loudnorm_target, target_i, -70.0, -5.0);
+            parse_into(&app.loudnorm_true_peak, true_peak, -9.0, 0.0);
+            parse_into(&app.loudnorm_range, lra, 1.0, 20.0);
+        }
+    }
+    normalization
+}
+
+/// Load a preset into the Convert view settings.
+pub fn apply_preset(app: &mut Lectern, preset: &ConversionPreset) {
+    app.conversion_format = preset.format;
+    app.conversion_codec = preset.codec.clone();
+    app.conversion_bitrate = preset.bitrate.clone();
+    app.conversion_channels = preset.channels.clone();
+    app.conversion_sample_rate = preset.sample_rate.clone();
+    app.conversion_aac_profile = preset.aac_profile;
+    app.conversion_normalization = preset.normalization;
+    let levels = preset.levels.clone();
+    app.speechnorm_expansion = levels.speechnorm_expansion;
+    app.speechnorm_raise = levels.speechnorm_raise;
+    app.speechnorm_peak = levels.speechnorm_peak;
+    app.loudnorm_target = levels.loudnorm_target;
+    app.loudnorm_true_peak = levels.loudnorm_true_peak;
+    app.loudnorm_range = levels.loudnorm_range;
+    if let Some(ref path) = app.output_path {
+        app.output_path = Some(preset.format.apply_extension(path));
+    }
+    app.conversion_preset = Some(preset.name.clone());
+    app.conversion_preset_status = None;
+    println!("[DEBUG] Applied conversion preset '{}'", preset.name);
+}
+
+/// Trim from the Convert view: seconds typed in, or the Audible brand intro/outro lengths
+/// from the last applied Audnexus chapter lookup.
+fn trim_from_inputs(app: &Lectern) -> Result<TrimOptions, LecternError> {
+    let seconds = |value: &str| -> Result<u64, LecternError> {
+        if value.trim().is_empty() {
+            return Ok(0);
+        }
+        value.trim().parse::<f64>().ok().filter(|v| v.is_finite() && *v >= 0.0)
+            .map(|v| (v * 1000.0).round() as u64)
+            .ok_or_else(|| LecternError::Parse(format!("Invalid trim: '{}' (seconds)", value)))
+    };
+    let (start_ms, end_ms) = match app.conversion_trim_mode {
+        TrimMode::None => (0, 0),
+        TrimMode::Manual => (seconds(&app.conversion_trim_start)?, seconds(&app.conversion_trim_end)?),
+        TrimMode::AudibleBrand => app.chapters.brand_ms.ok_or_else(|| LecternError::Other(
+            "No Audible intro/outro lengths known; look up chapters from Audible (Chapters tab) first".to_string(),
+        ))?,
+    };
+    Ok(TrimOptions { start_ms, end_ms, silence: app.conversion_trim_silence })
+}
+
+/// Split mode from the Convert view: limit in hours (duration) or MB (size).
+fn split_from_inputs(app: &Lectern) -> Result<SplitMode, LecternError> {
+    if app.conversion_split_by == SplitBy::None {
+        return Ok(SplitMode::None);
+    }
+    let limit = app.conversion_split_limit.trim().parse::<f64>().ok().filter(|v| v.is_finite() && *v > 0.0)
+        .ok_or_else(|| LecternError::Parse(format!("Invalid part limit: '{}'", app.conversion_split_limit)))?;
+    Ok(match app.conversion_split_by {
+        SplitBy::Duration => SplitMode::MaxDurationMs((limit * 3_600_000.0) as u64),
+        SplitBy::Size => SplitMode::MaxBytes((limit * 1024.0 * 1024.0) as u64),
+        SplitBy::None => SplitMode::None,
+    })
+}
+
+/// `chapters` with the skipped files' time ranges removed, laid out in the order the conversion
+/// joins the files of `input_path`. Durations come from the input check, then ffprobe; when the
+/// chapters map one-to-one onto the files, a chapter's own duration stands in for its file. Fails
+/// when a skipped file cannot be placed on the timeline.
+fn chapters_without_skipped(app: &Lectern, input_path: &str, chapters: &[Chapter], skip_files: &[String]) -> Result<Vec<Chapter>, LecternError> {
+    // Only a directory input joins several files; a skipped single file is refused by the conversion
+    let InputType::Directory(files) = detect_input_type(input_path)? else {
+        return Ok(chapters.to_vec());
+    };
+    let file_mapped = chapters.len() == files.len();
+    let mut durations = Vec::with_capacity(files.len());
+    for (index, file) in files.iter().enumerate() {
+        let checked = app.input_checks.as_ref()
+            .and_then(|checks| checks.iter().find(|c| &c.path == file))
+            .and_then(|c| c.duration_ms);
+        let duration_ms = checked
+            .or_else(|| probe_audio_file(file).ok().map(|p| p.duration_ms))
+            .or_else(|| file_mapped.then(|| chapters[index].duration));
+        match duration_ms {
+            Some(duration_ms) => durations.push((file.clone(), duration_ms)),
+            None => {
+                return Err(LecternError::Other(format!(
+                    "Cannot adjust the chapters for the skipped files: the length of {} is unknown. Regenerate the chapters from the files or stop skipping it.",
+                    file
+                )));
+            }
+        }
+    }
+    Ok(remove_skipped_ranges(chapters, &durations, skip_files))
+}
+
+/// Inputs of the current book: the scanned audio files, or the selected file on its own.
+fn files_to_check(app: &Lectern) -> Vec<String> {
+    if !app.file.audio_file_paths.is_empty() {
+        app.file.audio_file_paths.clone()
+    } else {
+        app.file.selected_file_path.iter()
+            .filter(|p| std::path::Path::new(p).is_file())
+            .cloned()
+            .collect()
+    }
+}
+
+fn start_input_check(app: &mut Lectern, files: Vec<String>) -> Command<Message> {
+    app.is_checking_inputs = true;
+    app.conversion_error = None;
+    Command::perform(
+        crate::services::input_check::check_inputs(files),
+        Message::InputCheckCompleted,
+    )
+}
@@ -1,6 +1,8 @@
 use crate::ui::{Lectern, Message};
-use crate::ui::cover_search::{search_cover_art, download_image, download_images_parallel_threaded};
+use crate::ui::cover_search::{search_cover_art, search_cover_folder, search_cover_page, download_image, download_images_parallel_threaded};
 use crate::ui::views::ViewMode;
+use crate::services::cover_edit::{load_cover_bytes, render_cover};
+use crate::ui::state::{CoverPreview, CropSide};
 use iced::Command;
 
 pub fn handle_cover(app: &mut Lectern, message: Message) -> Option<Command<Message>> {
@@ -46,13 +48,10 @@ pub fn handle_cover(app: &mut Lectern, message: Message) -> Option<Command<Messa
             Some(Command::none())
         }
         Message::SearchCover => {
-            app.cover.is_searching_cover = true;
-            app.cover.cover_search_error = None;
-            app.cover.cover_search_results.clear();
-            app.cover.cover_search_result_handles.clear();
+            start_cover_search(app);
             
             let title = app.metadata.editing_title.clone();
-            let author = app.metadata.editing_author.clone();
+            let author = app.metadata.editing_authors.join(", ");
             let isbn = if app.metadata.editing_isbn.is_empty() {
                 None
             } else {
@@ -75,11 +74,56 @@ pub fn handle_cover(app: &mut Lectern, message: Message) -> Option<Command<Messa
                 Message::CoverSearchCompleted,
             ))
         }
+        Message::BrowseCoverFolder => {
+            Some(Command::perform(async move {
+                let (tx, rx) = futures::channel::oneshot::channel();
+                std::thread::spawn(move || {
+                    let result = rfd::FileDialog::new()
+                        .pick_folder()
+                        .map(|p| p.to_string_lossy().to_string());
+                    let _ = tx.send(result);
+                });
+                rx.await.unwrap_or(None)
+            }, Message::CoverFolderSelected))
+        }
+        Message::CoverFolderSelected(Some(dir)) => {
+            start_cover_search(app);
+            println!("[DEBUG] Searching folder for cover images: {}", dir);
+            Some(Command::perform(
+                async move { search_cover_folder(&dir).await },
+                Message::CoverSearchCompleted,
+            ))
+        }
+        Message::CoverFolderSelected(None) => {
+            // User cancelled
+            Some(Command::none())
+        }
+        Message::CoverPageUrlChanged(url) => {
+            app.cover.cover_page_url = url;
+            Some(Command::none())
+        }
+        Message::SearchCoverPage => {
+            let url = app.cover.cover_page_url.trim().to_string();
+            if url.is_empty() {
+                return Some(Command::none());
+            }
+            start_cover_search(app);
+            println!("[DEBUG] Searching page for cover images: {}", url);
+            Some(Command::perform(
+                async move { search_cover_page(&url).await },
+                Message::CoverSearchCompleted,
+            ))
+        }
         Message::CoverSearchCompleted(Ok(results)) => {
             app.cover.is_searching_cover = false;
             app.cover.cover_search_results = results.clone();
             println!("[DEBUG] Cover search completed: {} results displayed", app.cover.cover_search_results.len());
 
+            // Local images (folder search) are shown straight from disk
+            for result in results.iter().filter(|r| !r.url.starts_with("http://") && !r.url.starts_with("https://")) {
+                app.cover.cover_search_result_handles
+                    .insert(result.url.clone(), iced::widget::image::Handle::from_path(&result.url));
+            }
             let urls: Vec<String> = results
                 .into_iter()
                 .map(|r| r.url)
@@ -162,9 +206,65 @@ pub fn handle_cover(app: &mut Lectern, message: Message) -> Option<Command<Messa
                 } else {
                     app.cover.cover_image_data = None;
                     app.cover.cover_image_url_cached = None;
-                    if let Some(ref mut book) = app.metadata.selected_book {
-                        book.cover_url = Some(cover.url.clone());
-                    }
+                    let path = cover.url.clone();
+                    return handle_cover(app, Message::CoverImageSelected(Some(path)));
+                }
+            }
+            Some(Command::none())
+        }
+        Message::CoverEditorToggled => {
+            app.cover.show_editor = !app.cover.show_editor;
+            if app.cover.show_editor && app.cover.edit_preview.is_none() {
+                return Some(render_cover_preview(app));
+            }
+            Some(Command::none())
+        }
+        Message::CoverCropChanged(crop) => {
+            app.cover.edit.crop = crop;
+            Some(render_cover_preview(app))
+        }
+        Message::CoverCropBoxChanged(side, value) => {
+            let crop_box = &mut app.cover.edit.crop_box;
+            let value = value.clamp(0.0, 1.0);
+            match side {
+                CropSide::Left => crop_box.left = value,
+                CropSide::Top => crop_box.top = value,
+                CropSide::Width => crop_box.width = value,
+                CropSide::Height => crop_box.height = value,
+            }
+            Some(Command::none()) // Rendered on slider release
+        }
+        Message::CoverMaxSizeChanged(size) => {
+            app.cover.edit.max_size = size;
+            Some(render_cover_preview(app))
+        }
+        Message::CoverJpegQualityChanged(quality) => {
+            app.cover.edit.jpeg_quality = quality;
+            Some(Command::none()) // Rendered on slider release
+        }
+        Message::RenderCoverPreview => Some(render_cover_preview(app)),
+        Message::CoverPreviewRendered(generation, source, result) => {
+            if generation != app.cover.preview_generation {
+                return Some(Command::none()); // A newer render is on its way
+            }
+            app.cover.is_rendering_preview = false;
+            match result {
+                Ok(cover) => {
+                    app.cover.edit_preview_error = None;
+                    app.cover.edit_preview = Some(CoverPreview {
+                        bytes: cover.jpeg.len(),
+                        handle: iced::widget::image::Handle::from_memory(cover.jpeg),
+                        source,
+                        width: cover.width,
+                        height: cover.height,
+                        source_width: cover.source_width,
+                        source_height: cover.source_height,
+                    });
+                }
+                Err(e) => {
+                    println!("[DEBUG] Cover preview failed: {}", e);
+                    app.cover.edit_preview = None;
+                    app.cover.edit_preview_error = Some(e);
                 }
             }
             Some(Command::none())
@@ -266,6 +366,22 @@ pub fn handle_cover(app: &mut Lectern, message: Message) -> Option<Command<Messa
             }
             Some(Command::none())
         }
+        Message::EmbeddedCoversFound(path, covers) => {
+            if app.file.selected_file_path.as_ref() != Some(&path) {
+                return Some(Command::none());
+            }
+            app.cover.is_scanning_embedded = false;
+            app.cover.embedded_covers = covers;
+            // No cover from metadata or the folder: the book's own artwork is the obvious choice
+            let first = app.cover.embedded_covers.first().map(|c| c.path.clone());
+            match first {
+                Some(first) if app.cover.cover_image_path.is_none() && !app.cover.is_downloading_cover => {
+                    println!("[DEBUG] Using embedded cover {}", first);
+                    handle_cover(app, Message::CoverImageSelected(Some(first)))
+                }
+                _ => Some(Command::none()),
+            }
+        }
         Message::SwitchToCover => {
             app.view_mode = ViewMode::Cover;
             // Check if we need to download or load the cover image handle
@@ -325,3 +441,41 @@ pub fn handle_cover(app: &mut Lectern, message: Message) -> Option<Command<Messa
         _ => None,
     }
 }
+
+/// Clear the previous results before a cover search of any kind.
+fn start_cover_search(app: &mut Lectern) {
+    app.cover.is_searching_cover = true;
+    app.cover.cover_search_error = None;
+    app.cover.cover_search_results.clear();
+    app.cover.cover_search_result_handles.clear();
+}
+
+/// Render the cover with the current edit settings, off the UI thread. Uses the downloaded
+/// bytes of a URL cover when they are cached.
+fn render_cover_preview(app: &mut Lectern) -> Command<Message> {
+    let Some(source) = app.cover.cover_image_path.clone() else {
+        app.cover.edit_preview = None;
+        return Command::none();
+    };
+    app.cover.preview_generation += 1;
+    app.cover.is_rendering_preview = true;
+    let generation = app.cover.preview_generation;
+    let edit = app.cover.edit;
+    let rendered_from = source.clone();
+    let cached = app.cover.cover_image_data.clone()
+        .filter(|_| app.cover.cover_image_url_cached.as_deref() == Some(source.as_str()));
+    Command::perform(
+        async move {
+            let bytes = match cached {
+                Some(bytes) => bytes,
+                None => load_cover_bytes(&source).await.map_err(|e| e.to_string())?,
+            };
+            let cover = tokio::task::spawn_blocking(move || render_cover(&bytes, &edit))
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string())?;
+            Ok(cover)
+        },
+        move |result| Message::CoverPreviewRendered(generation, rendered_from, result),
+    )
+}
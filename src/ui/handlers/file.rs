@@ -5,6 +5,9 @@ use crate::ui::views::ViewMode;
 use crate::ui::state::CoverState;
 use crate::utils::chapter_file::{parse_chapters_from_path, is_chapter_file_name};
 use crate::services::ffprobe::{extract_chapters_from_file, generate_chapters_from_files};
+use crate::services::abs_sidecar::{is_sidecar_file_name, read_sidecar, METADATA_JSON};
+use crate::services::embedded_cover::extract_embedded_covers;
+use crate::services::BookMetadata;
 use std::path::Path;
 use iced::Command;
 
@@ -16,6 +19,7 @@ pub fn handle_file(app: &mut Lectern, message: Message) -> Option<Command<Messag
             app.file.audio_file_paths.clear();
             app.file.found_metadata_chapter_files.clear();
             app.file.file_parse_error = None;
+            app.metadata.import_error = None;
             // Stop any in-flight chapter loading and wipe chapter state
             app.chapters.is_mapping_from_files = false;
             app.chapters.is_looking_up_chapters = false;
@@ -25,9 +29,15 @@ pub fn handle_file(app: &mut Lectern, message: Message) -> Option<Command<Messag
             app.chapters.chapter_time_editing.clear();
             app.chapters.lookup_result = None;
             app.chapters.lookup_duration_ms = None;
+            app.chapters.lookup_brand_ms = None;
+            app.chapters.brand_ms = None;
+            app.chapters.edition_candidates.clear();
             app.chapters.load_generation = app.chapters.load_generation.wrapping_add(1);
-            // Wipe cover state for the previous book
-            app.cover = CoverState::default();
+            app.input_checks = None;
+            app.input_check_skipped.clear();
+            // Wipe cover state for the previous book; the editor's shape, size and quality carry over
+            let edit = crate::services::cover_edit::CoverEdit { crop_box: Default::default(), ..app.cover.edit };
+            app.cover = CoverState { edit, ..CoverState::default() };
             // Stop chapter playback if running
             app.chapter_playback_state = None;
             let _ = app.chapter_playback_process.take();
@@ -80,6 +90,10 @@ pub fn handle_file(app: &mut Lectern, message: Message) -> Option<Command<Messag
             app.file.file_parse_error = None;
             app.source_size = 0;
             app.output_size = 0;
+            app.conversion_report = None;
+            app.chapters.brand_ms = None;
+            app.input_checks = None;
+            app.input_check_skipped.clear();
             
             let path_clone = path.clone();
             Some(Command::perform(
@@ -147,6 +161,10 @@ pub fn handle_file(app: &mut Lectern, message: Message) -> Option<Command<Messag
                 app.file.file_parse_error = None;
                 app.source_size = 0;
                 app.output_size = 0;
+                app.conversion_report = None;
+                app.chapters.brand_ms = None;
+                app.input_checks = None;
+                app.input_check_skipped.clear();
                 
                 let path_clone = path.clone();
                 let path_obj = Path::new(&path_clone);
@@ -162,7 +180,7 @@ pub fn handle_file(app: &mut Lectern, message: Message) -> Option<Command<Messag
                                 let result = parse_audiobook_file(&path_clone);
                                 match &result {
                                     Ok(meta) => println!("[DEBUG] Directory parsed successfully: '{}' by '{}' ({} files)", 
-                                        meta.title, meta.author, audio_files.len()),
+                                        meta.title, meta.author_display(), audio_files.len()),
                                     Err(e) => println!("[ERROR] Directory parse error: {}", e),
                                 }
                                 result
@@ -182,7 +200,7 @@ pub fn handle_file(app: &mut Lectern, message: Message) -> Option<Command<Messag
                         async move {
                             let result = parse_audiobook_file(&path_clone);
                             match &result {
-                                Ok(meta) => println!("[DEBUG] File parsed successfully: '{}' by '{}'", meta.title, meta.author),
+                                Ok(meta) => println!("[DEBUG] File parsed successfully: '{}' by '{}'", meta.title, meta.author_display()),
                                 Err(e) => println!("[DEBUG] File parse error: {}", e),
                             }
                             result
@@ -202,67 +220,117 @@ pub fn handle_file(app: &mut Lectern, message: Message) -> Option<Command<Messag
             Some(Command::none())
         }
         Message::FileParsed(Ok(metadata)) => {
-            println!("[DEBUG] FileParsed(Ok) - Successfully parsed file/directory");
+            let load = load_parsed_book(app, metadata);
+            Some(Command::batch([load, scan_embedded_covers(app), review_sidecars(app)]))
+        }
+        Message::FileParsed(Err(e)) => {
+            println!("[DEBUG] FileParsed(Err) - Error: {}", e);
             app.file.is_parsing_file = false;
-            app.metadata.selected_book = Some(metadata.clone());
-            // Populate editing fields
-            app.metadata.editing_title = metadata.title.clone();
-            app.metadata.editing_subtitle = metadata.subtitle.unwrap_or_default();
-            app.metadata.editing_author = metadata.author.clone();
-            app.metadata.editing_series = metadata.series.unwrap_or_default();
-            app.metadata.editing_series_number = metadata.series_number.unwrap_or_default();
-            app.metadata.editing_narrator = metadata.narrator.unwrap_or_default();
-            app.metadata.editing_description = metadata.description.unwrap_or_default();
-            app.metadata.editing_description_content = iced::widget::text_editor::Content::with_text(&app.metadata.editing_description);
-            app.metadata.editing_isbn = metadata.isbn.unwrap_or_default();
-            app.metadata.editing_asin = metadata.asin.unwrap_or_default();
-            app.metadata.editing_publisher = metadata.publisher.unwrap_or_default();
-            app.metadata.editing_publish_year = metadata.publish_year.unwrap_or_default();
-            app.metadata.editing_genre = metadata.genre.unwrap_or_default();
-            app.metadata.editing_tags = metadata.tags.unwrap_or_default();
-            app.metadata.editing_language = metadata.language.unwrap_or_default();
-            app.metadata.editing_explicit = metadata.explicit.unwrap_or(false);
-            app.metadata.editing_abridged = metadata.abridged.unwrap_or(false);
-            
-            // Initialize cover image path and handle caching
-            app.cover.cover_image_path = metadata.cover_url.clone();
-            if let Some(ref cover_url) = metadata.cover_url {
-                if cover_url.starts_with("http://") || cover_url.starts_with("https://") {
-                    if app.cover.cover_image_url_cached.as_ref() != Some(cover_url) {
-                        app.cover.cover_image_data = None;
-                        app.cover.cover_image_handle = None;
-                        app.cover.cover_image_url_cached = None;
-                        app.cover.is_downloading_cover = true;
-                        let url_clone = cover_url.clone();
-                        return Some(Command::perform(
-                            async move {
-                                match download_image(&url_clone).await {
-                                    Ok((url, data)) => {
-                                        if let Ok(img) = ::image::load_from_memory(&data) {
-                                            let rgba = img.to_rgba8();
-                                            let (width, height) = rgba.dimensions();
-                                            let pixels: Vec<u8> = rgba.into_raw();
-                                            let handle = iced::widget::image::Handle::from_pixels(width, height, pixels);
-                                            Ok((url, data, handle))
-                                        } else {
-                                            Err("Failed to decode image".to_string())
-                                        }
-                                    },
-                                    Err(e) => Err(e),
+            app.file.file_parse_error = Some(e.clone());
+            println!("[ERROR] Failed to parse file/directory: {}", e);
+            Some(Command::none())
+        }
+        _ => None,
+    }
+}
+
+/// Audiobookshelf sidecars (metadata.json, .opf) in the source folder open the same field-by-field
+/// review as "Import from file" instead of overwriting the tag metadata.
+fn review_sidecars(app: &mut Lectern) -> Command<Message> {
+    let has_sidecar = app.file.found_metadata_chapter_files.iter().any(|(name, _)| is_sidecar_file_name(name));
+    if !has_sidecar {
+        return Command::none();
+    }
+    crate::ui::handlers::merge::handle_merge(app, Message::ImportMetadataFiles).unwrap_or_else(Command::none)
+}
+
+/// Show a freshly parsed book: cover, audio files and chapters. Sidecar metadata is reviewed
+/// afterwards (`review_sidecars`); only metadata.json's chapters are taken here.
+fn load_parsed_book(app: &mut Lectern, metadata: BookMetadata) -> Command<Message> {
+    println!("[DEBUG] FileParsed(Ok) - Successfully parsed file/directory");
+    app.file.is_parsing_file = false;
+    let mut sidecar_chapters = Vec::new();
+    if let Some(dir) = app.file.selected_file_path.clone().filter(|p| Path::new(p).is_dir()) {
+        for (name, path) in find_metadata_or_chapter_files(&dir) {
+            if name.to_lowercase() != METADATA_JSON {
+                continue;
+            }
+            match read_sidecar(&path) {
+                Ok(Some(import)) => sidecar_chapters = import.chapters,
+                Ok(None) => {}
+                Err(e) => println!("[DEBUG] Could not read {}: {}", name, e),
+            }
+        }
+    }
+    app.metadata.load_book(metadata.clone());
+    
+    // Initialize cover image path and handle caching
+    app.cover.cover_image_path = metadata.cover_url.clone();
+    if let Some(ref cover_url) = metadata.cover_url {
+        if cover_url.starts_with("http://") || cover_url.starts_with("https://") {
+            if app.cover.cover_image_url_cached.as_ref() != Some(cover_url) {
+                app.cover.cover_image_data = None;
+                app.cover.cover_image_handle = None;
+                app.cover.cover_image_url_cached = None;
+                app.cover.is_downloading_cover = true;
+                let url_clone = cover_url.clone();
+                return Command::perform(
+                    async move {
+                        match download_image(&url_clone).await {
+                            Ok((url, data)) => {
+                                if let Ok(img) = ::image::load_from_memory(&data) {
+                                    let rgba = img.to_rgba8();
+                                    let (width, height) = rgba.dimensions();
+                                    let pixels: Vec<u8> = rgba.into_raw();
+                                    let handle = iced::widget::image::Handle::from_pixels(width, height, pixels);
+                                    Ok((url, data, handle))
+                                } else {
+                                    Err("Failed to decode image".to_string())
                                 }
                             },
-                            Message::CoverImageDownloaded,
-                        ));
-                    } else {
-                        println!("[DEBUG] Cover image already cached for URL: {}", cover_url);
+                            Err(e) => Err(e),
+                        }
+                    },
+                    Message::CoverImageDownloaded,
+                );
+            } else {
+                println!("[DEBUG] Cover image already cached for URL: {}", cover_url);
+            }
+        } else {
+            // Local file - load handle
+            app.cover.cover_image_data = None;
+            app.cover.cover_image_url_cached = None;
+            app.cover.cover_image_handle = None;
+            
+            let path = std::path::Path::new(cover_url);
+            if path.exists() {
+                if let Ok(img_data) = std::fs::read(path) {
+                    if let Ok(img) = ::image::load_from_memory(&img_data) {
+                        let rgba = img.to_rgba8();
+                        let (width, height) = rgba.dimensions();
+                        let pixels: Vec<u8> = rgba.into_raw();
+                        app.cover.cover_image_handle = Some(iced::widget::image::Handle::from_pixels(width, height, pixels));
                     }
-                } else {
-                    // Local file - load handle
+                }
+            }
+        }
+    } else {
+        app.cover.cover_image_data = None;
+        app.cover.cover_image_handle = None;
+        app.cover.cover_image_url_cached = None;
+    }
+    app.chapters.book_duration_ms = None;
+    println!("[DEBUG] FileParsed - Switching to Metadata view");
+    app.view_mode = crate::ui::views::ViewMode::Metadata;
+    // If directory and no cover from metadata, look for local cover (folder.jpg, cover.jpg, etc.)
+    if app.cover.cover_image_handle.is_none() {
+        if let Some(ref file_path) = app.file.selected_file_path {
+            if Path::new(file_path).is_dir() {
+                if let Some(local_cover) = find_local_cover_in_directory(file_path) {
+                    app.cover.cover_image_path = Some(local_cover.clone());
                     app.cover.cover_image_data = None;
                     app.cover.cover_image_url_cached = None;
-                    app.cover.cover_image_handle = None;
-                    
-                    let path = std::path::Path::new(cover_url);
+                    let path = std::path::Path::new(&local_cover);
                     if path.exists() {
                         if let Ok(img_data) = std::fs::read(path) {
                             if let Ok(img) = ::image::load_from_memory(&img_data) {
@@ -274,144 +342,133 @@ pub fn handle_file(app: &mut Lectern, message: Message) -> Option<Command<Messag
                         }
                     }
                 }
-            } else {
-                app.cover.cover_image_data = None;
-                app.cover.cover_image_handle = None;
-                app.cover.cover_image_url_cached = None;
             }
-            app.chapters.book_duration_ms = None;
-            println!("[DEBUG] FileParsed - Switching to Metadata view");
-            app.view_mode = crate::ui::views::ViewMode::Metadata;
-            // If directory and no cover from metadata, look for local cover (folder.jpg, cover.jpg, etc.)
-            if app.cover.cover_image_handle.is_none() {
-                if let Some(ref file_path) = app.file.selected_file_path {
-                    if Path::new(file_path).is_dir() {
-                        if let Some(local_cover) = find_local_cover_in_directory(file_path) {
-                            app.cover.cover_image_path = Some(local_cover.clone());
-                            app.cover.cover_image_data = None;
-                            app.cover.cover_image_url_cached = None;
-                            let path = std::path::Path::new(&local_cover);
-                            if path.exists() {
-                                if let Ok(img_data) = std::fs::read(path) {
-                                    if let Ok(img) = ::image::load_from_memory(&img_data) {
-                                        let rgba = img.to_rgba8();
-                                        let (width, height) = rgba.dimensions();
-                                        let pixels: Vec<u8> = rgba.into_raw();
-                                        app.cover.cover_image_handle = Some(iced::widget::image::Handle::from_pixels(width, height, pixels));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+        }
+    }
+    
+    // Store audio file paths and scan for metadata/chapter files if directory was selected
+    if let Some(ref file_path) = app.file.selected_file_path {
+        if Path::new(file_path).is_dir() {
+            let audio_files = get_audio_files_from_directory(file_path);
+            app.file.audio_file_paths = audio_files;
+            app.file.found_metadata_chapter_files = find_metadata_or_chapter_files(file_path);
+            println!("[DEBUG] Stored {} audio file paths, {} metadata/chapter files", app.file.audio_file_paths.len(), app.file.found_metadata_chapter_files.len());
+            if !app.file.found_metadata_chapter_files.is_empty() {
+                let names: Vec<&str> = app.file.found_metadata_chapter_files.iter().map(|(n, _)| n.as_str()).collect();
+                println!("[DEBUG] Found in folder: {}", names.join(", "));
             }
-            
-            // Store audio file paths and scan for metadata/chapter files if directory was selected
-            if let Some(ref file_path) = app.file.selected_file_path {
-                if Path::new(file_path).is_dir() {
-                    let audio_files = get_audio_files_from_directory(file_path);
-                    app.file.audio_file_paths = audio_files;
-                    app.file.found_metadata_chapter_files = find_metadata_or_chapter_files(file_path);
-                    println!("[DEBUG] Stored {} audio file paths, {} metadata/chapter files", app.file.audio_file_paths.len(), app.file.found_metadata_chapter_files.len());
-                    if !app.file.found_metadata_chapter_files.is_empty() {
-                        let names: Vec<&str> = app.file.found_metadata_chapter_files.iter().map(|(n, _)| n.as_str()).collect();
-                        println!("[DEBUG] Found in folder: {}", names.join(", "));
-                    }
 
-                    // Auto-load chapters from a chapter file in the directory (txt, json, cue, ini)
-                    for (name, path) in &app.file.found_metadata_chapter_files {
-                        if is_chapter_file_name(name) {
-                            if let Ok(chapters) = parse_chapters_from_path(path) {
-                                if !chapters.is_empty() {
-                                    app.chapters.chapters = chapters;
-                                    app.chapters.lookup_error = None;
-                                    println!("[DEBUG] Loaded {} chapters from file {}", app.chapters.chapters.len(), name);
-                                    break;
-                                }
-                            }
+            // Chapters from an Audiobookshelf metadata.json; a chapter file below takes precedence
+            if !sidecar_chapters.is_empty() {
+                println!("[DEBUG] Loaded {} chapters from metadata.json", sidecar_chapters.len());
+                app.chapters.chapters = sidecar_chapters;
+                app.chapters.lookup_error = None;
+            }
+
+            // Auto-load chapters from a chapter file in the directory (txt, json, cue, ini)
+            for (name, path) in &app.file.found_metadata_chapter_files {
+                if is_chapter_file_name(name) {
+                    if let Ok(chapters) = parse_chapters_from_path(path) {
+                        if !chapters.is_empty() {
+                            app.chapters.chapters = chapters;
+                            app.chapters.lookup_error = None;
+                            println!("[DEBUG] Loaded {} chapters from file {}", app.chapters.chapters.len(), name);
+                            break;
                         }
                     }
-                } else {
-                    app.file.audio_file_paths.clear();
-                    app.file.found_metadata_chapter_files.clear();
-
-                    // Auto-extract chapters from single file (e.g. M4B with embedded chapters)
-                    app.chapters.is_looking_up_chapters = true;
-                    app.chapters.lookup_error = None;
-                    let path_clone = file_path.clone();
-                    let gen = app.chapters.load_generation;
-                    return Some(Command::perform(
-                        async move {
-                            tokio::task::spawn_blocking(move || {
-                                extract_chapters_from_file(&path_clone).map_err(|e| e.to_string())
-                            })
-                            .await
-                            .unwrap_or_else(|_| Err("Task join error".to_string()))
-                        },
-                        move |result| Message::ChapterExtractCompleted(gen, result),
-                    ));
                 }
             }
+        } else {
+            app.file.audio_file_paths.clear();
+            app.file.found_metadata_chapter_files.clear();
 
-            // If directory with audio files but no chapters yet, auto-map one chapter per file
-            let need_duration = !app.file.audio_file_paths.is_empty();
-            let need_map_chapters = need_duration && app.chapters.chapters.is_empty();
-            if need_map_chapters {
-                app.chapters.is_mapping_from_files = true;
-                app.chapters.lookup_error = None;
-                let paths = app.file.audio_file_paths.clone();
-                let gen = app.chapters.load_generation;
-                let map_cmd = Command::perform(
-                    async move {
-                        tokio::task::spawn_blocking(move || {
-                            generate_chapters_from_files(&paths).map_err(|e| e.to_string())
-                        })
-                        .await
-                        .unwrap_or_else(|_| Err("Task failed".to_string()))
-                    },
-                    move |result| Message::MapChaptersFromFilesCompleted(gen, result),
-                );
-                if need_duration {
-                    let duration_paths = app.file.audio_file_paths.clone();
-                    let duration_gen = app.chapters.load_generation;
-                    let duration_cmd = Command::perform(
-                        async move {
-                            tokio::task::spawn_blocking(move || {
-                                crate::services::conversion::get_total_duration(&duration_paths).map_err(|e| e.to_string())
-                            })
-                            .await
-                            .unwrap_or(Err("Task failed".into()))
-                        },
-                        move |result| Message::BookDurationComputed(duration_gen, result),
-                    );
-                    return Some(Command::batch([duration_cmd, map_cmd]));
-                }
-                return Some(map_cmd);
-            }
-            // Compute total book duration in background (for chapters validation)
-            if need_duration {
-                let paths = app.file.audio_file_paths.clone();
-                let duration_gen = app.chapters.load_generation;
-                return Some(Command::perform(
-                    async move {
-                        tokio::task::spawn_blocking(move || {
-                            crate::services::conversion::get_total_duration(&paths).map_err(|e| e.to_string())
-                        })
-                        .await
-                        .unwrap_or(Err("Task failed".into()))
-                    },
-                    move |result| Message::BookDurationComputed(duration_gen, result),
-                ));
-            }
-            Some(Command::none())
+            // Auto-extract chapters from single file (e.g. M4B with embedded chapters)
+            app.chapters.is_looking_up_chapters = true;
+            app.chapters.lookup_error = None;
+            let path_clone = file_path.clone();
+            let gen = app.chapters.load_generation;
+            return Command::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || {
+                        extract_chapters_from_file(&path_clone).map_err(|e| e.to_string())
+                    })
+                    .await
+                    .unwrap_or_else(|_| Err("Task join error".to_string()))
+                },
+                move |result| Message::ChapterExtractCompleted(gen, result),
+            );
         }
-        Message::FileParsed(Err(e)) => {
-            println!("[DEBUG] FileParsed(Err) - Error: {}", e);
-            app.file.is_parsing_file = false;
-            app.file.file_parse_error = Some(e.clone());
-            println!("[ERROR] Failed to parse file/directory: {}", e);
-            Some(Command::none())
+    }
+
+    // If directory with audio files but no chapters yet, auto-map one chapter per file
+    let need_duration = !app.file.audio_file_paths.is_empty();
+    let need_map_chapters = need_duration && app.chapters.chapters.is_empty();
+    if need_map_chapters {
+        app.chapters.is_mapping_from_files = true;
+        app.chapters.lookup_error = None;
+        let paths = app.file.audio_file_paths.clone();
+        let gen = app.chapters.load_generation;
+        let map_cmd = Command::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    generate_chapters_from_files(&paths).map_err(|e| e.to_string())
+                })
+                .await
+                .unwrap_or_else(|_| Err("Task failed".to_string()))
+            },
+            move |result| Message::MapChaptersFromFilesCompleted(gen, result),
+        );
+        if need_duration {
+            let duration_paths = app.file.audio_file_paths.clone();
+            let duration_gen = app.chapters.load_generation;
+            let duration_cmd = Command::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || {
+                        crate::services::conversion::get_total_duration(&duration_paths).map_err(|e| e.to_string())
+                    })
+                    .await
+                    .unwrap_or(Err("Task failed".into()))
+                },
+                move |result| Message::BookDurationComputed(duration_gen, result),
+            );
+            return Command::batch([duration_cmd, map_cmd]);
         }
-        _ => None,
+        return map_cmd;
+    }
+    // Compute total book duration in background (for chapters validation)
+    if need_duration {
+        let paths = app.file.audio_file_paths.clone();
+        let duration_gen = app.chapters.load_generation;
+        return Command::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    crate::services::conversion::get_total_duration(&paths).map_err(|e| e.to_string())
+                })
+                .await
+                .unwrap_or(Err("Task failed".into()))
+            },
+            move |result| Message::BookDurationComputed(duration_gen, result),
+        );
+    }
+    Command::none()
+}
+
+/// Look for pictures embedded in the book's audio files (the folder's tracks or the single file).
+fn scan_embedded_covers(app: &mut Lectern) -> Command<Message> {
+    let Some(selected) = app.file.selected_file_path.clone() else {
+        return Command::none();
+    };
+    let files = if Path::new(&selected).is_dir() {
+        get_audio_files_from_directory(&selected)
+    } else {
+        vec![selected.clone()]
+    };
+    if files.is_empty() {
+        return Command::none();
     }
+    app.cover.is_scanning_embedded = true;
+    Command::perform(
+        async move { extract_embedded_covers(&files).await },
+        move |covers| Message::EmbeddedCoversFound(selected, covers),
+    )
 }
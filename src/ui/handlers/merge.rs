@@ -0,0 +1,123 @@
+use crate::ui::{Lectern, Message};
+use crate::ui::handlers::search::load_cover_url;
+use crate::ui::state::{MergeField, MergeState};
+use crate::ui::views::ViewMode;
+use crate::services::metadata_import::{import_metadata_file, is_metadata_file_name};
+use iced::Command;
+
+pub fn handle_merge(app: &mut Lectern, message: Message) -> Option<Command<Message>> {
+    match message {
+        Message::OpenMerge(index) => {
+            if let Some(result) = app.search.results.get(index) {
+                let candidates = result
+                    .variants
+                    .iter()
+                    .map(|v| (v.provider.clone(), v.book.clone()))
+                    .collect();
+                app.merge = MergeState::open(app.metadata.current_book(), candidates, &app.merge_preferences);
+                println!("[DEBUG] OpenMerge - '{}' with {} provider result(s)", result.book.title, result.variants.len());
+                app.view_mode = ViewMode::Merge;
+            }
+            Some(Command::none())
+        }
+        Message::MergeFieldSourceSelected(field, source) => {
+            if source < app.merge.sources.len() {
+                app.merge.choices.insert(field, source);
+            }
+            Some(Command::none())
+        }
+        Message::MergeApply => {
+            let Some(book) = app.merge.merged() else {
+                return Some(Command::none());
+            };
+            let cover_changed = MergeField::CoverUrl.get(&book) != app.metadata.current_book().cover_url;
+            println!("[DEBUG] MergeApply - applying merged metadata for '{}'", book.title);
+            app.metadata.load_book(book.clone());
+            app.merge = MergeState::default();
+            app.search.results.clear(); // Close search view
+            app.view_mode = ViewMode::Metadata;
+            if cover_changed {
+                if let Some(ref cover_url) = book.cover_url {
+                    app.cover.cover_image_path = Some(cover_url.clone());
+                    return Some(load_cover_url(app, cover_url));
+                }
+            }
+            Some(Command::none())
+        }
+        Message::MergeCancel => {
+            app.view_mode = if app.merge.from_files { ViewMode::Metadata } else { ViewMode::Search };
+            app.merge = MergeState::default();
+            Some(Command::none())
+        }
+        Message::ImportMetadataFiles => {
+            let files = importable_metadata_files(app);
+            if files.is_empty() || app.metadata.is_importing_files {
+                return Some(Command::none());
+            }
+            app.metadata.is_importing_files = true;
+            app.metadata.import_error = None;
+            Some(Command::perform(
+                async move {
+                    files
+                        .into_iter()
+                        .filter_map(|(name, path)| match import_metadata_file(&path) {
+                            Ok(Some(book)) => Some((name, Ok(book))),
+                            Ok(None) => {
+                                println!("[DEBUG] No metadata in {}", name);
+                                None
+                            }
+                            Err(e) => Some((name, Err(e.to_string()))),
+                        })
+                        .collect()
+                },
+                Message::MetadataFilesImported,
+            ))
+        }
+        Message::MetadataFilesImported(results) => {
+            app.metadata.is_importing_files = false;
+            let mut imported = Vec::new();
+            let mut errors = Vec::new();
+            for (name, result) in results {
+                match result {
+                    Ok(book) => imported.push((name, book)),
+                    Err(e) => errors.push(format!("{}: {}", name, e)),
+                }
+            }
+            for error in &errors {
+                println!("[ERROR] Metadata import failed: {}", error);
+            }
+            if imported.is_empty() {
+                app.metadata.import_error = Some(errors.first().cloned()
+                    .unwrap_or_else(|| "No metadata found in the folder's files".to_string()));
+                return Some(Command::none());
+            }
+            app.metadata.import_error = (!errors.is_empty()).then(|| errors.join("; "));
+            println!("[DEBUG] Imported metadata from {} file(s)", imported.len());
+            app.merge = MergeState::open_import(app.metadata.current_book(), imported);
+            app.view_mode = ViewMode::Merge;
+            Some(Command::none())
+        }
+        _ => None,
+    }
+}
+
+/// Metadata files found in the source folder, most specific first (metadata.json and OPF,
+/// then NFO/XML/book.json, then plain text).
+pub(crate) fn importable_metadata_files(app: &Lectern) -> Vec<(String, String)> {
+    let mut files: Vec<(String, String)> = app.file.found_metadata_chapter_files
+        .iter()
+        .filter(|(name, _)| is_metadata_file_name(name))
+        .cloned()
+        .collect();
+    files.sort_by_key(|(name, _)| {
+        let lower = name.to_lowercase();
+        if lower == "metadata.json" || lower.ends_with(".opf") {
+            0
+        } else if lower.ends_with(".txt") {
+            2
+        } else {
+            1
+        }
+    });
+    files
+}
@@ -10,25 +10,41 @@ pub fn handle_metadata(app: &mut Lectern, message: Message) -> Option<Command<Me
             }
             Some(Command::none())
         }
-        Message::AuthorChanged(author) => {
-            app.metadata.editing_author = author.clone();
-            if let Some(ref mut book) = app.metadata.selected_book {
-                book.author = author;
+        Message::ListInputChanged(field, value) => {
+            // Typing a separator commits the chip, so pasted "A, B" lists split right away
+            *app.metadata.input_mut(field) = value;
+            if app.metadata.input(field).ends_with([',', ';']) {
+                app.metadata.add_list_input(field);
+                app.metadata.sync_lists_to_book();
             }
             Some(Command::none())
         }
-        Message::SeriesChanged(series) => {
-            app.metadata.editing_series = series.clone();
-            if let Some(ref mut book) = app.metadata.selected_book {
-                book.series = Some(series);
-            }
+        Message::ListInputSubmitted(field) => {
+            app.metadata.add_list_input(field);
+            app.metadata.sync_lists_to_book();
             Some(Command::none())
         }
-        Message::NarratorChanged(narrator) => {
-            app.metadata.editing_narrator = narrator.clone();
-            if let Some(ref mut book) = app.metadata.selected_book {
-                book.narrator = Some(narrator);
-            }
+        Message::ListItemRemoved(field, index) => {
+            app.metadata.remove_list_item(field, index);
+            app.metadata.sync_lists_to_book();
+            Some(Command::none())
+        }
+        Message::SeriesNameInputChanged(name) => {
+            app.metadata.series_name_input = name;
+            Some(Command::none())
+        }
+        Message::SeriesNumberInputChanged(number) => {
+            app.metadata.series_number_input = number;
+            Some(Command::none())
+        }
+        Message::SeriesAdded => {
+            app.metadata.add_series_input();
+            app.metadata.sync_lists_to_book();
+            Some(Command::none())
+        }
+        Message::SeriesRemoved(index) => {
+            app.metadata.remove_series(index);
+            app.metadata.sync_lists_to_book();
             Some(Command::none())
         }
         Message::DescriptionAction(action) => {
@@ -47,13 +63,6 @@ pub fn handle_metadata(app: &mut Lectern, message: Message) -> Option<Command<Me
             }
             Some(Command::none())
         }
-        Message::SeriesNumberChanged(num) => {
-            app.metadata.editing_series_number = num.clone();
-            if let Some(ref mut book) = app.metadata.selected_book {
-                book.series_number = Some(num);
-            }
-            Some(Command::none())
-        }
         Message::IsbnChanged(isbn) => {
             app.metadata.editing_isbn = isbn.clone();
             if let Some(ref mut book) = app.metadata.selected_book {
@@ -86,20 +95,6 @@ pub fn handle_metadata(app: &mut Lectern, message: Message) -> Option<Command<Me
             }
             Some(Command::none())
         }
-        Message::GenreChanged(genre) => {
-            app.metadata.editing_genre = genre.clone();
-            if let Some(ref mut book) = app.metadata.selected_book {
-                book.genre = Some(genre);
-            }
-            Some(Command::none())
-        }
-        Message::TagsChanged(tags) => {
-            app.metadata.editing_tags = tags.clone();
-            if let Some(ref mut book) = app.metadata.selected_book {
-                book.tags = Some(tags);
-            }
-            Some(Command::none())
-        }
         Message::LanguageChanged(lang) => {
             app.metadata.editing_language = lang.clone();
             if let Some(ref mut book) = app.metadata.selected_book {
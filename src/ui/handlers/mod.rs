@@ -6,6 +6,7 @@ pub mod file;
 pub mod settings;
 pub mod convert;
 pub mod navigation;
+pub mod merge;
 
 pub use search::handle_search;
 pub use metadata::handle_metadata;
@@ -15,3 +16,4 @@ pub use file::handle_file;
 pub use settings::handle_settings;
 pub use convert::handle_convert;
 pub use navigation::handle_navigation;
+pub use merge::handle_merge;
@@ -1,6 +1,7 @@
 use crate::ui::{Lectern, Message};
 use crate::ui::state::MetadataProvider;
 use crate::services::AudioService;
+use crate::services::matching::{rank_results, LocalMatchContext};
 use crate::ui::cover_search::{download_images_parallel_threaded, download_image};
 use crate::ui::views::ViewMode;
 use iced::Command;
@@ -25,7 +26,7 @@ pub fn handle_search(app: &mut Lectern, message: Message) -> Option<Command<Mess
                 let page_results = &app.search.results[start_idx..end_idx];
                 
                 let urls_to_download: Vec<String> = page_results.iter()
-                    .filter_map(|book| book.cover_url.as_ref())
+                    .filter_map(|r| r.book.cover_url.as_ref())
                     .filter(|url| (url.starts_with("http://") || url.starts_with("https://")) 
                         && !app.search.result_covers.contains_key(*url))
                     .cloned()
@@ -72,7 +73,7 @@ pub fn handle_search(app: &mut Lectern, message: Message) -> Option<Command<Mess
                 let page_results = &app.search.results[start_idx..end_idx];
                 
                 let urls_to_download: Vec<String> = page_results.iter()
-                    .filter_map(|book| book.cover_url.as_ref())
+                    .filter_map(|r| r.book.cover_url.as_ref())
                     .filter(|url| (url.starts_with("http://") || url.starts_with("https://")) 
                         && !app.search.result_covers.contains_key(*url))
                     .cloned()
@@ -155,13 +156,20 @@ pub fn handle_search(app: &mut Lectern, message: Message) -> Option<Command<Mess
                         Ok(books) => println!("[DEBUG] Search returned {} results", books.len()),
                         Err(e) => println!("[DEBUG] Search error: {}", e),
                     }
-                    result
+                    // "Not found" is an empty result list; only unreachable providers are errors
+                    match result {
+                        Err(e) if e.is_not_found() => Ok(Vec::new()),
+                        other => other,
+                    }
                 },
                 Message::SearchCompleted,
             ))
         }
         Message::SearchCompleted(Ok(results)) => {
             app.search.is_searching = false;
+            let context = local_match_context(app);
+            let results = rank_results(results, &context);
+            println!("[DEBUG] Ranked {} results (top score: {:?})", results.len(), results.first().map(|r| r.score));
             app.search.results = results.clone();
             app.view_mode = ViewMode::Search;
             app.search.current_page = 0; // Reset to first page
@@ -173,7 +181,7 @@ pub fn handle_search(app: &mut Lectern, message: Message) -> Option<Command<Mess
             
             // Collect cover URLs for current page only
             let urls_to_download: Vec<String> = page_results.iter()
-                .filter_map(|book| book.cover_url.as_ref())
+                .filter_map(|r| r.book.cover_url.as_ref())
                 .filter(|url| (url.starts_with("http://") || url.starts_with("https://")) 
                     && !app.search.result_covers.contains_key(*url))
                 .cloned()
@@ -222,85 +230,31 @@ pub fn handle_search(app: &mut Lectern, message: Message) -> Option<Command<Mess
         }
         Message::SearchCompleted(Err(e)) => {
             app.search.is_searching = false;
-            app.search.error = Some(format!("Search failed: {}", e));
             println!("[ERROR] Search failed: {}", e);
+            app.search.error = Some(e);
             Some(Command::none())
         }
         Message::SelectBook(index) => {
-            if let Some(book) = app.search.results.get(index).cloned() {
-                println!("[DEBUG] SelectBook - Populating fields from book: '{}' by '{}'", book.title, book.author);
-                println!("[DEBUG] SelectBook - Book fields: subtitle={:?}, series={:?}, series_number={:?}, narrator={:?}, description={:?}, isbn={:?}, publisher={:?}, publish_year={:?}, genre={:?}, language={:?}, explicit={:?}, abridged={:?}",
-                    book.subtitle, book.series, book.series_number, book.narrator, 
+            if let Some(book) = app.search.results.get(index).map(|r| r.book.clone()) {
+                println!("[DEBUG] SelectBook - Populating fields from book: '{}' by '{}'", book.title, book.author_display());
+                println!("[DEBUG] SelectBook - Book fields: subtitle={:?}, series={:?}, narrators={:?}, description={:?}, isbn={:?}, publisher={:?}, publish_year={:?}, genres={:?}, language={:?}, explicit={:?}, abridged={:?}",
+                    book.subtitle, book.series, book.narrators, 
                     book.description.as_ref().map(|d| if d.len() > 50 { format!("{}...", &d[..50]) } else { d.clone() }),
-                    book.isbn, book.publisher, book.publish_year, book.genre, book.language, book.explicit, book.abridged);
+                    book.isbn, book.publisher, book.publish_year, book.genres, book.language, book.explicit, book.abridged);
                 
-                app.metadata.selected_book = Some(book.clone());
-                app.metadata.editing_title = book.title;
-                app.metadata.editing_subtitle = book.subtitle.unwrap_or_default();
-                app.metadata.editing_author = book.author;
-                app.metadata.editing_series = book.series.unwrap_or_default();
-                app.metadata.editing_series_number = book.series_number.unwrap_or_default();
-                app.metadata.editing_narrator = book.narrator.unwrap_or_default();
-                app.metadata.editing_description = book.description.unwrap_or_default();
-                app.metadata.editing_description_content = iced::widget::text_editor::Content::with_text(&app.metadata.editing_description);
-                app.metadata.editing_isbn = book.isbn.unwrap_or_default();
-                app.metadata.editing_asin = book.asin.unwrap_or_default();
-                app.metadata.editing_publisher = book.publisher.unwrap_or_default();
-                app.metadata.editing_publish_year = book.publish_year.unwrap_or_default();
-                app.metadata.editing_genre = book.genre.unwrap_or_default();
-                app.metadata.editing_tags = book.tags.unwrap_or_default();
-                app.metadata.editing_language = book.language.unwrap_or_default();
-                app.metadata.editing_explicit = book.explicit.unwrap_or(false);
-                app.metadata.editing_abridged = book.abridged.unwrap_or(false);
+                app.metadata.load_book(book.clone());
                 
-                println!("[DEBUG] SelectBook - Populated editing fields: subtitle='{}', series='{}', narrator='{}', isbn='{}', publisher='{}', year='{}', genre='{}', language='{}'",
-                    app.metadata.editing_subtitle, app.metadata.editing_series, app.metadata.editing_narrator, 
+                println!("[DEBUG] SelectBook - Populated editing fields: subtitle='{}', series={:?}, narrators={:?}, isbn='{}', publisher='{}', year='{}', genres={:?}, language='{}'",
+                    app.metadata.editing_subtitle, app.metadata.editing_series, app.metadata.editing_narrators, 
                     app.metadata.editing_isbn, app.metadata.editing_publisher, app.metadata.editing_publish_year, 
-                    app.metadata.editing_genre, app.metadata.editing_language);
+                    app.metadata.editing_genres, app.metadata.editing_language);
                 // Initialize cover image path
                 app.cover.cover_image_path = book.cover_url.clone();
                 app.view_mode = ViewMode::Metadata;
                 app.search.results.clear(); // Close search view
                 // If cover is a URL, check if we already have it cached
                 if let Some(ref cover_url) = book.cover_url {
-                    if cover_url.starts_with("http://") || cover_url.starts_with("https://") {
-                        // First check if it's already in the main cover cache
-                        if app.cover.cover_image_url_cached.as_ref() == Some(cover_url) {
-                            println!("[DEBUG] Cover image already in main cache");
-                        } else if let Some(handle) = app.search.result_covers.get(cover_url).cloned() {
-                            // If it's in the search result cache, promote it to main cache
-                            println!("[DEBUG] Promoting cover handle from search cache to main cache");
-                            app.cover.cover_image_handle = Some(handle);
-                            app.cover.cover_image_url_cached = Some(cover_url.clone());
-                            // We don't have the raw data here easily but the handle is enough for display
-                        } else {
-                            // URL changed or not cached - download it
-                            app.cover.cover_image_data = None; // Clear old cached data
-                            app.cover.cover_image_handle = None;
-                            app.cover.cover_image_url_cached = None;
-                            app.cover.is_downloading_cover = true;
-                            let url_clone = cover_url.clone();
-                            return Some(Command::perform(
-                                async move {
-                                    match download_image(&url_clone).await {
-                                        Ok((url, data)) => {
-                                            if let Ok(img) = ::image::load_from_memory(&data) {
-                                                let rgba = img.to_rgba8();
-                                                let (width, height) = rgba.dimensions();
-                                                let pixels: Vec<u8> = rgba.into_raw();
-                                                let handle = iced::widget::image::Handle::from_pixels(width, height, pixels);
-                                                Ok((url, data, handle))
-                                            } else {
-                                                Err("Failed to decode image".to_string())
-                                            }
-                                        },
-                                        Err(e) => Err(e),
-                                    }
-                                },
-                                Message::CoverImageDownloaded,
-                            ));
-                        }
-                    }
+                    return Some(load_cover_url(app, cover_url));
                 }
             }
             Some(Command::none())
@@ -328,3 +282,70 @@ pub fn handle_search(app: &mut Lectern, message: Message) -> Option<Command<Mess
         _ => None, // This handler doesn't handle this message
     }
 }
+
+/// Collect what we already know about the local book for scoring search results.
+fn local_match_context(app: &Lectern) -> LocalMatchContext {
+    let non_empty = |s: &str| {
+        let s = s.trim();
+        if s.is_empty() { None } else { Some(s.to_string()) }
+    };
+    let selected = app.metadata.selected_book.as_ref();
+    LocalMatchContext {
+        title: app.metadata.editing_title.clone(),
+        query: app.search.query.clone(),
+        author: if app.search.author.trim().is_empty() {
+            app.metadata.editing_authors.join(", ")
+        } else {
+            app.search.author.clone()
+        },
+        asin: non_empty(&app.metadata.editing_asin).or_else(|| selected.and_then(|b| b.asin.clone())),
+        isbn: non_empty(&app.metadata.editing_isbn).or_else(|| selected.and_then(|b| b.isbn.clone())),
+        duration_ms: app.chapters.book_duration_ms,
+        narrator: crate::models::metadata::join_names(&app.metadata.editing_narrators),
+        series: app.metadata.editing_series.first().map(|s| s.name.clone()),
+    }
+}
+
+/// Show the cover at `cover_url`: reuse a cached handle (main or search-result cache) or download it.
+pub(crate) fn load_cover_url(app: &mut Lectern, cover_url: &str) -> Command<Message> {
+    if !(cover_url.starts_with("http://") || cover_url.starts_with("https://")) {
+        return Command::none();
+    }
+    // First check if it's already in the main cover cache
+    if app.cover.cover_image_url_cached.as_deref() == Some(cover_url) {
+        println!("[DEBUG] Cover image already in main cache");
+    } else if let Some(handle) = app.search.result_covers.get(cover_url).cloned() {
+        // If it's in the search result cache, promote it to main cache
+        println!("[DEBUG] Promoting cover handle from search cache to main cache");
+        app.cover.cover_image_handle = Some(handle);
+        app.cover.cover_image_url_cached = Some(cover_url.to_string());
+        // We don't have the raw data here easily but the handle is enough for display
+    } else {
+        // URL changed or not cached - download it
+        app.cover.cover_image_data = None; // Clear old cached data
+        app.cover.cover_image_handle = None;
+        app.cover.cover_image_url_cached = None;
+        app.cover.is_downloading_cover = true;
+        let url_clone = cover_url.to_string();
+        return Command::perform(
+            async move {
+                match download_image(&url_clone).await {
+                    Ok((url, data)) => {
+                        if let Ok(img) = ::image::load_from_memory(&data) {
+                            let rgba = img.to_rgba8();
+                            let (width, height) = rgba.dimensions();
+                            let pixels: Vec<u8> = rgba.into_raw();
+                            let handle = iced::widget::image::Handle::from_pixels(width, height, pixels);
+                            Ok((url, data, handle))
+                        } else {
+                            Err("Failed to decode image".to_string())
+                        }
+                    },
+                    Err(e) => Err(e),
+                }
+            },
+            Message::CoverImageDownloaded,
+        );
+    }
+    Command::none()
+}
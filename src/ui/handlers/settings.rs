@@ -1,6 +1,10 @@
 use crate::ui::theme::build_theme;
 use crate::ui::theme::palette_for;
 use crate::ui::theme_settings;
+use crate::ui::merge_settings;
+use crate::ui::cache_settings;
+use crate::services::http_cache;
+use crate::utils::format::format_size;
 use crate::ui::{Lectern, Message};
 use iced::Command;
 
@@ -14,6 +18,16 @@ fn save_theme_settings(app: &Lectern) {
     theme_settings::save(app.theme_id, app.dark_mode, app.accent_override);
 }
 
+fn apply_cache_policy(app: &Lectern) {
+    http_cache::set_policy(app.cache_policy);
+    cache_settings::save(&app.cache_policy);
+}
+
+fn refresh_cache_status(app: &mut Lectern) {
+    let (entries, bytes) = http_cache::stats();
+    app.cache_status = Some(format!("{} cached responses, {}", entries, format_size(bytes)));
+}
+
 pub fn handle_settings(app: &mut Lectern, message: Message) -> Option<Command<Message>> {
     match message {
         Message::ThemeIdChanged(theme_id) => {
@@ -105,7 +119,62 @@ pub fn handle_settings(app: &mut Lectern, message: Message) -> Option<Command<Me
             app.audiobookshelf_library_id = library_id;
             Some(Command::none())
         }
+        Message::MergePreferenceChanged(field, source) => {
+            app.merge_preferences.insert(field, source);
+            merge_settings::save(&app.merge_preferences);
+            Some(Command::none())
+        }
+        Message::CacheEnabledToggled(enabled) => {
+            app.cache_policy.enabled = enabled;
+            apply_cache_policy(app);
+            Some(Command::none())
+        }
+        Message::CacheOfflineToggled(offline) => {
+            app.cache_policy.offline = offline;
+            apply_cache_policy(app);
+            Some(Command::none())
+        }
+        Message::CacheTtlDaysChanged(input) => {
+            if let Ok(days) = input.trim().parse::<u64>() {
+                app.cache_policy.ttl_secs = days.saturating_mul(86_400);
+                apply_cache_policy(app);
+            }
+            app.cache_ttl_input = input;
+            Some(Command::none())
+        }
+        Message::CacheMaxSizeChanged(input) => {
+            if let Ok(mb) = input.trim().parse::<u64>() {
+                app.cache_policy.max_bytes = mb.saturating_mul(1024 * 1024);
+                apply_cache_policy(app);
+            }
+            app.cache_size_input = input;
+            Some(Command::none())
+        }
+        Message::ClearCache => {
+            Some(Command::perform(
+                async move {
+                    tokio::task::spawn_blocking(|| http_cache::clear().map_err(|e| e.to_string()))
+                        .await
+                        .unwrap_or_else(|e| Err(format!("Clear cache task failed: {}", e)))
+                },
+                Message::CacheCleared,
+            ))
+        }
+        Message::CacheCleared(result) => {
+            match result {
+                Ok(bytes) => {
+                    println!("[DEBUG] Cleared HTTP cache ({})", format_size(bytes));
+                    refresh_cache_status(app);
+                }
+                Err(e) => {
+                    println!("[ERROR] {}", e);
+                    app.cache_status = Some(e);
+                }
+            }
+            Some(Command::none())
+        }
         Message::SwitchToSettings => {
+            refresh_cache_status(app);
             app.view_mode = crate::ui::views::ViewMode::Settings;
             Some(Command::none())
         }
@@ -141,7 +141,7 @@ pub fn find_metadata_or_chapter_files(dir_path: &str) -> Vec<(String, String)> {
             if p.is_file() {
                 if let Some(ext) = p.extension() {
                     let ext_lower = ext.to_string_lossy().to_lowercase();
-                    if matches!(ext_lower.as_str(), "nfo" | "sfv" | "cue") {
+                    if matches!(ext_lower.as_str(), "nfo" | "sfv" | "cue" | "opf") {
                         if let (Some(name_os), Some(path_str)) = (p.file_name(), p.to_str()) {
                             if let Some(n) = name_os.to_str() {
                                 if !found.iter().any(|(_, fp)| fp == path_str) {
@@ -217,28 +217,27 @@ fn expand_series_number(template: &str, series_number_s: &str) -> String {
 /// Placeholders: {Author}, {Title}, {Series}, {SeriesNumber}, {Year}, {Genre}, {ASIN}, {Language}, {Tags}
 /// Optional suffix on SeriesNumber: {SeriesNumber-} → "4-", {SeriesNumber.} → "4.", {SeriesNumber } → "4 "
 /// (suffix only when value is non-empty). PathBuf used for correct separators.
-pub fn apply_media_template(
-    template: &str,
-    lib_path: &str,
-    title: &str,
-    author: &str,
-    series: &str,
-    series_number: &str,
-    year: &str,
-    genre: &str,
-    asin: &str,
-    language: &str,
-    tags: &str,
-) -> String {
-    let author_s = sanitize_path_component(if author.is_empty() { "Unknown Author" } else { author });
+/// List fields are joined with ", "; {Series}/{SeriesNumber} use the first series.
+/// {Part} is left in place; conversion expands it per part (see conversion::part_output_path).
+pub fn apply_media_template(template: &str, lib_path: &str, book: &crate::models::BookMetadata) -> String {
+    let author = book.author_display();
+    let title = book.title.trim();
+    let series = book.series_name().unwrap_or_default();
+    let series_number = book.series_number().unwrap_or_default();
+    let year = book.publish_year.clone().unwrap_or_default();
+    let genre = book.genre_display().unwrap_or_default();
+    let asin = book.asin.clone().unwrap_or_default();
+    let language = book.language.clone().unwrap_or_default();
+    let tags = book.tags_display().unwrap_or_default();
+    let author_s = sanitize_path_component(if author.is_empty() { "Unknown Author" } else { &author });
     let title_s = sanitize_path_component(if title.is_empty() { "Unknown Title" } else { title });
-    let series_s = sanitize_path_component(series);
-    let series_number_s = sanitize_path_component(series_number);
-    let year_s = sanitize_path_component(year);
-    let genre_s = sanitize_path_component(genre);
-    let asin_s = sanitize_path_component(asin);
-    let language_s = sanitize_path_component(language);
-    let tags_s = sanitize_path_component(tags);
+    let series_s = sanitize_path_component(&series);
+    let series_number_s = sanitize_path_component(&series_number);
+    let year_s = sanitize_path_component(&year);
+    let genre_s = sanitize_path_component(&genre);
+    let asin_s = sanitize_path_component(&asin);
+    let language_s = sanitize_path_component(&language);
+    let tags_s = sanitize_path_component(&tags);
 
     let expanded = expand_series_number(template, &series_number_s)
         .replace("{Author}", &author_s)
@@ -0,0 +1,59 @@
+//! Persist per-field source preferences for the metadata merge view.
+//! Uses XDG config on Linux: ~/.config/lectern/merge_preferences.json
+use crate::config;
+use crate::ui::state::MergeField;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MergePreferencesFile {
+    /// Field key (e.g. "narrator") -> source name (e.g. "Audible.com" or "Current").
+    #[serde(default)]
+    fields: BTreeMap<String, String>,
+}
+
+fn config_path() -> Option<std::path::PathBuf> {
+    config::config_file("merge_preferences.json")
+}
+
+/// Built-in preferences, used for fields missing from the config file.
+pub fn defaults() -> HashMap<MergeField, String> {
+    MergeField::ALL
+        .iter()
+        .map(|f| (*f, f.default_source().to_string()))
+        .collect()
+}
+
+/// Load per-field preferences; fields not in the file keep their built-in default.
+pub fn load() -> HashMap<MergeField, String> {
+    let mut prefs = defaults();
+    let file: Option<MergePreferencesFile> = config_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|data| serde_json::from_str(&data).ok());
+    if let Some(file) = file {
+        for (key, source) in file.fields {
+            if let Some(field) = MergeField::from_key(&key) {
+                prefs.insert(field, source);
+            }
+        }
+    }
+    prefs
+}
+
+/// Save per-field preferences to config file.
+pub fn save(prefs: &HashMap<MergeField, String>) {
+    let path = match config_path() {
+        Some(p) => p,
+        None => return,
+    };
+    let file = MergePreferencesFile {
+        fields: prefs
+            .iter()
+            .map(|(f, s)| (f.key().to_string(), s.clone()))
+            .collect(),
+    };
+    if let Ok(json) = serde_json::to_string_pretty(&file) {
+        let _ = fs::write(&path, json);
+    }
+}
@@ -2,6 +2,9 @@ pub mod colors;
 pub mod icons;
 pub mod theme;
 pub mod theme_settings;
+pub mod merge_settings;
+pub mod cache_settings;
+pub mod preset_settings;
 pub mod views;
 pub mod helpers;
 pub mod cover_search;
@@ -17,10 +20,15 @@ use cover_search::CoverResult;
 
 
 use crate::models::{Chapter, BookMetadata};
+use crate::services::matching::ProviderResult;
+use crate::error::LecternError;
 use std::time::Instant;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use state::{SearchState, MetadataState, CoverState, ChapterState, ChapterRegion, FileState, MetadataProvider};
+use crate::services::cover_edit::{CoverCrop, EditedCover};
+use crate::services::embedded_cover::EmbeddedCover;
+use crate::utils::chapter_titles::{NumberWords, TitleCase, TitleNumbering};
+use state::{SearchState, MetadataState, CoverState, CropSide, ChapterState, ChapterRegion, TitleRuleScope, FileState, MetadataProvider, ListField, MergeState, MergeField};
 
 // Store process handle separately since Child is not Clone
 #[derive(Debug)]
@@ -46,6 +54,17 @@ pub struct ChapterPlaybackState {
     pub was_manually_stopped: bool, // Track if user manually stopped playback
 }
 
+/// Editable normalization parameter in the Convert view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormalizationParam {
+    SpeechnormExpansion,
+    SpeechnormRaise,
+    SpeechnormPeak,
+    LoudnormTarget,
+    LoudnormTruePeak,
+    LoudnormRange,
+}
+
 use iced::widget::{column, container, text_editor};
 use iced::{Application, Command, Element, Length, Theme, Subscription};
 use iced::time;
@@ -62,8 +81,16 @@ pub enum Message {
     PerformSearch,
     NextPage,
     PreviousPage,
-    SearchCompleted(Result<Vec<BookMetadata>, String>),
+    SearchCompleted(Result<Vec<ProviderResult>, LecternError>),
     SelectBook(usize), // Index into results
+    // Field-by-field merge
+    OpenMerge(usize), // Index into results; compares current book with that result's providers
+    MergeFieldSourceSelected(MergeField, usize), // Field, source column
+    MergeApply,
+    MergeCancel,
+    /// Parse the metadata files (OPF, NFO, JSON, description) in the source folder and review them in the merge view.
+    ImportMetadataFiles,
+    MetadataFilesImported(Vec<(String, Result<BookMetadata, String>)>), // (file name, parsed metadata)
     SwitchToSearch,
     SwitchToMetadata,
     SwitchToCover,
@@ -71,20 +98,22 @@ pub enum Message {
     SwitchToConvert,
     TitleChanged(String),
     SubtitleChanged(String),
-    AuthorChanged(String),
+    /// Chip lists (authors, narrators, genres, tags): typed text, Enter/Add, remove a chip.
+    ListInputChanged(ListField, String),
+    ListInputSubmitted(ListField),
+    ListItemRemoved(ListField, usize),
     /// Keyboard tab: focus next/previous on metadata tab (from subscription).
     MetadataFocusNext,
     MetadataFocusPrevious,
-    SeriesChanged(String),
-    SeriesNumberChanged(String),
-    NarratorChanged(String),
+    SeriesNameInputChanged(String),
+    SeriesNumberInputChanged(String),
+    SeriesAdded,
+    SeriesRemoved(usize),
     DescriptionAction(text_editor::Action),
     IsbnChanged(String),
     AsinChanged(String),
     PublisherChanged(String),
     PublishYearChanged(String),
-    GenreChanged(String),
-    TagsChanged(String),
     LanguageChanged(String),
     ExplicitToggled(bool),
     AbridgedToggled(bool),
@@ -102,7 +131,20 @@ pub enum Message {
     SearchCover,
     CoverSearchCompleted(Result<Vec<CoverResult>, String>),
     SelectCover(usize), // Index into cover search results
+    BrowseCoverFolder,
+    CoverFolderSelected(Option<String>), // Folder to scan for cover images
+    CoverPageUrlChanged(String),
+    SearchCoverPage, // Scan the page URL for images
     CoverUrlChanged(String), // Manual URL entry
+    EmbeddedCoversFound(String, Vec<EmbeddedCover>), // Selected book path, distinct embedded pictures
+    // Cover editor (crop, pad, resize, JPEG quality) with preview of the embedded image
+    CoverEditorToggled,
+    CoverCropChanged(CoverCrop),
+    CoverCropBoxChanged(CropSide, f32), // Side, fraction of the image (0.0-1.0)
+    CoverMaxSizeChanged(u32),
+    CoverJpegQualityChanged(u8),
+    RenderCoverPreview,
+    CoverPreviewRendered(u64, String, Result<EditedCover, String>), // Generation, source path/URL, result
     DownloadCoverImage(String), // URL to download
     CoverImageDownloaded(Result<(String, Vec<u8>, iced::widget::image::Handle), String>), // URL, raw data, handle
     SearchCoverImageDownloaded(Result<(String, Vec<u8>, iced::widget::image::Handle), String>), // URL, raw data, handle
@@ -118,7 +160,10 @@ pub enum Message {
     ChapterRemoveAll,
     ChapterShiftTimes(i64), // Shift all unlocked chapters by seconds
     ChapterLookup, // Lookup chapters from provider
-    ChapterLookupCompleted(u64, Result<Vec<Chapter>, String>),
+    ChapterFindEditions, // Search Audible by title/author for editions to take chapters from
+    ChapterEditionsFound(u64, Result<Vec<crate::models::ChapterEdition>, String>), // Generation, editions
+    ChapterEditionChosen(usize), // Index into edition candidates; fetches its chapters
+    ChapterLookupCompleted(u64, Result<crate::models::ChapterLookup, String>),
     ChapterLookupApply, // Apply looked-up chapters (replace current)
     MapChapterTitlesOnly, // Apply looked-up titles to existing chapters by index, keep timestamps
     ChapterLookupCancel, // Cancel lookup results, back to chapter tab
@@ -142,6 +187,19 @@ pub enum Message {
     ChapterToggleAsinInput, // Toggle ASIN input area
     ShiftModifierChanged(bool), // Shift key pressed (true) or released (false)
     ChapterRegionChanged(ChapterRegion),
+    // Chapter title cleanup rules (previewed, then applied to the chapters in scope)
+    ChapterTitleRulesToggled,
+    TitleRuleStripPrefixToggled(bool),
+    TitleRuleFindChanged(String),
+    TitleRuleReplaceChanged(String),
+    TitleRuleNumberingChanged(TitleNumbering),
+    TitleRuleTemplateChanged(String),
+    TitleRuleNumberWordsChanged(NumberWords),
+    TitleRuleCaseChanged(TitleCase),
+    TitleRuleScopeChanged(TitleRuleScope),
+    TitleRuleFromChanged(String),
+    TitleRuleToChanged(String),
+    ApplyTitleRules,
     ChapterRemoveAudibleToggled(bool),
     MapChaptersFromFiles, // Map chapters from audio files (one file = one chapter)
     MapChaptersFromFilesCompleted(u64, Result<Vec<Chapter>, String>),
@@ -158,17 +216,43 @@ pub enum Message {
     AudiobookshelfHostChanged(String),
     AudiobookshelfTokenChanged(String),
     AudiobookshelfLibraryIdChanged(String),
+    MergePreferenceChanged(MergeField, String), // Default source for a field in the merge view
+    // HTTP cache
+    CacheEnabledToggled(bool),
+    CacheOfflineToggled(bool),
+    CacheTtlDaysChanged(String),
+    CacheMaxSizeChanged(String), // MB
+    ClearCache,
+    CacheCleared(Result<u64, String>), // Bytes freed
     // Provider selection
     MetadataProviderChanged(MetadataProvider), // Provider name for metadata search
     // Convert messages
     StartConversion,
     BrowseOutputPath,
     OutputPathSelected(Option<String>),
-    ConversionCompleted(Result<(String, u64, u64), String>),
-    ConversionNormalizeVolumeToggled(bool),
+    ConversionCompleted(Result<(crate::services::conversion::ConversionReport, u64, u64), LecternError>),
+    ConversionNormalizationChanged(crate::services::conversion::NormalizationMode),
+    ConversionNormalizationParamChanged(NormalizationParam, String),
+    ConversionSplitByChanged(crate::services::conversion::SplitBy),
+    ConversionEncodeStrategyChanged(crate::services::conversion::EncodeStrategy),
+    ConversionFormatChanged(crate::services::conversion::OutputFormat),
+    ConversionSplitLimitChanged(String), // Hours or MB depending on SplitBy
+    ConversionTrimModeChanged(crate::services::conversion::TrimMode),
+    ConversionTrimStartChanged(String), // Seconds
+    ConversionTrimEndChanged(String),   // Seconds
+    ConversionTrimSilenceToggled(bool),
+    ConversionWriteSidecarsToggled(bool),
     ConversionBitrateChanged(String),
     ConversionCodecChanged(String),
+    ConversionSampleRateChanged(String),
+    ConversionAacProfileChanged(crate::services::conversion::AacProfile),
+    ConversionPresetSelected(String),
+    ConversionPresetNameChanged(String),
+    SaveConversionPreset,
     ConversionChannelsChanged(String),
+    CheckInputs,
+    InputCheckCompleted(Vec<crate::services::input_check::InputCheck>),
+    InputCheckSkipToggled(String, bool),
     // Theme / appearance
     ThemeIdChanged(crate::ui::theme::ThemeId),
     DarkModeToggled(bool),
@@ -188,6 +272,7 @@ pub struct Lectern {
     pub chapter_playback_state: Option<ChapterPlaybackState>,
     pub chapter_playback_process: Option<Arc<Mutex<ChapterPlaybackProcess>>>,
     pub file: FileState,
+    pub merge: MergeState,
     
     // Current view mode
     pub view_mode: ViewMode,
@@ -198,17 +283,58 @@ pub struct Lectern {
     pub audiobookshelf_host: String,
     pub audiobookshelf_token: String,
     pub audiobookshelf_library_id: String,
+    /// Default source per field in the merge view (persisted in merge_preferences.json).
+    pub merge_preferences: std::collections::HashMap<MergeField, String>,
+    /// Response/cover cache behaviour (persisted in cache_settings.json, mirrored into services::http_cache).
+    pub cache_policy: crate::services::http_cache::CachePolicy,
+    pub cache_ttl_input: String, // Days
+    pub cache_size_input: String, // MB
+    /// Entry count / size line shown in Settings; refreshed when Settings opens or the cache is cleared.
+    pub cache_status: Option<String>,
     
     // Convert state
     pub output_path: Option<String>,
     pub is_converting: bool,
-    pub conversion_error: Option<String>,
+    pub conversion_error: Option<LecternError>,
     pub source_size: u64,
     pub output_size: u64,
-    pub conversion_normalize_volume: bool,
+    pub conversion_normalization: crate::services::conversion::NormalizationMode,
+    // Normalization parameter inputs (parsed when the conversion starts)
+    pub speechnorm_expansion: String,
+    pub speechnorm_raise: String,
+    pub speechnorm_peak: String,
+    pub loudnorm_target: String, // LUFS
+    pub loudnorm_true_peak: String, // dBTP
+    pub loudnorm_range: String, // LU
+    pub conversion_encode_strategy: crate::services::conversion::EncodeStrategy,
+    pub conversion_format: crate::services::conversion::OutputFormat,
+    pub conversion_split_by: crate::services::conversion::SplitBy,
+    pub conversion_split_limit: String,
+    pub conversion_trim_mode: crate::services::conversion::TrimMode,
+    pub conversion_trim_start: String,
+    pub conversion_trim_end: String,
+    pub conversion_trim_silence: bool,
+    pub conversion_write_sidecars: bool,
+    /// Result of the last successful conversion (normalization and loudness measurement).
+    pub conversion_report: Option<crate::services::conversion::ConversionReport>,
     pub conversion_bitrate: String, // "auto", "64k", "96k", "128k", "192k"
     pub conversion_codec: String, // "aac", "copy", "opus"
     pub conversion_channels: String, // "auto", "1", "2"
+    pub conversion_sample_rate: String, // "auto", "22050", "24000", "32000", "44100", "48000"
+    pub conversion_aac_profile: crate::services::conversion::AacProfile,
+    /// Whether ffmpeg has libfdk_aac (HE-AAC); probed when an HE profile is first picked.
+    pub fdk_aac_available: Option<bool>,
+    pub conversion_presets: Vec<crate::ui::preset_settings::ConversionPreset>,
+    pub conversion_preset: Option<String>, // Selected preset; cleared when a setting is changed by hand
+    pub conversion_preset_name: String, // Name for "Save as preset"
+    pub conversion_preset_status: Option<String>,
+    /// Result of the last input health check; cleared when the input changes.
+    pub input_checks: Option<Vec<crate::services::input_check::InputCheck>>,
+    pub is_checking_inputs: bool,
+    /// Inputs left out of the conversion (defaults to the files that failed the check).
+    pub input_check_skipped: std::collections::HashSet<String>,
+    /// Start the conversion once the automatic pre-conversion check comes back clean.
+    pub convert_after_check: bool,
 
     // Theme state (cache lives here so we don't re-allocate on every view())
     pub theme_id: crate::ui::theme::ThemeId,
@@ -235,6 +361,7 @@ impl Default for Lectern {
             chapter_playback_state: None,
             chapter_playback_process: None,
             file: FileState::default(),
+            merge: MergeState::default(),
             
             // Current view mode
             view_mode: ViewMode::Metadata,
@@ -245,6 +372,11 @@ impl Default for Lectern {
             audiobookshelf_host: String::new(),
             audiobookshelf_token: String::new(),
             audiobookshelf_library_id: String::new(),
+            merge_preferences: crate::ui::merge_settings::defaults(),
+            cache_policy: crate::services::http_cache::CachePolicy::default(),
+            cache_ttl_input: (crate::services::http_cache::CachePolicy::DEFAULT.ttl_secs / 86_400).to_string(),
+            cache_size_input: (crate::services::http_cache::CachePolicy::DEFAULT.max_bytes / (1024 * 1024)).to_string(),
+            cache_status: None,
             
             // Convert state
             output_path: None,
@@ -252,10 +384,37 @@ impl Default for Lectern {
             conversion_error: None,
             source_size: 0,
             output_size: 0,
-            conversion_normalize_volume: false,
+            conversion_normalization: crate::services::conversion::NormalizationMode::None,
+            speechnorm_expansion: "6.5".to_string(),
+            speechnorm_raise: "0.0001".to_string(),
+            speechnorm_peak: "0.95".to_string(),
+            loudnorm_target: "-18".to_string(),
+            loudnorm_true_peak: "-1.5".to_string(),
+            loudnorm_range: "11".to_string(),
+            conversion_encode_strategy: crate::services::conversion::EncodeStrategy::SingleProcess,
+            conversion_format: crate::services::conversion::OutputFormat::M4b,
+            conversion_split_by: crate::services::conversion::SplitBy::None,
+            conversion_split_limit: String::new(),
+            conversion_trim_mode: crate::services::conversion::TrimMode::None,
+            conversion_trim_start: String::new(),
+            conversion_trim_end: String::new(),
+            conversion_trim_silence: false,
+            conversion_write_sidecars: false,
+            conversion_report: None,
             conversion_bitrate: "auto".to_string(),
             conversion_codec: "aac".to_string(),
             conversion_channels: "auto".to_string(),
+            conversion_sample_rate: "auto".to_string(),
+            conversion_aac_profile: crate::services::conversion::AacProfile::Lc,
+            fdk_aac_available: None,
+            conversion_presets: crate::ui::preset_settings::builtin(),
+            conversion_preset: None,
+            conversion_preset_name: String::new(),
+            conversion_preset_status: None,
+            input_checks: None,
+            is_checking_inputs: false,
+            input_check_skipped: std::collections::HashSet::new(),
+            convert_after_check: false,
 
             // Theme state
             theme_id: crate::ui::theme::ThemeId::default(),
@@ -324,10 +483,24 @@ impl Application for Lectern {
     type Message = Message;
     type Theme = Theme;
     type Executor = iced::executor::Default;
-    type Flags = ();
+    type Flags = crate::CliOptions;
 
-    fn new(_flags: ()) -> (Self, Command<Message>) {
+    fn new(flags: crate::CliOptions) -> (Self, Command<Message>) {
         let mut app = Self::default();
+        app.merge_preferences = crate::ui::merge_settings::load();
+        app.conversion_presets = crate::ui::preset_settings::load();
+        if let Some(ref name) = flags.preset {
+            // parse_args already rejected unknown names
+            if let Some(preset) = crate::ui::preset_settings::find(&app.conversion_presets, name).cloned() {
+                handlers::convert::apply_preset(&mut app, &preset);
+            }
+        }
+        if let Some(policy) = crate::ui::cache_settings::load() {
+            app.cache_policy = policy;
+            app.cache_ttl_input = (policy.ttl_secs / 86_400).to_string();
+            app.cache_size_input = (policy.max_bytes / (1024 * 1024)).to_string();
+        }
+        crate::services::http_cache::set_policy(app.cache_policy);
         if let Some((theme_id, dark_mode, accent_override)) = crate::ui::theme_settings::load() {
             app.theme_id = theme_id;
             app.dark_mode = dark_mode;
@@ -363,6 +536,9 @@ impl Application for Lectern {
         if let Some(cmd) = handle_search(self, message.clone()) {
             return cmd;
         }
+        if let Some(cmd) = handle_merge(self, message.clone()) {
+            return cmd;
+        }
         if let Some(cmd) = handle_metadata(self, message.clone()) {
             return cmd;
         }
@@ -413,6 +589,7 @@ impl Application for Lectern {
             ViewMode::Chapters => views::chapters::view_chapters(self),
             ViewMode::Convert => views::convert::view_convert(self),
             ViewMode::Settings => views::settings::view_settings(self),
+            ViewMode::Merge => views::merge::view_merge(self),
         };
         
         container(
@@ -0,0 +1,171 @@
+//! Named conversion presets (format, codec, bitrate, channels, sample rate, AAC profile, normalization).
+//! Built-in presets plus user presets from ~/.config/lectern/conversion_presets.json (XDG on Linux).
+use crate::config;
+use crate::services::conversion::{AacProfile, NormalizationMode, OutputFormat};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Convert view settings saved under a name. Values use the same strings as the view's
+/// pick lists ("auto", "64k", "1", ...).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversionPreset {
+    pub name: String,
+    pub format: OutputFormat,
+    pub codec: String,
+    pub bitrate: String,
+    pub channels: String,
+    #[serde(default = "auto")]
+    pub sample_rate: String,
+    #[serde(default)]
+    pub aac_profile: AacProfile,
+    pub normalization: NormalizationMode,
+    /// Speechnorm/loudnorm parameters; presets saved before they existed get the defaults.
+    #[serde(default)]
+    pub levels: NormalizationLevels,
+}
+
+/// The Convert view's normalization parameter inputs, as typed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NormalizationLevels {
+    pub speechnorm_expansion: String,
+    pub speechnorm_raise: String,
+    pub speechnorm_peak: String,
+    pub loudnorm_target: String,
+    pub loudnorm_true_peak: String,
+    pub loudnorm_range: String,
+}
+
+/// Same values as the Convert view's defaults (`Normalization::SPEECHNORM_DEFAULT` / `LOUDNORM_DEFAULT`).
+impl Default for NormalizationLevels {
+    fn default() -> Self {
+        Self {
+            speechnorm_expansion: "6.5".to_string(),
+            speechnorm_raise: "0.0001".to_string(),
+            speechnorm_peak: "0.95".to_string(),
+            loudnorm_target: "-18".to_string(),
+            loudnorm_true_peak: "-1.5".to_string(),
+            loudnorm_range: "11".to_string(),
+        }
+    }
+}
+
+fn auto() -> String {
+    "auto".to_string()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PresetsFile {
+    #[serde(default)]
+    presets: Vec<ConversionPreset>,
+}
+
+fn config_path() -> Option<std::path::PathBuf> {
+    config::config_file("conversion_presets.json")
+}
+
+/// Preset with automatic sample rate and AAC-LC.
+fn preset(name: &str, format: OutputFormat, codec: &str, bitrate: &str, channels: &str, normalization: NormalizationMode) -> ConversionPreset {
+    ConversionPreset {
+        name: name.to_string(),
+        format,
+        codec: codec.to_string(),
+        bitrate: bitrate.to_string(),
+        channels: channels.to_string(),
+        sample_rate: auto(),
+        aac_profile: AacProfile::Lc,
+        normalization,
+        levels: NormalizationLevels::default(),
+    }
+}
+
+/// Presets that always exist; a user preset with the same name replaces one.
+pub fn builtin() -> Vec<ConversionPreset> {
+    vec![
+        ConversionPreset {
+            sample_rate: "44100".to_string(),
+            aac_profile: AacProfile::HeV1,
+            ..preset("Spoken word mono 48k HE-AAC", OutputFormat::M4b, "aac", "48k", "1", NormalizationMode::SpeechNorm)
+        },
+        preset("Archive 128k stereo", OutputFormat::M4b, "aac", "128k", "2", NormalizationMode::None),
+        ConversionPreset {
+            sample_rate: "48000".to_string(),
+            ..preset("Opus 32k", OutputFormat::OggOpus, "opus", "32k", "auto", NormalizationMode::SpeechNorm)
+        },
+    ]
+}
+
+/// Presets saved by the user (empty on missing/invalid file).
+fn load_user() -> Vec<ConversionPreset> {
+    config_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|data| serde_json::from_str::<PresetsFile>(&data).ok())
+        .map(|file| file.presets)
+        .unwrap_or_default()
+}
+
+/// Built-in presets followed by user presets; user presets override built-ins by name.
+pub fn load() -> Vec<ConversionPreset> {
+    let mut presets = builtin();
+    for user in load_user() {
+        match presets.iter_mut().find(|p| p.name == user.name) {
+            Some(existing) => *existing = user,
+            None => presets.push(user),
+        }
+    }
+    presets
+}
+
+/// Find a preset by name, ignoring case.
+pub fn find<'a>(presets: &'a [ConversionPreset], name: &str) -> Option<&'a ConversionPreset> {
+    presets.iter().find(|p| p.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Add or replace a user preset and write the user presets to the config file.
+pub fn save(preset: ConversionPreset) -> Result<(), String> {
+    let path = config_path().ok_or_else(|| "No config directory".to_string())?;
+    let mut presets = load_user();
+    match presets.iter_mut().find(|p| p.name == preset.name) {
+        Some(existing) => *existing = preset,
+        None => presets.push(preset),
+    }
+    let json = serde_json::to_string_pretty(&PresetsFile { presets })
+        .map_err(|e| format!("Failed to serialize presets: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::conversion::Normalization;
+
+    #[test]
+    fn default_levels_match_the_normalization_defaults() {
+        let levels = NormalizationLevels::default();
+        let parse = |value: &str| value.parse::<f64>().expect("number");
+        assert_eq!(
+            Normalization::SpeechNorm {
+                expansion: parse(&levels.speechnorm_expansion),
+                raise: parse(&levels.speechnorm_raise),
+                peak: parse(&levels.speechnorm_peak),
+            },
+            Normalization::SPEECHNORM_DEFAULT
+        );
+        assert_eq!(
+            Normalization::Loudnorm {
+                target_i: parse(&levels.loudnorm_target),
+                true_peak: parse(&levels.loudnorm_true_peak),
+                lra: parse(&levels.loudnorm_range),
+            },
+            Normalization::LOUDNORM_DEFAULT
+        );
+    }
+
+    #[test]
+    fn presets_without_levels_load_with_defaults() {
+        let json = r#"{"name": "Old", "format": "m4b", "codec": "aac", "bitrate": "64k", "channels": "1", "normalization": "speech_norm"}"#;
+        let preset: ConversionPreset = serde_json::from_str(json).expect("legacy preset");
+        assert_eq!(preset.levels, NormalizationLevels::default());
+        assert_eq!(preset.sample_rate, "auto");
+    }
+}
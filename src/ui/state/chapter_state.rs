@@ -1,4 +1,5 @@
 use crate::models::Chapter;
+use crate::utils::chapter_titles::TitleRules;
 use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -9,17 +10,30 @@ pub enum ChapterRegion {
     AU,
     FR,
     DE,
+    JP,
+    IT,
+    IN,
+    ES,
 }
 
 impl ChapterRegion {
-    pub const ALL: [ChapterRegion; 6] = [
+    pub const ALL: [ChapterRegion; 10] = [
         ChapterRegion::US,
         ChapterRegion::CA,
         ChapterRegion::UK,
         ChapterRegion::AU,
         ChapterRegion::FR,
         ChapterRegion::DE,
+        ChapterRegion::JP,
+        ChapterRegion::IT,
+        ChapterRegion::IN,
+        ChapterRegion::ES,
     ];
+
+    /// Region code used by Audnexus and `audible_tld` ("us", "uk", ...).
+    pub fn code(self) -> String {
+        self.to_string().to_lowercase()
+    }
 }
 
 impl fmt::Display for ChapterRegion {
@@ -31,6 +45,30 @@ impl fmt::Display for ChapterRegion {
             ChapterRegion::AU => write!(f, "AU"),
             ChapterRegion::FR => write!(f, "FR"),
             ChapterRegion::DE => write!(f, "DE"),
+            ChapterRegion::JP => write!(f, "JP"),
+            ChapterRegion::IT => write!(f, "IT"),
+            ChapterRegion::IN => write!(f, "IN"),
+            ChapterRegion::ES => write!(f, "ES"),
+        }
+    }
+}
+
+/// Which chapters the title rules rewrite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleRuleScope {
+    All,
+    Range,
+}
+
+impl TitleRuleScope {
+    pub const ALL: [TitleRuleScope; 2] = [TitleRuleScope::All, TitleRuleScope::Range];
+}
+
+impl fmt::Display for TitleRuleScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TitleRuleScope::All => write!(f, "All chapters"),
+            TitleRuleScope::Range => write!(f, "Chapters from/to"),
         }
     }
 }
@@ -66,6 +104,39 @@ pub struct ChapterState {
     pub lookup_result: Option<Vec<crate::models::Chapter>>,
     /// Total duration (ms) from last lookup; used to warn if it differs from book_duration_ms.
     pub lookup_duration_ms: Option<u64>,
+    /// Audible brand intro/outro lengths (ms) from the pending lookup.
+    pub lookup_brand_ms: Option<(u64, u64)>,
+    /// Audible brand intro/outro lengths (ms) of the loaded book, kept once a lookup is applied;
+    /// used by the conversion trim.
+    pub brand_ms: Option<(u64, u64)>,
+    /// Audible editions found by title when there is no ASIN; the user picks one to fetch.
+    pub edition_candidates: Vec<crate::models::ChapterEdition>,
+    /// Title cleanup rules panel.
+    pub show_title_rules: bool,
+    pub title_rules: TitleRules,
+    pub title_rule_scope: TitleRuleScope,
+    /// 1-based chapter numbers bounding `TitleRuleScope::Range` (inclusive).
+    pub title_rule_from: String,
+    pub title_rule_to: String,
+    /// Why the title rules could not be applied (invalid find pattern); shown in the rules panel.
+    pub title_rule_error: Option<String>,
+}
+
+impl ChapterState {
+    /// Indices of the chapters the title rules apply to; locked chapters are never rewritten.
+    pub fn title_rule_targets(&self) -> Vec<usize> {
+        let (from, to) = match self.title_rule_scope {
+            TitleRuleScope::All => (1, self.chapters.len()),
+            TitleRuleScope::Range => (
+                self.title_rule_from.trim().parse().unwrap_or(1),
+                self.title_rule_to.trim().parse().unwrap_or(self.chapters.len()),
+            ),
+        };
+        (from.max(1)..=to.min(self.chapters.len()))
+            .map(|number| number - 1)
+            .filter(|&i| !self.chapters[i].is_locked)
+            .collect()
+    }
 }
 
 impl Default for ChapterState {
@@ -92,6 +163,15 @@ impl Default for ChapterState {
             load_generation: 0,
             lookup_result: None,
             lookup_duration_ms: None,
+            lookup_brand_ms: None,
+            brand_ms: None,
+            edition_candidates: Vec::new(),
+            show_title_rules: false,
+            title_rules: TitleRules::default(),
+            title_rule_scope: TitleRuleScope::All,
+            title_rule_from: String::new(),
+            title_rule_to: String::new(),
+            title_rule_error: None,
         }
     }
 }
@@ -1,3 +1,5 @@
+use crate::services::cover_edit::CoverEdit;
+use crate::services::embedded_cover::EmbeddedCover;
 use crate::ui::cover_search::CoverResult;
 use std::collections::HashMap;
 
@@ -12,7 +14,42 @@ pub struct CoverState {
     /// Cache for cover search result thumbnails (URL -> image handle)
     pub cover_search_result_handles: HashMap<String, iced::widget::image::Handle>,
     pub cover_search_error: Option<String>,
+    /// Web page to take cover images from.
+    pub cover_page_url: String,
     pub is_downloading_cover: bool,
+    /// Distinct pictures embedded in the source files.
+    pub embedded_covers: Vec<EmbeddedCover>,
+    pub is_scanning_embedded: bool,
+    /// Crop/resize/quality applied when the cover is embedded.
+    pub edit: CoverEdit,
+    pub show_editor: bool,
+    pub edit_preview: Option<CoverPreview>,
+    pub edit_preview_error: Option<String>,
+    pub is_rendering_preview: bool,
+    /// Bumped per render so a slow, stale preview does not replace a newer one.
+    pub preview_generation: u64,
+}
+
+/// The edited cover as it will be embedded.
+#[derive(Debug, Clone)]
+pub struct CoverPreview {
+    pub handle: iced::widget::image::Handle,
+    /// Cover path/URL the preview was rendered from.
+    pub source: String,
+    pub width: u32,
+    pub height: u32,
+    pub source_width: u32,
+    pub source_height: u32,
+    pub bytes: usize,
+}
+
+/// Side of the manual crop box moved by a slider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CropSide {
+    Left,
+    Top,
+    Width,
+    Height,
 }
 
 impl Default for CoverState {
@@ -26,7 +63,16 @@ impl Default for CoverState {
             cover_search_results: Vec::new(),
             cover_search_result_handles: HashMap::new(),
             cover_search_error: None,
+            cover_page_url: String::new(),
             is_downloading_cover: false,
+            embedded_covers: Vec::new(),
+            is_scanning_embedded: false,
+            edit: CoverEdit::default(),
+            show_editor: false,
+            edit_preview: None,
+            edit_preview_error: None,
+            is_rendering_preview: false,
+            preview_generation: 0,
         }
     }
 }
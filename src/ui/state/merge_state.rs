@@ -0,0 +1,232 @@
+use crate::models::BookMetadata;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Name of the column holding the metadata currently being edited.
+pub const CURRENT_SOURCE: &str = "Current";
+
+/// Sources offered as per-field defaults in Settings (provider display names).
+pub const PREFERENCE_SOURCES: [&str; 7] = [
+    CURRENT_SOURCE,
+    "Audible.com",
+    "Audible.ca",
+    "Audnexus",
+    "Google Books",
+    "Open Library",
+    "iTunes",
+];
+
+/// A BookMetadata field that can be taken from a different source in the merge view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeField {
+    Title,
+    Subtitle,
+    Author,
+    Narrator,
+    Series,
+    Description,
+    Isbn,
+    Asin,
+    Publisher,
+    PublishYear,
+    Genre,
+    Tags,
+    Language,
+    CoverUrl,
+}
+
+impl MergeField {
+    pub const ALL: [MergeField; 14] = [
+        MergeField::Title,
+        MergeField::Subtitle,
+        MergeField::Author,
+        MergeField::Narrator,
+        MergeField::Series,
+        MergeField::Description,
+        MergeField::Isbn,
+        MergeField::Asin,
+        MergeField::Publisher,
+        MergeField::PublishYear,
+        MergeField::Genre,
+        MergeField::Tags,
+        MergeField::Language,
+        MergeField::CoverUrl,
+    ];
+
+    /// Stable key used in merge_preferences.json.
+    pub fn key(&self) -> &'static str {
+        match self {
+            MergeField::Title => "title",
+            MergeField::Subtitle => "subtitle",
+            MergeField::Author => "author",
+            MergeField::Narrator => "narrator",
+            MergeField::Series => "series",
+            MergeField::Description => "description",
+            MergeField::Isbn => "isbn",
+            MergeField::Asin => "asin",
+            MergeField::Publisher => "publisher",
+            MergeField::PublishYear => "publish_year",
+            MergeField::Genre => "genre",
+            MergeField::Tags => "tags",
+            MergeField::Language => "language",
+            MergeField::CoverUrl => "cover_url",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|f| f.key() == key)
+    }
+
+    /// Built-in default source: Audible has the best narrator/series data, Google Books
+    /// ISBN and publisher, Open Library subjects (genre).
+    pub fn default_source(&self) -> &'static str {
+        match self {
+            MergeField::Narrator
+            | MergeField::Series
+            | MergeField::Asin
+            | MergeField::Description => "Audible.com",
+            MergeField::Isbn | MergeField::Publisher => "Google Books",
+            MergeField::Genre => "Open Library",
+            _ => CURRENT_SOURCE,
+        }
+    }
+
+    /// The field's value as shown in the merge view (lists joined); None when empty.
+    pub fn get(&self, book: &BookMetadata) -> Option<String> {
+        let value = match self {
+            MergeField::Title => Some(book.title.clone()),
+            MergeField::Author => Some(book.author_display()),
+            MergeField::Subtitle => book.subtitle.clone(),
+            MergeField::Narrator => book.narrator_display(),
+            MergeField::Series => {
+                let series: Vec<String> = book.series.iter().map(|s| s.to_string()).collect();
+                crate::models::metadata::join_names(&series)
+            }
+            MergeField::Description => book.description.clone(),
+            MergeField::Isbn => book.isbn.clone(),
+            MergeField::Asin => book.asin.clone(),
+            MergeField::Publisher => book.publisher.clone(),
+            MergeField::PublishYear => book.publish_year.clone(),
+            MergeField::Genre => book.genre_display(),
+            MergeField::Tags => book.tags_display(),
+            MergeField::Language => book.language.clone(),
+            MergeField::CoverUrl => book.cover_url.clone(),
+        };
+        value.filter(|v| !v.trim().is_empty())
+    }
+
+    /// Copy the field from `source` onto `book` (lists as a whole, so entries keep their numbers).
+    pub fn copy(&self, source: &BookMetadata, book: &mut BookMetadata) {
+        let text = |value: &Option<String>| value.clone().filter(|v| !v.trim().is_empty());
+        match self {
+            MergeField::Title => book.title = source.title.clone(),
+            MergeField::Author => book.authors = source.authors.clone(),
+            MergeField::Subtitle => book.subtitle = text(&source.subtitle),
+            MergeField::Narrator => book.narrators = source.narrators.clone(),
+            MergeField::Series => book.series = source.series.clone(),
+            MergeField::Description => book.description = text(&source.description),
+            MergeField::Isbn => book.isbn = text(&source.isbn),
+            MergeField::Asin => book.asin = text(&source.asin),
+            MergeField::Publisher => book.publisher = text(&source.publisher),
+            MergeField::PublishYear => book.publish_year = text(&source.publish_year),
+            MergeField::Genre => book.genres = source.genres.clone(),
+            MergeField::Tags => book.tags = source.tags.clone(),
+            MergeField::Language => book.language = text(&source.language),
+            MergeField::CoverUrl => book.cover_url = text(&source.cover_url),
+        }
+    }
+}
+
+impl fmt::Display for MergeField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            MergeField::Title => "Title",
+            MergeField::Subtitle => "Subtitle",
+            MergeField::Author => "Author",
+            MergeField::Narrator => "Narrator",
+            MergeField::Series => "Series",
+            MergeField::Description => "Description",
+            MergeField::Isbn => "ISBN",
+            MergeField::Asin => "ASIN",
+            MergeField::Publisher => "Publisher",
+            MergeField::PublishYear => "Year",
+            MergeField::Genre => "Genre",
+            MergeField::Tags => "Tags",
+            MergeField::Language => "Language",
+            MergeField::CoverUrl => "Cover",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Merge view: the current book (column 0) side by side with provider results.
+#[derive(Debug, Clone, Default)]
+pub struct MergeState {
+    /// (source name, metadata). Index 0 is always the current book.
+    pub sources: Vec<(String, BookMetadata)>,
+    /// Selected source column per field.
+    pub choices: HashMap<MergeField, usize>,
+    /// Sources are metadata files from the book's folder (Cancel goes back to the Metadata tab).
+    pub from_files: bool,
+}
+
+impl MergeState {
+    /// Start a merge; each field defaults to the preferred source when it has a value there,
+    /// otherwise the current value, otherwise the first source that has one.
+    pub fn open(
+        current: BookMetadata,
+        candidates: Vec<(String, BookMetadata)>,
+        preferences: &HashMap<MergeField, String>,
+    ) -> Self {
+        let mut sources = vec![(CURRENT_SOURCE.to_string(), current)];
+        sources.extend(candidates);
+
+        let mut choices = HashMap::new();
+        for field in MergeField::ALL {
+            let preferred = preferences
+                .get(&field)
+                .map(|s| s.as_str())
+                .unwrap_or(field.default_source());
+            let has_value = |i: &usize| field.get(&sources[*i].1).is_some();
+            let choice = sources
+                .iter()
+                .position(|(name, book)| name == preferred && field.get(book).is_some())
+                .or_else(|| Some(0).filter(has_value))
+                .or_else(|| (0..sources.len()).find(has_value))
+                .unwrap_or(0);
+            choices.insert(field, choice);
+        }
+        Self { sources, choices, from_files: false }
+    }
+
+    /// Review metadata imported from files; each field defaults to the first file that has it,
+    /// otherwise the current value.
+    pub fn open_import(current: BookMetadata, files: Vec<(String, BookMetadata)>) -> Self {
+        let mut sources = vec![(CURRENT_SOURCE.to_string(), current)];
+        sources.extend(files);
+
+        let choices = MergeField::ALL
+            .iter()
+            .map(|field| {
+                let choice = (1..sources.len()).find(|i| field.get(&sources[*i].1).is_some()).unwrap_or(0);
+                (*field, choice)
+            })
+            .collect();
+        Self { sources, choices, from_files: true }
+    }
+
+    pub fn choice(&self, field: MergeField) -> usize {
+        self.choices.get(&field).copied().unwrap_or(0)
+    }
+
+    /// The current book with every field replaced by its chosen source.
+    pub fn merged(&self) -> Option<BookMetadata> {
+        let mut book = self.sources.first()?.1.clone();
+        for field in MergeField::ALL {
+            if let Some((_, source)) = self.sources.get(self.choice(field)) {
+                field.copy(source, &mut book);
+            }
+        }
+        Some(book)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::models::SeriesEntry;
 use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -67,6 +68,25 @@ impl fmt::Display for MetadataProvider {
     }
 }
 
+/// Metadata fields edited as a list of chips.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListField {
+    Authors,
+    Narrators,
+    Genres,
+    Tags,
+}
+
+impl ListField {
+    /// Split typed or pasted text into entries ("A & B" is two people, not for genres/tags).
+    fn split(self, value: &str) -> Vec<String> {
+        match self {
+            ListField::Authors | ListField::Narrators => crate::models::metadata::split_names(value),
+            ListField::Genres | ListField::Tags => crate::models::metadata::split_list(value),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct MetadataState {
     pub selected_book: Option<crate::models::BookMetadata>,
@@ -74,22 +94,33 @@ pub struct MetadataState {
     // Editing fields (for metadata tab)
     pub editing_title: String,
     pub editing_subtitle: String,
-    pub editing_author: String,
-    pub editing_series: String,
-    pub editing_series_number: String,
-    pub editing_narrator: String,
+    pub editing_authors: Vec<String>,
+    pub editing_series: Vec<SeriesEntry>,
+    pub editing_narrators: Vec<String>,
     pub editing_description: String,
     pub editing_description_content: iced::widget::text_editor::Content,
     pub editing_isbn: String,
     pub editing_asin: String,
     pub editing_publisher: String,
     pub editing_publish_year: String,
-    pub editing_genre: String,
-    pub editing_tags: String,
+    pub editing_genres: Vec<String>,
+    pub editing_tags: Vec<String>,
     pub editing_language: String,
     pub editing_explicit: bool,
     pub editing_abridged: bool,
+
+    // Text typed into the chip inputs, not yet added
+    pub author_input: String,
+    pub narrator_input: String,
+    pub genre_input: String,
+    pub tag_input: String,
+    pub series_name_input: String,
+    pub series_number_input: String,
     
+    // Import from metadata files in the source folder
+    pub is_importing_files: bool,
+    pub import_error: Option<String>,
+
     // Provider selection
     pub metadata_provider: MetadataProvider,
 }
@@ -100,22 +131,177 @@ impl Default for MetadataState {
             selected_book: None,
             editing_title: String::new(),
             editing_subtitle: String::new(),
-            editing_author: String::new(),
-            editing_series: String::new(),
-            editing_series_number: String::new(),
-            editing_narrator: String::new(),
+            editing_authors: Vec::new(),
+            editing_series: Vec::new(),
+            editing_narrators: Vec::new(),
             editing_description: String::new(),
             editing_description_content: iced::widget::text_editor::Content::new(),
             editing_isbn: String::new(),
             editing_asin: String::new(),
             editing_publisher: String::new(),
             editing_publish_year: String::new(),
-            editing_genre: String::new(),
-            editing_tags: String::new(),
+            editing_genres: Vec::new(),
+            editing_tags: Vec::new(),
             editing_language: String::new(),
             editing_explicit: false,
             editing_abridged: false,
+            author_input: String::new(),
+            narrator_input: String::new(),
+            genre_input: String::new(),
+            tag_input: String::new(),
+            series_name_input: String::new(),
+            series_number_input: String::new(),
+            is_importing_files: false,
+            import_error: None,
             metadata_provider: MetadataProvider::Auto,
         }
     }
 }
+
+impl MetadataState {
+    /// Snapshot of the book as currently edited (editing fields over selected_book).
+    /// Text still in a chip input counts as an entry, so nothing typed is lost.
+    pub fn current_book(&self) -> crate::models::BookMetadata {
+        let opt = |s: &str| if s.trim().is_empty() { None } else { Some(s.to_string()) };
+        let mut book = self.selected_book.clone().unwrap_or_default();
+        book.title = self.editing_title.clone();
+        book.subtitle = opt(&self.editing_subtitle);
+        book.authors = self.list_with_input(ListField::Authors);
+        book.series = self.editing_series.clone();
+        if !self.series_name_input.trim().is_empty() {
+            book.series.push(SeriesEntry::new(self.series_name_input.clone(), opt(&self.series_number_input)));
+        }
+        book.narrators = self.list_with_input(ListField::Narrators);
+        book.description = opt(&self.editing_description);
+        book.isbn = opt(&self.editing_isbn);
+        book.asin = opt(&self.editing_asin);
+        book.publisher = opt(&self.editing_publisher);
+        book.publish_year = opt(&self.editing_publish_year);
+        book.genres = self.list_with_input(ListField::Genres);
+        book.tags = self.list_with_input(ListField::Tags);
+        book.language = opt(&self.editing_language);
+        book.explicit = Some(self.editing_explicit);
+        book.abridged = Some(self.editing_abridged);
+        book
+    }
+
+    /// Make `book` the selected book and copy its fields into the editing fields.
+    pub fn load_book(&mut self, book: crate::models::BookMetadata) {
+        self.selected_book = Some(book.clone());
+        self.editing_title = book.title;
+        self.editing_subtitle = book.subtitle.unwrap_or_default();
+        self.editing_authors = book.authors;
+        self.editing_series = book.series;
+        self.editing_narrators = book.narrators;
+        self.editing_description = book.description.unwrap_or_default();
+        self.editing_description_content = iced::widget::text_editor::Content::with_text(&self.editing_description);
+        self.editing_isbn = book.isbn.unwrap_or_default();
+        self.editing_asin = book.asin.unwrap_or_default();
+        self.editing_publisher = book.publisher.unwrap_or_default();
+        self.editing_publish_year = book.publish_year.unwrap_or_default();
+        self.editing_genres = book.genres;
+        self.editing_tags = book.tags;
+        self.editing_language = book.language.unwrap_or_default();
+        self.editing_explicit = book.explicit.unwrap_or(false);
+        self.editing_abridged = book.abridged.unwrap_or(false);
+        for input in [
+            &mut self.author_input,
+            &mut self.narrator_input,
+            &mut self.genre_input,
+            &mut self.tag_input,
+            &mut self.series_name_input,
+            &mut self.series_number_input,
+        ] {
+            input.clear();
+        }
+    }
+
+    pub fn list(&self, field: ListField) -> &Vec<String> {
+        match field {
+            ListField::Authors => &self.editing_authors,
+            ListField::Narrators => &self.editing_narrators,
+            ListField::Genres => &self.editing_genres,
+            ListField::Tags => &self.editing_tags,
+        }
+    }
+
+    fn list_mut(&mut self, field: ListField) -> &mut Vec<String> {
+        match field {
+            ListField::Authors => &mut self.editing_authors,
+            ListField::Narrators => &mut self.editing_narrators,
+            ListField::Genres => &mut self.editing_genres,
+            ListField::Tags => &mut self.editing_tags,
+        }
+    }
+
+    pub fn input(&self, field: ListField) -> &String {
+        match field {
+            ListField::Authors => &self.author_input,
+            ListField::Narrators => &self.narrator_input,
+            ListField::Genres => &self.genre_input,
+            ListField::Tags => &self.tag_input,
+        }
+    }
+
+    pub fn input_mut(&mut self, field: ListField) -> &mut String {
+        match field {
+            ListField::Authors => &mut self.author_input,
+            ListField::Narrators => &mut self.narrator_input,
+            ListField::Genres => &mut self.genre_input,
+            ListField::Tags => &mut self.tag_input,
+        }
+    }
+
+    /// The list plus whatever is typed in its input, without duplicates.
+    fn list_with_input(&self, field: ListField) -> Vec<String> {
+        let mut list = self.list(field).clone();
+        for entry in field.split(self.input(field)) {
+            if !list.iter().any(|e| e.eq_ignore_ascii_case(&entry)) {
+                list.push(entry);
+            }
+        }
+        list
+    }
+
+    /// Move the typed text into the list as one or more chips.
+    pub fn add_list_input(&mut self, field: ListField) {
+        let list = self.list_with_input(field);
+        *self.list_mut(field) = list;
+        self.input_mut(field).clear();
+    }
+
+    pub fn remove_list_item(&mut self, field: ListField, index: usize) {
+        let list = self.list_mut(field);
+        if index < list.len() {
+            list.remove(index);
+        }
+    }
+
+    /// Add the typed series name and number as a series entry.
+    pub fn add_series_input(&mut self) {
+        if self.series_name_input.trim().is_empty() {
+            return;
+        }
+        let number = Some(self.series_number_input.clone());
+        self.editing_series.push(SeriesEntry::new(self.series_name_input.clone(), number));
+        self.series_name_input.clear();
+        self.series_number_input.clear();
+    }
+
+    pub fn remove_series(&mut self, index: usize) {
+        if index < self.editing_series.len() {
+            self.editing_series.remove(index);
+        }
+    }
+
+    /// Copy the list fields into selected_book after a chip was added or removed.
+    pub fn sync_lists_to_book(&mut self) {
+        if let Some(ref mut book) = self.selected_book {
+            book.authors = self.editing_authors.clone();
+            book.narrators = self.editing_narrators.clone();
+            book.series = self.editing_series.clone();
+            book.genres = self.editing_genres.clone();
+            book.tags = self.editing_tags.clone();
+        }
+    }
+}
@@ -3,9 +3,11 @@ pub mod metadata_state;
 pub mod cover_state;
 pub mod chapter_state;
 pub mod file_state;
+pub mod merge_state;
 
 pub use search_state::SearchState;
-pub use metadata_state::{MetadataState, MetadataProvider};
-pub use cover_state::CoverState;
-pub use chapter_state::{ChapterState, ChapterRegion};
+pub use metadata_state::{MetadataState, MetadataProvider, ListField};
+pub use cover_state::{CoverState, CoverPreview, CropSide};
+pub use chapter_state::{ChapterState, ChapterRegion, TitleRuleScope};
 pub use file_state::FileState;
+pub use merge_state::{MergeState, MergeField};
@@ -1,4 +1,5 @@
-use crate::models::BookMetadata;
+use crate::error::LecternError;
+use crate::services::matching::RankedResult;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
@@ -8,8 +9,8 @@ pub struct SearchState {
     pub author: String,
     pub by_asin: bool,
     pub is_searching: bool,
-    pub results: Vec<BookMetadata>,
-    pub error: Option<String>,
+    pub results: Vec<RankedResult>, // Ranked by match score, duplicates across providers merged
+    pub error: Option<LecternError>,
     pub current_page: usize,
     pub results_per_page: usize,
     pub result_covers: HashMap<String, iced::widget::image::Handle>, // Cache for search result cover images (URL -> image handle)
@@ -1,7 +1,9 @@
 use crate::ui::{Message, Lectern};
 use crate::ui::colors;
 use crate::utils::time::format_time;
-use iced::widget::{button, checkbox, column, container, row, scrollable, text, text_input, tooltip, Column, Space, Image};
+use crate::utils::chapter_titles::{NumberWords, TitleCase, TitleNumbering};
+use crate::ui::state::TitleRuleScope;
+use iced::widget::{button, checkbox, column, container, pick_list, row, scrollable, text, text_input, tooltip, Column, Space, Image};
 use iced::widget::tooltip::Position;
 use iced::{Alignment, Element, Length, Point};
 use iced::widget::canvas::{Canvas, Frame, Path, Program, Stroke};
@@ -93,6 +95,10 @@ pub fn view_chapters(app: &Lectern) -> Element<'_, Message> {
                             .on_press(Message::ChapterLookup)
                             .style(iced::theme::Button::custom(crate::ui::theme::RoundedPrimary(app.theme_id)))
                             .padding([12, 20]),
+                        button("Find by Title")
+                            .on_press(Message::ChapterFindEditions)
+                            .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id)))
+                            .padding([12, 20]),
                     ]
                     .spacing(15)
                     .align_items(Alignment::End),
@@ -102,6 +108,8 @@ pub fn view_chapters(app: &Lectern) -> Element<'_, Message> {
                         .style(iced::theme::Checkbox::Custom(Box::new(crate::ui::theme::ThemedCheckbox(app.theme_id))))
                         .text_size(14),
 
+                    view_chapter_editions(app),
+
                     row![
                         text(format!("Current book ASIN/ISBN: {}", 
                             app.metadata.selected_book.as_ref()
@@ -138,6 +146,14 @@ pub fn view_chapters(app: &Lectern) -> Element<'_, Message> {
                     iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id))
                 })
                 .padding([10, 15]),
+            button("Clean Titles")
+                .on_press(Message::ChapterTitleRulesToggled)
+                .style(if app.chapters.show_title_rules {
+                    iced::theme::Button::custom(crate::ui::theme::RoundedPrimary(app.theme_id))
+                } else {
+                    iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id))
+                })
+                .padding([10, 15]),
             button("Remove All")
                 .on_press(Message::ChapterRemoveAll)
                 .style(iced::theme::Button::custom(crate::ui::theme::RoundedDestructive(app.theme_id)))
@@ -741,6 +757,7 @@ pub fn view_chapters(app: &Lectern) -> Element<'_, Message> {
                 top_controls,
                 duration_warning,
                 shift_controls,
+                view_title_rules(app),
                 Space::with_height(Length::Fixed(6.0)),
                 header,
                 chapter_list_content,
@@ -763,3 +780,214 @@ pub fn view_chapters(app: &Lectern) -> Element<'_, Message> {
         .height(Length::Fill)
         .into()
 }
+
+/// Audible editions found by title; picking one fetches its chapters.
+fn view_chapter_editions(app: &Lectern) -> Element<'_, Message> {
+    let editions = &app.chapters.edition_candidates;
+    if editions.is_empty() {
+        return Space::with_height(Length::Fixed(0.0)).into();
+    }
+    let book_ms = app.chapters.book_duration_ms;
+    let mut list = Column::new().spacing(6);
+    for (index, edition) in editions.iter().enumerate() {
+        let runtime = match (edition.runtime_ms, book_ms) {
+            (Some(runtime), Some(book)) => {
+                let diff = runtime.abs_diff(book);
+                let sign = if runtime >= book { "+" } else { "-" };
+                format!("{} ({}{} vs this book)", format_time(runtime, false), sign, format_time(diff, false))
+            }
+            (Some(runtime), None) => format_time(runtime, false),
+            (None, _) => "Runtime unknown".to_string(),
+        };
+        let mut details = vec![edition.asin.clone(), runtime];
+        if !edition.narrators.is_empty() {
+            details.push(format!("Narrated by {}", edition.narrators));
+        }
+        if let Some(ref date) = edition.release_date {
+            details.push(date.clone());
+        }
+        list = list.push(
+            container(
+                row![
+                    column![
+                        text(if edition.authors.is_empty() {
+                            edition.title.clone()
+                        } else {
+                            format!("{} - {}", edition.title, edition.authors)
+                        })
+                            .size(14)
+                            .style(iced::theme::Text::Color(app.palette().background.base.text)),
+                        text(details.join("  |  "))
+                            .size(12)
+                            .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+                    ]
+                    .spacing(3)
+                    .width(Length::Fill),
+                    button("Get Chapters")
+                        .on_press(Message::ChapterEditionChosen(index))
+                        .style(iced::theme::Button::custom(crate::ui::theme::RoundedPrimary(app.theme_id)))
+                        .padding([8, 16]),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            )
+            .padding(8)
+            .style(iced::theme::Container::Box)
+            .width(Length::Fill),
+        );
+    }
+    column![
+        text(if book_ms.is_some() {
+            format!("{} editions found (closest runtime first):", editions.len())
+        } else {
+            format!("{} editions found:", editions.len())
+        })
+            .size(14)
+            .style(iced::theme::Text::Color(app.palette().background.base.text)),
+        scrollable(list).height(Length::Fixed(240.0)),
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Most preview rows shown; the rules still apply to every chapter in scope.
+const TITLE_PREVIEW_ROWS: usize = 200;
+
+/// Title cleanup rules with a preview of the renamed chapters.
+fn view_title_rules(app: &Lectern) -> Element<'_, Message> {
+    if !app.chapters.show_title_rules || app.chapters.chapters.is_empty() {
+        return Space::with_height(Length::Fixed(0.0)).into();
+    }
+    let rules = &app.chapters.title_rules;
+    let label = |s: &'static str| {
+        text(s)
+            .size(12)
+            .style(iced::theme::Text::Color(app.palette().background.weak.text))
+    };
+
+    let error_text = |e: String| {
+        text(e)
+            .size(12)
+            .style(iced::theme::Text::Color(app.palette().danger.base.color))
+    };
+    let compiled = rules.compile();
+    // Shown under the find box; the preview repeats it in place of the renamed titles
+    let rule_error: Element<Message> = match compiled.as_ref().err().or(app.chapters.title_rule_error.as_ref()) {
+        Some(e) => error_text(e.clone()).into(),
+        None => Space::with_height(Length::Fixed(0.0)).into(),
+    };
+
+    let targets = app.chapters.title_rule_targets();
+    let (preview, changed): (Element<Message>, usize) = match compiled {
+        Ok(transformer) => {
+            let mut list = Column::new().spacing(2);
+            let mut changed = 0;
+            for &index in &targets {
+                let old = &app.chapters.chapters[index].title;
+                let new = transformer.apply(old, index + 1);
+                if &new == old {
+                    continue;
+                }
+                changed += 1;
+                if changed <= TITLE_PREVIEW_ROWS {
+                    list = list.push(
+                        text(format!("{}. {}  ->  {}", index + 1, old, new))
+                            .size(12)
+                            .style(iced::theme::Text::Color(app.palette().background.base.text)),
+                    );
+                }
+            }
+            let preview = if changed == 0 {
+                label("No titles change with these rules.").into()
+            } else {
+                scrollable(list).height(Length::Fixed(160.0)).into()
+            };
+            (preview, changed)
+        }
+        Err(e) => (error_text(e).into(), 0),
+    };
+
+    let range_inputs: Element<Message> = if app.chapters.title_rule_scope == TitleRuleScope::Range {
+        row![
+            text_input("From", &app.chapters.title_rule_from)
+                .on_input(Message::TitleRuleFromChanged)
+                .width(Length::Fixed(70.0))
+                .padding(8),
+            text_input("To", &app.chapters.title_rule_to)
+                .on_input(Message::TitleRuleToChanged)
+                .width(Length::Fixed(70.0))
+                .padding(8),
+        ]
+        .spacing(6)
+        .into()
+    } else {
+        Space::with_width(Length::Fixed(0.0)).into()
+    };
+
+    container(
+        column![
+            text("Clean Up Chapter Titles")
+                .size(16)
+                .style(iced::theme::Text::Color(app.palette().background.base.text)),
+            row![
+                checkbox("Strip number prefix (\"01 - \", \"Track 3: \")", rules.strip_prefix)
+                    .on_toggle(Message::TitleRuleStripPrefixToggled)
+                    .style(iced::theme::Checkbox::Custom(Box::new(crate::ui::theme::ThemedCheckbox(app.theme_id))))
+                    .text_size(13),
+                Space::with_width(Length::Fill),
+                pick_list(&NumberWords::ALL[..], Some(rules.number_words), Message::TitleRuleNumberWordsChanged)
+                    .padding(8),
+                pick_list(&TitleCase::ALL[..], Some(rules.case), Message::TitleRuleCaseChanged)
+                    .padding(8),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            row![
+                label("Find (regex)"),
+                text_input("e.g. \\s*\\(Unabridged\\)", &rules.find)
+                    .on_input(Message::TitleRuleFindChanged)
+                    .padding(8),
+                label("Replace"),
+                text_input("$1 for groups", &rules.replace)
+                    .on_input(Message::TitleRuleReplaceChanged)
+                    .padding(8),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center),
+            rule_error,
+            row![
+                pick_list(&TitleNumbering::ALL[..], Some(rules.numbering), Message::TitleRuleNumberingChanged)
+                    .padding(8),
+                text_input("Chapter {n}", &rules.template)
+                    .on_input(Message::TitleRuleTemplateChanged)
+                    .width(Length::Fixed(160.0))
+                    .padding(8),
+                label("{n} is the chapter number"),
+                Space::with_width(Length::Fill),
+                pick_list(&TitleRuleScope::ALL[..], Some(app.chapters.title_rule_scope), Message::TitleRuleScopeChanged)
+                    .padding(8),
+                range_inputs,
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center),
+            label("Locked chapters are left unchanged. Preview:"),
+            preview,
+            row![
+                button(text(format!("Rename {} Chapters", changed)))
+                    .on_press_maybe((changed > 0).then_some(Message::ApplyTitleRules))
+                    .style(iced::theme::Button::custom(crate::ui::theme::RoundedPrimary(app.theme_id)))
+                    .padding([8, 16]),
+                button("Close")
+                    .on_press(Message::ChapterTitleRulesToggled)
+                    .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id)))
+                    .padding([8, 16]),
+            ]
+            .spacing(10),
+        ]
+        .spacing(8),
+    )
+    .padding(12)
+    .style(iced::theme::Container::Box)
+    .width(Length::Fill)
+    .into()
+}
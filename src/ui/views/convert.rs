@@ -1,8 +1,16 @@
-use crate::ui::{Message, Lectern};
+use crate::ui::{Message, Lectern, NormalizationParam};
+use crate::services::conversion::{AacProfile, EncodeStrategy, Normalization, NormalizationMode, OutputFormat, SplitBy, TrimMode};
 use crate::ui::colors; // WARNING only
-use iced::widget::{button, column, container, row, scrollable, text, text_input, Space, pick_list, checkbox};
+use iced::widget::{button, checkbox, column, container, row, scrollable, text, text_input, Space, pick_list};
 use iced::{Alignment, Element, Length};
 
+#[cfg(target_os = "windows")]
+const FFMPEG_INSTALL_HINT: &str = "Install FFmpeg (e.g. `winget install ffmpeg`) and make sure ffmpeg.exe is in PATH, then try again.";
+#[cfg(target_os = "macos")]
+const FFMPEG_INSTALL_HINT: &str = "Install FFmpeg (e.g. `brew install ffmpeg`), then try again.";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const FFMPEG_INSTALL_HINT: &str = "Install FFmpeg with your package manager (e.g. `sudo apt install ffmpeg` or `sudo dnf install ffmpeg`), then try again.";
+
 pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
     use crate::ui::views::LecternView;
     let tab_bar = app.view_tab_bar();
@@ -37,21 +45,18 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
         crate::ui::helpers::apply_media_template(
             &app.media_management_template,
             lib_path,
-            &app.metadata.editing_title,
-            &app.metadata.editing_author,
-            &app.metadata.editing_series,
-            &app.metadata.editing_series_number,
-            &app.metadata.editing_publish_year,
-            &app.metadata.editing_genre,
-            &app.metadata.editing_asin,
-            &app.metadata.editing_language,
-            &app.metadata.editing_tags,
+            &app.metadata.current_book(),
         )
     } else if let Some(ref path) = app.output_path {
         path.clone()
     } else {
         "Not set - will prompt for location".to_string()
     };
+    let output_path_display = if app.output_path.is_some() || app.local_library_path.is_some() {
+        app.conversion_format.apply_extension(&output_path_display)
+    } else {
+        output_path_display
+    };
 
     // Conversion result with size comparison
     let conversion_result: Element<'_, Message> = if app.source_size > 0 && app.output_size > 0 {
@@ -84,6 +89,13 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
                     Space::with_width(Length::Fixed(10.0)),
                     text(saved).size(12).style(iced::theme::Text::Color(if app.output_size < app.source_size { app.palette().success.base.color } else { colors::WARNING })),
                 ],
+                view_plan_result(app),
+                view_encoder_warnings(app),
+                view_trim_result(app),
+                view_loudness_result(app),
+                view_parts_result(app),
+                view_sidecars_result(app),
+                view_verification_result(app),
             ]
             .spacing(8),
         )
@@ -96,7 +108,7 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
 
     // Header section
     let header = column![
-        text("Convert to M4B")
+        text(format!("Convert to {}", app.conversion_format.extension().to_uppercase()))
             .size(28)
             .style(iced::theme::Text::Color(app.palette().background.base.text)),
         Space::with_height(Length::Fixed(10.0)),
@@ -106,12 +118,13 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
     ];
 
     // Presets Section (Inspired by Audiobookshelf)
-    let note_text = if app.conversion_codec == "copy" {
+    let copy_codec = app.conversion_format == OutputFormat::M4b && app.conversion_codec == "copy";
+    let note_text = if copy_codec {
         "Note: 'Copy' codec preserves original streams and is extremely fast, but metadata/cover art embedding might not work in all players."
     } else {
-        "Re-encoding uses FFmpeg and creates a single consolidated file."
+        "Inputs that already match the output (e.g. AAC M4A with the same sample rate and channels) are stream copied; others are re-encoded with FFmpeg. Choosing a bitrate always re-encodes."
     };
-    let note_color = if app.conversion_codec == "copy" {
+    let note_color = if copy_codec {
         colors::WARNING
     } else {
         app.palette().secondary.base.text
@@ -124,19 +137,34 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
                 .style(iced::theme::Text::Color(app.palette().background.base.text)),
             Space::with_height(Length::Fixed(15.0)),
             
+            view_presets(app),
+            Space::with_height(Length::Fixed(10.0)),
+            
             row![
-                // Codec Pick List
+                // Output format
                 column![
-                    text("Codec")
+                    text("Format")
                         .size(14)
                         .style(iced::theme::Text::Color(app.palette().background.weak.text)),
                     Space::with_height(Length::Fixed(5.0)),
                     pick_list(
-                        vec!["aac".to_string(), "copy".to_string(), "opus".to_string()],
-                        Some(app.conversion_codec.clone()),
-                        Message::ConversionCodecChanged
+                        &OutputFormat::ALL[..],
+                        Some(app.conversion_format),
+                        Message::ConversionFormatChanged
                     )
-                    .width(Length::Fixed(120.0)),
+                    .width(Length::Fixed(180.0)),
+                ]
+                .spacing(5),
+                
+                Space::with_width(Length::Fixed(20.0)),
+                
+                // Codec Pick List (other formats have a fixed codec)
+                column![
+                    text("Codec")
+                        .size(14)
+                        .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+                    Space::with_height(Length::Fixed(5.0)),
+                    view_codec_choice(app),
                 ]
                 .spacing(5),
                 
@@ -149,7 +177,7 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
                         .style(iced::theme::Text::Color(app.palette().background.weak.text)),
                     Space::with_height(Length::Fixed(5.0)),
                     pick_list(
-                        vec!["auto".to_string(), "64k".to_string(), "96k".to_string(), "128k".to_string(), "192k".to_string()],
+                        vec!["auto".to_string(), "32k".to_string(), "48k".to_string(), "64k".to_string(), "96k".to_string(), "128k".to_string(), "192k".to_string()],
                         Some(app.conversion_bitrate.clone()),
                         Message::ConversionBitrateChanged
                     )
@@ -176,16 +204,14 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
             ]
             .align_items(Alignment::Center),
             
+            view_sample_rate_and_profile(app),
+            
             Space::with_height(Length::Fixed(15.0)),
             
-            checkbox(
-                "Normalize Volume (slower, re-encodes)",
-                app.conversion_normalize_volume
-            )
-            .style(iced::theme::Checkbox::Custom(Box::new(crate::ui::theme::ThemedCheckbox(app.theme_id))))
-            .on_toggle(Message::ConversionNormalizeVolumeToggled)
-            .size(18)
-            .text_size(14),
+            view_encode_strategy(app),
+            view_normalization_settings(app),
+            view_trim_settings(app),
+            view_split_settings(app),
             
             text(note_text)
                 .size(11)
@@ -230,6 +256,10 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
                     .align_items(Alignment::Center)
                 ])
             },
+            checkbox("Write Audiobookshelf sidecars (metadata.json, metadata.opf) next to the book", app.conversion_write_sidecars)
+                .on_toggle(Message::ConversionWriteSidecarsToggled)
+                .style(iced::theme::Checkbox::Custom(Box::new(crate::ui::theme::ThemedCheckbox(app.theme_id))))
+                .text_size(12),
         ]
         .spacing(10),
     )
@@ -282,7 +312,19 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
     };
 
     // Action buttons display
-    let action_display: Element<'_, Message> = if app.is_converting {
+    let action_display: Element<'_, Message> = if app.is_checking_inputs && app.convert_after_check {
+        column![
+            text("Checking input files...")
+                .size(20)
+                .style(iced::theme::Text::Color(app.palette().success.base.color)),
+            text("Every file is decoded once before converting")
+                .size(12)
+                .style(iced::theme::Text::Color(app.palette().secondary.base.text)),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .into()
+    } else if app.is_converting {
         column![
             text("Converting...")
                 .size(20)
@@ -295,10 +337,20 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
         .align_items(Alignment::Center)
         .into()
     } else if let Some(ref error) = app.conversion_error {
+        // Missing ffmpeg is not fixable by retrying; tell the user how to install it
+        let hint: Element<'_, Message> = if error.is_ffmpeg_missing() {
+            text(FFMPEG_INSTALL_HINT)
+                .size(12)
+                .style(iced::theme::Text::Color(app.palette().background.weak.text))
+                .into()
+        } else {
+            Space::with_height(Length::Shrink).into()
+        };
         column![
-            text(format!("Error: {}", error))
+            text(format!("Conversion failed: {}", error))
                 .size(14)
                 .style(iced::theme::Text::Color(app.palette().danger.base.color)),
+            hint,
             Space::with_height(Length::Fixed(10.0)),
             button("Try Again")
                 .on_press(Message::StartConversion)
@@ -345,7 +397,7 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
                                     .style(iced::theme::Text::Color(app.palette().background.base.text)),
                                 Space::with_height(Length::Fixed(10.0)),
                                 text(format!("Title: {}", app.metadata.editing_title)).size(13).style(iced::theme::Text::Color(app.palette().background.weak.text)),
-                                text(format!("Author: {}", app.metadata.editing_author)).size(13).style(iced::theme::Text::Color(app.palette().background.weak.text)),
+                                text(format!("Author: {}", app.metadata.editing_authors.join(", "))).size(13).style(iced::theme::Text::Color(app.palette().background.weak.text)),
                                 text(format!("Chapters: {}", app.chapters.chapters.len())).size(13).style(iced::theme::Text::Color(app.palette().background.weak.text)),
                             ]
                             .spacing(8)
@@ -361,6 +413,8 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
                     output_location,
                     Space::with_height(Length::Fixed(15.0)),
                     source_summary,
+                    Space::with_height(Length::Fixed(15.0)),
+                    view_input_check(app),
                     
                     Space::with_height(Length::Fixed(40.0)),
                     
@@ -384,3 +438,549 @@ pub fn view_convert(app: &Lectern) -> Element<'_, Message> {
     .height(Length::Fill)
     .into()
 }
+
+/// Normalization strategy picker plus the parameters of the selected strategy.
+fn view_normalization_settings(app: &Lectern) -> Element<'_, Message> {
+    let param_input = |label: &'static str, value: &str, param: NormalizationParam| {
+        column![
+            text(label)
+                .size(12)
+                .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+            text_input("", value)
+                .on_input(move |v| Message::ConversionNormalizationParamChanged(param, v))
+                .padding(6)
+                .width(Length::Fixed(100.0)),
+        ]
+        .spacing(4)
+    };
+
+    let (params, hint): (Element<'_, Message>, &str) = match app.conversion_normalization {
+        NormalizationMode::None => (
+            Space::with_height(Length::Fixed(0.0)).into(),
+            "No volume processing.",
+        ),
+        NormalizationMode::SpeechNorm => (
+            row![
+                param_input("Expansion", &app.speechnorm_expansion, NormalizationParam::SpeechnormExpansion),
+                param_input("Raise", &app.speechnorm_raise, NormalizationParam::SpeechnormRaise),
+                param_input("Peak", &app.speechnorm_peak, NormalizationParam::SpeechnormPeak),
+            ]
+            .spacing(15)
+            .into(),
+            "Dynamic speech normalizer (re-encodes). Lower expansion pumps less on quiet narrators.",
+        ),
+        NormalizationMode::Loudnorm => (
+            row![
+                param_input("Target (LUFS)", &app.loudnorm_target, NormalizationParam::LoudnormTarget),
+                param_input("True peak (dBTP)", &app.loudnorm_true_peak, NormalizationParam::LoudnormTruePeak),
+                param_input("Range (LU)", &app.loudnorm_range, NormalizationParam::LoudnormRange),
+            ]
+            .spacing(15)
+            .into(),
+            "Measures the whole book first, then applies constant gain (two passes, slower).",
+        ),
+    };
+
+    column![
+        row![
+            text("Normalization")
+                .size(14)
+                .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+            pick_list(
+                &NormalizationMode::ALL[..],
+                Some(app.conversion_normalization),
+                Message::ConversionNormalizationChanged
+            )
+            .width(Length::Fixed(200.0)),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        params,
+        text(hint)
+            .size(11)
+            .style(iced::theme::Text::Color(app.palette().secondary.base.text)),
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Why the audio was stream copied or re-encoded.
+fn view_plan_result(app: &Lectern) -> Element<'_, Message> {
+    let Some(report) = app.conversion_report.as_ref() else {
+        return Space::with_height(Length::Fixed(0.0)).into();
+    };
+    row![
+        text("Audio:")
+            .size(12)
+            .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+        Space::with_width(Length::Fixed(10.0)),
+        text(report.plan.to_string())
+            .size(12)
+            .style(iced::theme::Text::Color(if report.plan.is_copy() {
+                app.palette().success.base.color
+            } else {
+                app.palette().background.base.text
+            })),
+    ]
+    .into()
+}
+
+/// Pass/fail checks of the written file(s) from the last conversion.
+fn view_verification_result(app: &Lectern) -> Element<'_, Message> {
+    let Some(report) = app.conversion_report.as_ref() else {
+        return Space::with_height(Length::Fixed(0.0)).into();
+    };
+    let verification = &report.verification;
+    let (heading, heading_color) = if verification.passed() {
+        ("Verification passed", app.palette().success.base.color)
+    } else {
+        ("Verification failed", app.palette().danger.base.color)
+    };
+    let mut checks = column![
+        Space::with_height(Length::Fixed(5.0)),
+        text(heading).size(14).style(iced::theme::Text::Color(heading_color)),
+    ]
+    .spacing(4);
+    for check in &verification.checks {
+        let (mark, color) = if check.passed {
+            ("OK", app.palette().success.base.color)
+        } else {
+            ("FAIL", app.palette().danger.base.color)
+        };
+        checks = checks.push(
+            row![
+                text(mark).size(12).style(iced::theme::Text::Color(color)).width(Length::Fixed(36.0)),
+                text(check.name)
+                    .size(12)
+                    .style(iced::theme::Text::Color(app.palette().background.weak.text))
+                    .width(Length::Fixed(80.0)),
+                text(&check.detail).size(12),
+            ]
+            .spacing(6),
+        );
+    }
+    checks.into()
+}
+
+/// Audio cut from the start/end by the last conversion, if any.
+fn view_trim_result(app: &Lectern) -> Element<'_, Message> {
+    let Some(trim) = app.conversion_report.as_ref().and_then(|r| r.trim) else {
+        return Space::with_height(Length::Fixed(0.0)).into();
+    };
+    row![
+        text("Trimmed:")
+            .size(12)
+            .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+        Space::with_width(Length::Fixed(10.0)),
+        text(format!(
+            "{:.1} s from the start, {:.1} s from the end",
+            trim.start_ms as f64 / 1000.0,
+            trim.total_ms.saturating_sub(trim.end_ms) as f64 / 1000.0
+        ))
+        .size(12),
+    ]
+    .into()
+}
+
+/// Loudness measured by the first loudnorm pass of the last conversion, if any.
+fn view_loudness_result(app: &Lectern) -> Element<'_, Message> {
+    let Some(report) = app.conversion_report.as_ref() else {
+        return Space::with_height(Length::Fixed(0.0)).into();
+    };
+    let (Some(measured), Normalization::Loudnorm { target_i, true_peak, .. }) = (report.loudness.as_ref(), report.normalization) else {
+        return Space::with_height(Length::Fixed(0.0)).into();
+    };
+    row![
+        text("Loudness:")
+            .size(12)
+            .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+        Space::with_width(Length::Fixed(10.0)),
+        text(format!(
+            "measured {:.1} LUFS, {:.1} dBTP peak, {:.1} LU range → target {:.1} LUFS / {:.1} dBTP",
+            measured.input_i, measured.input_tp, measured.input_lra, target_i, true_peak
+        ))
+        .size(12),
+    ]
+    .into()
+}
+
+/// Preset picker and "Save as preset" for the current settings.
+fn view_presets(app: &Lectern) -> Element<'_, Message> {
+    let names: Vec<String> = app.conversion_presets.iter().map(|p| p.name.clone()).collect();
+    let status: Element<'_, Message> = match app.conversion_preset_status {
+        Some(ref status) => text(status)
+            .size(11)
+            .style(iced::theme::Text::Color(app.palette().secondary.base.text))
+            .into(),
+        None => Space::with_height(Length::Fixed(0.0)).into(),
+    };
+    column![
+        row![
+            text("Preset")
+                .size(14)
+                .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+            pick_list(names, app.conversion_preset.clone(), Message::ConversionPresetSelected)
+                .placeholder("Custom")
+                .width(Length::Fixed(260.0)),
+            Space::with_width(Length::Fixed(10.0)),
+            text_input("Preset name", &app.conversion_preset_name)
+                .on_input(Message::ConversionPresetNameChanged)
+                .on_submit(Message::SaveConversionPreset)
+                .padding(6)
+                .width(Length::Fixed(180.0)),
+            button("Save as preset")
+                .on_press(Message::SaveConversionPreset)
+                .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id)))
+                .padding([6, 12]),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        status,
+    ]
+    .spacing(6)
+    .into()
+}
+
+/// Sample rate picker, plus the AAC profile when encoding M4B with AAC.
+fn view_sample_rate_and_profile(app: &Lectern) -> Element<'_, Message> {
+    let sample_rate = column![
+        text("Sample rate")
+            .size(14)
+            .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+        Space::with_height(Length::Fixed(5.0)),
+        pick_list(
+            vec!["auto".to_string(), "22050".to_string(), "24000".to_string(), "32000".to_string(), "44100".to_string(), "48000".to_string()],
+            Some(app.conversion_sample_rate.clone()),
+            Message::ConversionSampleRateChanged
+        )
+        .width(Length::Fixed(120.0)),
+    ]
+    .spacing(5);
+    if app.conversion_format != OutputFormat::M4b || app.conversion_codec != "aac" {
+        return row![sample_rate].into();
+    }
+    let hint = match (app.conversion_aac_profile, app.fdk_aac_available) {
+        (AacProfile::Lc, _) => "",
+        (_, Some(false)) => "libfdk_aac not found in ffmpeg: will fall back to AAC-LC",
+        (AacProfile::HeV1, _) => "Best for speech at 32-64 kbps (needs libfdk_aac)",
+        (AacProfile::HeV2, _) => "Stereo only, for very low bitrates (needs libfdk_aac)",
+    };
+    let hint_color = if app.fdk_aac_available == Some(false) {
+        colors::WARNING
+    } else {
+        app.palette().secondary.base.text
+    };
+    row![
+        sample_rate,
+        Space::with_width(Length::Fixed(20.0)),
+        column![
+            text("AAC profile")
+                .size(14)
+                .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+            Space::with_height(Length::Fixed(5.0)),
+            pick_list(
+                &AacProfile::ALL[..],
+                Some(app.conversion_aac_profile),
+                Message::ConversionAacProfileChanged
+            )
+            .width(Length::Fixed(140.0)),
+        ]
+        .spacing(5),
+        Space::with_width(Length::Fixed(10.0)),
+        text(hint).size(11).style(iced::theme::Text::Color(hint_color)),
+    ]
+    .align_items(Alignment::End)
+    .into()
+}
+
+/// Input health check: button, summary and a row per file with issues (skip toggle for each).
+fn view_input_check(app: &Lectern) -> Element<'_, Message> {
+    let check_button = if app.is_checking_inputs {
+        button("Checking...")
+            .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id)))
+            .padding([8, 15])
+    } else {
+        button("Check inputs")
+            .on_press(Message::CheckInputs)
+            .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id)))
+            .padding([8, 15])
+    };
+    let summary = match app.input_checks.as_ref() {
+        None => "Decode every input once to find corrupt or truncated files before converting".to_string(),
+        Some(checks) => {
+            let with_issues = checks.iter().filter(|c| !c.issues.is_empty()).count();
+            if with_issues == 0 {
+                format!("All {} file(s) look fine", checks.len())
+            } else {
+                format!(
+                    "{} of {} file(s) have issues, {} skipped",
+                    with_issues,
+                    checks.len(),
+                    app.input_check_skipped.len()
+                )
+            }
+        }
+    };
+    let mut content = column![
+        row![
+            text("Input Check")
+                .size(16)
+                .style(iced::theme::Text::Color(app.palette().background.base.text)),
+            Space::with_width(Length::Fill),
+            check_button,
+        ]
+        .align_items(Alignment::Center),
+        text(summary)
+            .size(12)
+            .style(iced::theme::Text::Color(app.palette().secondary.base.text)),
+    ]
+    .spacing(8);
+
+    let Some(checks) = app.input_checks.as_ref() else {
+        return container(content).padding(20).style(iced::theme::Container::Box).into();
+    };
+    for check in checks.iter().filter(|c| !c.issues.is_empty()) {
+        let filename = std::path::Path::new(&check.path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&check.path)
+            .to_string();
+        let duration = check.duration_ms
+            .map(|ms| crate::utils::time::format_time(ms, false))
+            .unwrap_or_else(|| "-".to_string());
+        let params = match (check.sample_rate, check.channels) {
+            (Some(rate), Some(channels)) => format!("{} Hz, {} ch", rate, channels),
+            _ => "-".to_string(),
+        };
+        let issue_color = if check.has_errors() {
+            app.palette().danger.base.color
+        } else {
+            colors::WARNING
+        };
+        let issues = check.issues.iter().map(|i| i.to_string()).collect::<Vec<_>>().join("\n");
+        let path = check.path.clone();
+        content = content.push(
+            row![
+                checkbox("Skip", app.input_check_skipped.contains(&check.path))
+                    .on_toggle(move |skip| Message::InputCheckSkipToggled(path.clone(), skip))
+                    .style(iced::theme::Checkbox::Custom(Box::new(crate::ui::theme::ThemedCheckbox(app.theme_id))))
+                    .text_size(12)
+                    .width(Length::Fixed(70.0)),
+                column![
+                    text(filename).size(12).style(iced::theme::Text::Color(app.palette().background.base.text)),
+                    text(format!("{} · {} · {}", duration, params, crate::utils::format::format_size(check.size)))
+                        .size(11)
+                        .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+                ]
+                .spacing(2)
+                .width(Length::FillPortion(2)),
+                text(issues)
+                    .size(11)
+                    .style(iced::theme::Text::Color(issue_color))
+                    .width(Length::FillPortion(3)),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+        );
+    }
+    if !app.input_check_skipped.is_empty() {
+        content = content.push(
+            text("Skipped files are left out of the audio; re-map chapters from files so chapter times stay in sync")
+                .size(11)
+                .style(iced::theme::Text::Color(colors::WARNING)),
+        );
+    }
+    container(content).padding(20).style(iced::theme::Container::Box).into()
+}
+
+/// Encoder fallbacks taken by the last conversion.
+fn view_encoder_warnings(app: &Lectern) -> Element<'_, Message> {
+    let Some(report) = app.conversion_report.as_ref().filter(|r| !r.warnings.is_empty()) else {
+        return Space::with_height(Length::Fixed(0.0)).into();
+    };
+    let mut warnings = column![].spacing(4);
+    for warning in &report.warnings {
+        warnings = warnings.push(text(warning).size(12).style(iced::theme::Text::Color(colors::WARNING)));
+    }
+    warnings.into()
+}
+
+/// Codec picker for M4B; the other formats have a fixed codec.
+fn view_codec_choice(app: &Lectern) -> Element<'_, Message> {
+    let fixed = match app.conversion_format {
+        OutputFormat::M4b => {
+            return pick_list(
+                vec!["aac".to_string(), "copy".to_string(), "opus".to_string()],
+                Some(app.conversion_codec.clone()),
+                Message::ConversionCodecChanged
+            )
+            .width(Length::Fixed(120.0))
+            .into();
+        }
+        OutputFormat::OggOpus => "Opus",
+        OutputFormat::Mp3 => "MP3 (LAME)",
+        OutputFormat::Flac => "FLAC (lossless)",
+    };
+    container(text(fixed).size(14))
+        .width(Length::Fixed(120.0))
+        .padding([5, 0])
+        .into()
+}
+
+/// Encoding strategy picker (only matters for multi-file books encoded to AAC).
+fn view_encode_strategy(app: &Lectern) -> Element<'_, Message> {
+    let hint = match app.conversion_encode_strategy {
+        EncodeStrategy::SingleProcess => "One ffmpeg process encodes all files in order.",
+        EncodeStrategy::ParallelFiles => "Encodes each file to AAC on its own CPU core, then joins them without re-encoding. Applies to multi-file books with the AAC codec.",
+    };
+    column![
+        row![
+            text("Encoding")
+                .size(14)
+                .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+            pick_list(
+                &EncodeStrategy::ALL[..],
+                Some(app.conversion_encode_strategy),
+                Message::ConversionEncodeStrategyChanged
+            )
+            .width(Length::Fixed(200.0)),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        text(hint)
+            .size(11)
+            .style(iced::theme::Text::Color(app.palette().secondary.base.text)),
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Start/end trim picker, the seconds to cut, and leading/trailing silence removal.
+fn view_trim_settings(app: &Lectern) -> Element<'_, Message> {
+    let amounts: Element<'_, Message> = match app.conversion_trim_mode {
+        TrimMode::None => Space::with_width(Length::Fixed(0.0)).into(),
+        TrimMode::Manual => row![
+            text("Start").size(12),
+            text_input("0", &app.conversion_trim_start)
+                .on_input(Message::ConversionTrimStartChanged)
+                .padding(6)
+                .width(Length::Fixed(80.0)),
+            text("End").size(12),
+            text_input("0", &app.conversion_trim_end)
+                .on_input(Message::ConversionTrimEndChanged)
+                .padding(6)
+                .width(Length::Fixed(80.0)),
+            text("s").size(12),
+        ]
+        .spacing(6)
+        .align_items(Alignment::Center)
+        .into(),
+        TrimMode::AudibleBrand => match app.chapters.brand_ms {
+            Some((intro, outro)) => text(format!("Intro {:.1} s, outro {:.1} s (Audnexus)", intro as f64 / 1000.0, outro as f64 / 1000.0))
+                .size(12)
+                .into(),
+            None => text("Unknown: look up chapters from Audible first")
+                .size(12)
+                .style(iced::theme::Text::Color(colors::WARNING))
+                .into(),
+        },
+    };
+    column![
+        row![
+            text("Trim")
+                .size(14)
+                .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+            pick_list(
+                &TrimMode::ALL[..],
+                Some(app.conversion_trim_mode),
+                Message::ConversionTrimModeChanged
+            )
+            .width(Length::Fixed(200.0)),
+            amounts,
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        checkbox("Also trim leading/trailing silence", app.conversion_trim_silence)
+            .on_toggle(Message::ConversionTrimSilenceToggled)
+            .style(iced::theme::Checkbox::Custom(Box::new(crate::ui::theme::ThemedCheckbox(app.theme_id))))
+            .text_size(12),
+        text("Trimmed audio is removed from the file; chapter times are shifted to match.")
+            .size(11)
+            .style(iced::theme::Text::Color(app.palette().secondary.base.text)),
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Split-into-parts picker and limit.
+fn view_split_settings(app: &Lectern) -> Element<'_, Message> {
+    let limit: Element<'_, Message> = if app.conversion_split_by == SplitBy::None {
+        Space::with_width(Length::Fixed(0.0)).into()
+    } else {
+        text_input("", &app.conversion_split_limit)
+            .on_input(Message::ConversionSplitLimitChanged)
+            .padding(6)
+            .width(Length::Fixed(100.0))
+            .into()
+    };
+    column![
+        row![
+            text("Parts")
+                .size(14)
+                .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+            pick_list(
+                &SplitBy::ALL[..],
+                Some(app.conversion_split_by),
+                Message::ConversionSplitByChanged
+            )
+            .width(Length::Fixed(200.0)),
+            limit,
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        text("Parts are cut on chapter boundaries; use {Part} in the file template to name them.")
+            .size(11)
+            .style(iced::theme::Text::Color(app.palette().secondary.base.text)),
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Files written by the last conversion when it was split into parts.
+fn view_parts_result(app: &Lectern) -> Element<'_, Message> {
+    let Some(report) = app.conversion_report.as_ref().filter(|r| r.output_paths.len() > 1) else {
+        return Space::with_height(Length::Fixed(0.0)).into();
+    };
+    let mut parts = column![
+        text(format!("Parts ({}):", report.output_paths.len()))
+            .size(12)
+            .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+    ]
+    .spacing(4);
+    for path in &report.output_paths {
+        let name = std::path::Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(path);
+        parts = parts.push(text(name).size(12));
+    }
+    parts.into()
+}
+
+/// Sidecar files written by the last conversion.
+fn view_sidecars_result(app: &Lectern) -> Element<'_, Message> {
+    let Some(report) = app.conversion_report.as_ref().filter(|r| !r.sidecars.is_empty()) else {
+        return Space::with_height(Length::Fixed(0.0)).into();
+    };
+    let names: Vec<&str> = report.sidecars.iter()
+        .map(|p| std::path::Path::new(p).file_name().and_then(|n| n.to_str()).unwrap_or(p))
+        .collect();
+    row![
+        text("Sidecars:")
+            .size(12)
+            .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+        Space::with_width(Length::Fixed(10.0)),
+        text(names.join(", ")).size(12),
+    ]
+    .into()
+}
@@ -1,5 +1,7 @@
+use crate::services::cover_edit::CoverCrop;
+use crate::ui::state::CropSide;
 use crate::ui::{Message, Lectern};
-use iced::widget::{button, column, container, row, scrollable, text, text_input, Column, Space, image};
+use iced::widget::{button, column, container, pick_list, row, scrollable, slider, text, text_input, Column, Space, image};
 use iced::{Alignment, Element, Length};
 
 pub fn view_cover(app: &Lectern) -> Element<'_, Message> {
@@ -141,6 +143,25 @@ pub fn view_cover(app: &Lectern) -> Element<'_, Message> {
             ]
             .spacing(15)
             .align_items(Alignment::Center),
+            text("Or take images from a folder or a web page (e.g. the publisher's book page):")
+                .size(14)
+                .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+            row![
+                button("Search Folder...")
+                    .on_press(Message::BrowseCoverFolder)
+                    .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id)))
+                    .padding([10, 16]),
+                text_input("Page URL", &app.cover.cover_page_url)
+                    .on_input(Message::CoverPageUrlChanged)
+                    .on_submit(Message::SearchCoverPage)
+                    .padding(10),
+                button("Find Images")
+                    .on_press_maybe((!app.cover.cover_page_url.trim().is_empty()).then_some(Message::SearchCoverPage))
+                    .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id)))
+                    .padding([10, 16]),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
             if let Some(ref error) = app.cover.cover_search_error {
                 text(format!("Error: {}", error))
                     .size(14)
@@ -155,7 +176,7 @@ pub fn view_cover(app: &Lectern) -> Element<'_, Message> {
         let mut cover_results_column = Column::new();
         if !app.cover.cover_search_results.is_empty() {
             cover_results_column = cover_results_column.push(
-                text(format!("Found {} cover results:", app.cover.cover_search_results.len()))
+                text(format!("Found {} different covers (largest and squarest first):", app.cover.cover_search_results.len()))
                     .size(16)
                     .style(iced::theme::Text::Color(app.palette().background.base.text))
             );
@@ -195,7 +216,18 @@ pub fn view_cover(app: &Lectern) -> Element<'_, Message> {
                                 text(&cover.source)
                                     .size(14)
                                     .style(iced::theme::Text::Color(iced::Color::from_rgb(0.7, 0.7, 0.7))),
-                                text(format!("{}x{}", cover.width, cover.height))
+                                text(if cover.width == 0 {
+                                    "Size unknown".to_string()
+                                } else {
+                                    format!("{}x{}", cover.width, cover.height)
+                                })
+                                    .size(12)
+                                    .style(iced::theme::Text::Color(iced::Color::from_rgb(0.6, 0.6, 0.6))),
+                                text(if cover.also_from.is_empty() {
+                                    String::new()
+                                } else {
+                                    format!("Also from {}", cover.also_from.join(", "))
+                                })
                                     .size(12)
                                     .style(iced::theme::Text::Color(iced::Color::from_rgb(0.6, 0.6, 0.6))),
                             ]
@@ -247,6 +279,9 @@ pub fn view_cover(app: &Lectern) -> Element<'_, Message> {
                                     app.cover.cover_image_path.as_deref().unwrap_or(""))
                                     .on_input(Message::CoverUrlChanged)
                                     .padding(12),
+                                view_embedded_covers(app),
+                                Space::with_height(Length::Fixed(20.0)),
+                                view_cover_editor(app),
                                 Space::with_height(Length::Fixed(20.0)),
                                 cover_search_section,
                             ]
@@ -269,3 +304,201 @@ pub fn view_cover(app: &Lectern) -> Element<'_, Message> {
         .height(Length::Fill)
         .into()
 }
+
+/// Pictures embedded in the source files, each with a button to use it.
+fn view_embedded_covers(app: &Lectern) -> Element<'_, Message> {
+    let covers = &app.cover.embedded_covers;
+    if covers.is_empty() {
+        return if app.cover.is_scanning_embedded {
+            column![
+                Space::with_height(Length::Fixed(10.0)),
+                text("Looking for embedded cover art...")
+                    .size(14)
+                    .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+            ]
+            .into()
+        } else {
+            Space::with_height(Length::Fixed(0.0)).into()
+        };
+    }
+
+    let mut list = Column::new().spacing(10).push(Space::with_height(Length::Fixed(10.0))).push(
+        text(if covers.len() == 1 {
+            "Embedded cover".to_string()
+        } else {
+            format!("Embedded covers ({} different)", covers.len())
+        })
+        .size(16)
+        .style(iced::theme::Text::Color(app.palette().background.base.text)),
+    );
+    for cover in covers {
+        let in_use = app.cover.cover_image_path.as_deref() == Some(cover.path.as_str());
+        let files = match cover.files.as_slice() {
+            [only] => crate::services::embedded_cover::file_label(only),
+            [first, rest @ ..] => format!(
+                "{} and {} more file{}",
+                crate::services::embedded_cover::file_label(first),
+                rest.len(),
+                if rest.len() == 1 { "" } else { "s" }
+            ),
+            [] => String::new(),
+        };
+        let use_button = button(if in_use { "In Use" } else { "Use This" })
+            .on_press_maybe((!in_use).then(|| Message::CoverImageSelected(Some(cover.path.clone()))))
+            .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id)))
+            .padding([8, 16]);
+        list = list.push(
+            container(
+                row![
+                    image(iced::widget::image::Handle::from_path(&cover.path))
+                        .width(Length::Fixed(60.0))
+                        .height(Length::Fixed(60.0)),
+                    column![
+                        text(format!("{}x{}", cover.width, cover.height))
+                            .size(13)
+                            .style(iced::theme::Text::Color(app.palette().background.base.text)),
+                        text(files)
+                            .size(12)
+                            .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+                    ]
+                    .spacing(4)
+                    .width(Length::Fill),
+                    use_button,
+                ]
+                .spacing(12)
+                .align_items(Alignment::Center)
+                .padding(8),
+            )
+            .style(iced::theme::Container::Box)
+            .width(Length::Fill),
+        );
+    }
+    list.into()
+}
+
+/// Longest-side choices for the embedded cover.
+const COVER_SIZES: [u32; 6] = [500, 800, 1000, 1400, 2000, 3000];
+
+/// Crop/pad, size and JPEG quality of the embedded cover, with a preview of the result.
+fn view_cover_editor(app: &Lectern) -> Element<'_, Message> {
+    let label_color = app.palette().background.weak.text;
+    let header = row![
+        text("Edit Cover")
+            .size(18)
+            .style(iced::theme::Text::Color(app.palette().background.base.text)),
+        Space::with_width(Length::Fill),
+        button(if app.cover.show_editor { "Hide" } else { "Show" })
+            .on_press(Message::CoverEditorToggled)
+            .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id)))
+            .padding([6, 14]),
+    ]
+    .align_items(Alignment::Center);
+    if !app.cover.show_editor {
+        return header.into();
+    }
+    let edit = app.cover.edit;
+
+    let mut controls = Column::new().spacing(10).width(Length::Fill).push(
+        row![
+            column![
+                text("Shape").size(13).style(iced::theme::Text::Color(label_color)),
+                pick_list(&CoverCrop::ALL[..], Some(edit.crop), Message::CoverCropChanged).width(Length::Fixed(220.0)),
+            ]
+            .spacing(5),
+            column![
+                text("Max size (px)").size(13).style(iced::theme::Text::Color(label_color)),
+                pick_list(&COVER_SIZES[..], Some(edit.max_size).filter(|s| COVER_SIZES.contains(s)), Message::CoverMaxSizeChanged)
+                    .width(Length::Fixed(110.0)),
+            ]
+            .spacing(5),
+        ]
+        .spacing(15),
+    );
+    if edit.crop == CoverCrop::Manual {
+        let crop_box = edit.crop_box;
+        for (label, side, value) in [
+            ("Left", CropSide::Left, crop_box.left),
+            ("Top", CropSide::Top, crop_box.top),
+            ("Width", CropSide::Width, crop_box.width),
+            ("Height", CropSide::Height, crop_box.height),
+        ] {
+            controls = controls.push(
+                row![
+                    text(format!("{} {:.0}%", label, value * 100.0)).size(12).width(Length::Fixed(90.0)),
+                    slider(0.0..=1.0, value, move |v| Message::CoverCropBoxChanged(side, v))
+                        .step(0.01)
+                        .on_release(Message::RenderCoverPreview),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            );
+        }
+    }
+    controls = controls.push(
+        row![
+            text(format!("JPEG quality {}", edit.jpeg_quality)).size(12).width(Length::Fixed(90.0)),
+            slider(40..=100u8, edit.jpeg_quality, Message::CoverJpegQualityChanged)
+                .on_release(Message::RenderCoverPreview),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+    );
+
+    let status_color = app.palette().background.weak.text;
+    let preview: Element<Message> = match (&app.cover.edit_preview, &app.cover.edit_preview_error) {
+        (_, Some(error)) => text(format!("Preview failed: {}", error))
+            .size(12)
+            .style(iced::theme::Text::Color(app.palette().danger.base.color))
+            .into(),
+        (Some(preview), None) => {
+            let stale = app.cover.cover_image_path.as_deref() != Some(preview.source.as_str());
+            let mut col = column![
+                container(image(preview.handle.clone()).width(Length::Fixed(220.0)).height(Length::Fixed(220.0)))
+                    .style(iced::theme::Container::Box)
+                    .padding(5),
+                text(format!(
+                    "{}x{} -> {}x{} JPEG, {}",
+                    preview.source_width,
+                    preview.source_height,
+                    preview.width,
+                    preview.height,
+                    crate::utils::format::format_size(preview.bytes as u64)
+                ))
+                .size(12)
+                .style(iced::theme::Text::Color(status_color)),
+            ]
+            .spacing(6)
+            .align_items(Alignment::Center);
+            if stale {
+                col = col.push(
+                    button(text("Cover changed - update preview").size(12))
+                        .on_press(Message::RenderCoverPreview)
+                        .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id)))
+                        .padding([6, 12]),
+                );
+            }
+            col.into()
+        }
+        (None, None) if app.cover.is_rendering_preview => {
+            text("Rendering preview...").size(12).style(iced::theme::Text::Color(status_color)).into()
+        }
+        (None, None) => text("No cover to preview").size(12).style(iced::theme::Text::Color(status_color)).into(),
+    };
+
+    column![
+        header,
+        container(
+            row![controls, preview]
+                .spacing(20)
+                .align_items(Alignment::Start),
+        )
+        .padding(15)
+        .style(iced::theme::Container::Box)
+        .width(Length::Fill),
+        text("Applied when the cover is embedded during conversion.")
+            .size(12)
+            .style(iced::theme::Text::Color(status_color)),
+    ]
+    .spacing(10)
+    .into()
+}
@@ -0,0 +1,110 @@
+use crate::ui::{Message, Lectern};
+use crate::ui::state::MergeField;
+use iced::widget::{button, column, container, row, scrollable, text, Column, Row, Space};
+use iced::{Alignment, Element, Length};
+
+/// Longest value shown in a merge cell before it is cut off.
+const MAX_CELL_CHARS: usize = 60;
+
+pub fn view_merge(app: &Lectern) -> Element<'_, Message> {
+        use crate::ui::views::LecternView;
+        let tab_bar = app.view_tab_bar();
+
+        let header = row![
+            column![
+                text("Merge Metadata")
+                    .size(24)
+                    .style(iced::theme::Text::Color(app.palette().background.base.text)),
+                text(if app.merge.from_files {
+                    "Pick the source for each field. Defaults come from the imported files."
+                } else {
+                    "Pick the source for each field. Defaults come from Settings → Metadata Merge."
+                })
+                    .size(12)
+                    .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+            ]
+            .spacing(5),
+            Space::with_width(Length::Fill),
+            button("Cancel")
+                .on_press(Message::MergeCancel)
+                .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id)))
+                .padding([10, 20]),
+            button("Apply Merge")
+                .on_press(Message::MergeApply)
+                .style(iced::theme::Button::custom(crate::ui::theme::RoundedPrimary(app.theme_id)))
+                .padding([10, 20]),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let field_width = Length::Fixed(100.0);
+
+        // Column headings: one per source (Current first)
+        let mut heading = Row::new().spacing(8).push(
+            text("Field").size(12).width(field_width)
+                .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+        );
+        for (name, _) in &app.merge.sources {
+            heading = heading.push(
+                text(name).size(12).width(Length::FillPortion(1))
+                    .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+            );
+        }
+
+        let mut rows = Column::new().spacing(6).push(heading);
+        for field in MergeField::ALL {
+            let chosen = app.merge.choice(field);
+            let mut field_row = Row::new().spacing(8).align_items(Alignment::Center).push(
+                text(field.to_string()).size(13).width(field_width),
+            );
+            for (idx, (_, book)) in app.merge.sources.iter().enumerate() {
+                let value = field.get(book);
+                let label = match value.as_deref() {
+                    Some(v) => truncate(v),
+                    None => "—".to_string(),
+                };
+                let style = if idx == chosen {
+                    iced::theme::Button::custom(crate::ui::theme::RoundedPrimary(app.theme_id))
+                } else {
+                    iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id))
+                };
+                // Empty values can only be chosen from the current column (to keep it empty)
+                let cell = button(text(label).size(12))
+                    .style(style)
+                    .padding([6, 10])
+                    .width(Length::FillPortion(1));
+                let cell = if value.is_some() || idx == 0 {
+                    cell.on_press(Message::MergeFieldSourceSelected(field, idx))
+                } else {
+                    cell
+                };
+                field_row = field_row.push(cell);
+            }
+            rows = rows.push(field_row);
+        }
+
+        container(
+            column![
+                tab_bar,
+                header,
+                container(scrollable(rows.padding(10)))
+                    .padding(10)
+                    .style(iced::theme::Container::Box)
+                    .width(Length::Fill)
+                    .height(Length::Fill),
+            ]
+            .spacing(20),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+fn truncate(value: &str) -> String {
+    let single_line = value.replace('\n', " ");
+    if single_line.chars().count() > MAX_CELL_CHARS {
+        format!("{}…", single_line.chars().take(MAX_CELL_CHARS).collect::<String>())
+    } else {
+        single_line
+    }
+}
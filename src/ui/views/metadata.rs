@@ -1,3 +1,4 @@
+use crate::ui::state::ListField;
 use crate::ui::{Message, Lectern};
 use iced::widget::{button, checkbox, column, container, row, scrollable, text, text_input, text_editor, Column, Space};
 use iced::{Alignment, Element, Length};
@@ -123,6 +124,9 @@ pub fn view_metadata(app: &Lectern) -> Element<'_, Message> {
 
         // Metadata grid
         let fields = column![
+            // Row 0: import from metadata files in the source folder
+            view_import_files(app),
+            
             // Row 1: Title
             labeled_field("Book Title", label_color, 
                 text_input("Enter book title", &app.metadata.editing_title)
@@ -141,41 +145,12 @@ pub fn view_metadata(app: &Lectern) -> Element<'_, Message> {
             
             // Row 3: Author & Narrator
             row![
-                labeled_field("Author(s)", label_color, 
-                    text_input("Enter author name(s)", &app.metadata.editing_author)
-                        .on_input(Message::AuthorChanged)
-                        .padding(12)
-                ),
-                labeled_field("Narrator(s)", label_color, 
-                    text_input("Enter narrator name(s)", &app.metadata.editing_narrator)
-                        .on_input(Message::NarratorChanged)
-                        .padding(12)
-                ),
+                labeled_field("Author(s)", label_color, chip_list(app, ListField::Authors, "Add author")),
+                labeled_field("Narrator(s)", label_color, chip_list(app, ListField::Narrators, "Add narrator")),
             ].spacing(20),
             
             // Row 4: Series
-            row![
-                column![
-                    text("Series")
-                        .size(13)
-                        .style(iced::theme::Text::Color(app.palette().background.weak.text)),
-                    text_input("Series name", &app.metadata.editing_series)
-                        .on_input(Message::SeriesChanged)
-                        .padding(12),
-                ]
-                .spacing(5)
-                .width(Length::FillPortion(3)),
-                column![
-                    text("Series #")
-                        .size(13)
-                        .style(iced::theme::Text::Color(app.palette().background.weak.text)),
-                    text_input("Number", &app.metadata.editing_series_number)
-                        .on_input(Message::SeriesNumberChanged)
-                        .padding(12),
-                ]
-                .spacing(5)
-                .width(Length::FillPortion(1)),
-            ].spacing(20),
+            labeled_field("Series", label_color, series_list(app)),
             
             // Row 5: ISBN & ASIN
             row![
@@ -207,11 +182,7 @@ pub fn view_metadata(app: &Lectern) -> Element<'_, Message> {
             
             // Row 7: Genre & Language
             row![
-                labeled_field("Genre", label_color, 
-                    text_input("Genre", &app.metadata.editing_genre)
-                        .on_input(Message::GenreChanged)
-                        .padding(12)
-                ),
+                labeled_field("Genres", label_color, chip_list(app, ListField::Genres, "Add genre")),
                 labeled_field("Language", label_color,
                     text_input("Language", &app.metadata.editing_language)
                         .on_input(Message::LanguageChanged)
@@ -220,11 +191,7 @@ pub fn view_metadata(app: &Lectern) -> Element<'_, Message> {
             ].spacing(20),
             
             // Row 8: Tags
-            labeled_field("Tags (comma-separated)", label_color, 
-                text_input("Tags", &app.metadata.editing_tags)
-                    .on_input(Message::TagsChanged)
-                    .padding(12)
-            ),
+            labeled_field("Tags", label_color, chip_list(app, ListField::Tags, "Add tag")),
             
             // Row 9: Checkboxes
             row![
@@ -278,3 +245,113 @@ pub fn view_metadata(app: &Lectern) -> Element<'_, Message> {
         .height(Length::Fill)
         .into()
 }
+
+/// Chips shown per row (iced rows do not wrap).
+const CHIPS_PER_ROW: usize = 4;
+
+/// One removable entry: its label and an "x" button.
+fn chip<'a>(app: &Lectern, label: String, on_remove: Message) -> Element<'a, Message> {
+    container(
+        row![
+            text(label).size(13),
+            button(text("x").size(11))
+                .on_press(on_remove)
+                .padding([1, 6])
+                .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id))),
+        ]
+        .spacing(6)
+        .align_items(Alignment::Center),
+    )
+    .padding([4, 4, 4, 10])
+    .style(iced::theme::Container::Box)
+    .into()
+}
+
+/// Existing chips in rows, then the input that adds more (Enter, "," or the Add button).
+fn chips_with_input<'a>(chips: Vec<Element<'a, Message>>, input: Element<'a, Message>) -> Element<'a, Message> {
+    let mut col = Column::new().spacing(6);
+    let mut chips = chips.into_iter().peekable();
+    while chips.peek().is_some() {
+        col = col.push(row(chips.by_ref().take(CHIPS_PER_ROW).collect::<Vec<_>>()).spacing(6));
+    }
+    col.push(input).into()
+}
+
+fn chip_list<'a>(app: &'a Lectern, field: ListField, placeholder: &str) -> Element<'a, Message> {
+    let chips = app.metadata.list(field).iter().enumerate()
+        .map(|(i, entry)| chip(app, entry.clone(), Message::ListItemRemoved(field, i)))
+        .collect();
+    let input = row![
+        text_input(placeholder, app.metadata.input(field))
+            .on_input(move |value| Message::ListInputChanged(field, value))
+            .on_submit(Message::ListInputSubmitted(field))
+            .padding(12),
+        button(text("Add").size(13))
+            .on_press(Message::ListInputSubmitted(field))
+            .padding([10, 14])
+            .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id))),
+    ]
+    .spacing(6)
+    .align_items(Alignment::Center);
+    chips_with_input(chips, input.into())
+}
+
+fn series_list(app: &Lectern) -> Element<'_, Message> {
+    let chips = app.metadata.editing_series.iter().enumerate()
+        .map(|(i, series)| chip(app, series.to_string(), Message::SeriesRemoved(i)))
+        .collect();
+    let input = row![
+        text_input("Series name", &app.metadata.series_name_input)
+            .on_input(Message::SeriesNameInputChanged)
+            .on_submit(Message::SeriesAdded)
+            .padding(12)
+            .width(Length::FillPortion(3)),
+        text_input("Number", &app.metadata.series_number_input)
+            .on_input(Message::SeriesNumberInputChanged)
+            .on_submit(Message::SeriesAdded)
+            .padding(12)
+            .width(Length::FillPortion(1)),
+        button(text("Add").size(13))
+            .on_press(Message::SeriesAdded)
+            .padding([10, 14])
+            .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id))),
+    ]
+    .spacing(6)
+    .align_items(Alignment::Center);
+    chips_with_input(chips, input.into())
+}
+
+/// "Import from file" bar, shown when the source folder has OPF/NFO/JSON/description files.
+fn view_import_files(app: &Lectern) -> Element<'_, Message> {
+    let files = crate::ui::handlers::merge::importable_metadata_files(app);
+    if files.is_empty() {
+        return Space::with_height(Length::Fixed(0.0)).into();
+    }
+    let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+    let mut col = Column::new().spacing(6).push(
+        row![
+            text(format!("Metadata files in folder: {}", names.join(", ")))
+                .size(13)
+                .style(iced::theme::Text::Color(app.palette().background.weak.text))
+                .width(Length::Fill),
+            if app.metadata.is_importing_files {
+                button(text("Importing...").size(13)).padding([8, 16])
+            } else {
+                button(text("Import from file").size(13))
+                    .on_press(Message::ImportMetadataFiles)
+                    .padding([8, 16])
+            }
+            .style(iced::theme::Button::custom(crate::ui::theme::RoundedSecondary(app.theme_id))),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+    );
+    if let Some(ref error) = app.metadata.import_error {
+        col = col.push(
+            text(error)
+                .size(12)
+                .style(iced::theme::Text::Color(app.palette().danger.base.color)),
+        );
+    }
+    container(col).padding([10, 15]).style(iced::theme::Container::Box).width(Length::Fill).into()
+}
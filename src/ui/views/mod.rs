@@ -4,6 +4,7 @@ pub mod cover;
 pub mod chapters;
 pub mod convert;
 pub mod settings;
+pub mod merge;
 
 use crate::ui::{Message, Lectern};
 use iced::widget::{button, container, row, text, Space};
@@ -17,6 +18,7 @@ pub enum ViewMode {
     Chapters,
     Convert,
     Settings,
+    Merge, // Field-by-field merge of search results (opened from Search)
 }
 
 
@@ -1,6 +1,7 @@
 use crate::ui::{Message, Lectern};
 use crate::ui::state::MetadataProvider;
-use crate::models::BookMetadata;
+use crate::services::matching::RankedResult;
+use crate::ui::colors;
 use iced::widget::{button, column, container, row, scrollable, text, text_input, Column, Space, image, pick_list};
 use iced::{Alignment, Element, Length};
 
@@ -68,6 +69,17 @@ pub fn view_search(app: &Lectern) -> Element<'_, Message> {
                     text(format!("Error: {}", error)).size(16),
                     text("Check console for details").size(12)
                         .style(iced::theme::Text::Color(app.palette().secondary.base.text)),
+                    // Network/throttling failures may clear up; offer a retry for those only
+                    if error.is_retryable() {
+                        Element::from(
+                            button("Retry")
+                                .on_press(Message::PerformSearch)
+                                .style(iced::theme::Button::custom(crate::ui::theme::RoundedPrimary(app.theme_id)))
+                                .padding([10, 30]),
+                        )
+                    } else {
+                        Element::from(Space::with_height(Length::Shrink))
+                    },
                 ]
                 .spacing(10)
                 .align_items(Alignment::Center),
@@ -153,9 +165,9 @@ pub fn view_search(app: &Lectern) -> Element<'_, Message> {
             }
             
             // Display results for current page
-            for (page_idx, book) in current_page_results.iter().enumerate() {
+            for (page_idx, result) in current_page_results.iter().enumerate() {
                 let global_idx = start_idx + page_idx;
-                results_column = results_column.push(view_search_result(global_idx, book, app));
+                results_column = results_column.push(view_search_result(global_idx, result, app));
             }
             
             scrollable(
@@ -180,7 +192,8 @@ pub fn view_search(app: &Lectern) -> Element<'_, Message> {
         .into()
 }
 
-pub fn view_search_result<'a>(index: usize, book: &BookMetadata, app: &'a Lectern) -> Element<'a, Message> {
+pub fn view_search_result<'a>(index: usize, result: &RankedResult, app: &'a Lectern) -> Element<'a, Message> {
+        let book = &result.book;
         // Try to load cover image from cached handles
         let cover_display: Element<Message> = if let Some(ref cover_url) = book.cover_url {
             if let Some(handle) = app.search.result_covers.get(cover_url) {
@@ -232,11 +245,11 @@ pub fn view_search_result<'a>(index: usize, book: &BookMetadata, app: &'a Lecter
                         .size(18)
                         .style(iced::theme::Text::Color(app.palette().background.base.text)),
                     Space::with_height(Length::Fixed(5.0)),
-                    text(&book.author)
+                    text(book.author_display())
                         .size(14)
                         .style(iced::theme::Text::Color(app.palette().background.weak.text)),
                     Space::with_height(Length::Fixed(8.0)),
-                    if let Some(ref narrator) = book.narrator {
+                    if let Some(narrator) = book.narrator_display() {
                         text(format!("Narrated by: {}", narrator))
                             .size(12)
                             .style(iced::theme::Text::Color(app.palette().secondary.base.text))
@@ -250,16 +263,44 @@ pub fn view_search_result<'a>(index: usize, book: &BookMetadata, app: &'a Lecter
                     } else {
                         text("").size(12)
                     },
+                    text(format!("Source: {}", result.providers.join(" + ")))
+                        .size(12)
+                        .style(iced::theme::Text::Color(app.palette().background.weak.text)),
                 ]
                 .spacing(3)
                 .width(Length::Fill)
                 .align_items(Alignment::Start),
                 
-                // "Use This" button
-                button("Use This")
-                    .on_press(Message::SelectBook(index))
-                    .style(iced::theme::Button::Primary)
-                    .padding([12, 20]),
+                // Match score against the local book
+                column![
+                    text(format!("{}%", result.score))
+                        .size(20)
+                        .style(iced::theme::Text::Color(if result.score >= 80 {
+                            app.palette().success.base.color
+                        } else if result.score >= 50 {
+                            colors::WARNING
+                        } else {
+                            app.palette().background.weak.text
+                        })),
+                    text("match")
+                        .size(11)
+                        .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+                ]
+                .align_items(Alignment::Center),
+                
+                // "Use This" replaces the whole book; "Merge…" picks fields one by one
+                column![
+                    button("Use This")
+                        .on_press(Message::SelectBook(index))
+                        .style(iced::theme::Button::Primary)
+                        .padding([12, 20]),
+                    button("Merge…")
+                        .on_press(Message::OpenMerge(index))
+                        .style(iced::theme::Button::Secondary)
+                        .padding([8, 20]),
+                ]
+                .spacing(8)
+                .align_items(Alignment::Center),
             ]
             .spacing(20)
             .align_items(Alignment::Center)
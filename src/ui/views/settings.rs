@@ -1,7 +1,9 @@
 use crate::ui::theme::ThemeId;
+use crate::ui::state::MergeField;
+use crate::ui::state::merge_state::PREFERENCE_SOURCES;
 use crate::ui::{Message, Lectern};
 use iced::widget::container::{Appearance as ContainerAppearance, StyleSheet as ContainerStyleSheet};
-use iced::widget::{button, column, container, row, scrollable, text, text_input, toggler, Space, pick_list};
+use iced::widget::{button, column, container, row, scrollable, text, text_input, toggler, Column, Space, pick_list};
 use iced::{Alignment, Background, Element, Length};
 
 pub fn view_settings(app: &Lectern) -> Element<'_, Message> {
@@ -165,6 +167,9 @@ pub fn view_settings(app: &Lectern) -> Element<'_, Message> {
                             text("SeriesNumber suffix (only when set): {SeriesNumber-} → \"4-\", {SeriesNumber.} → \"4.\", {SeriesNumber } → \"4 \"")
                                 .size(11)
                                 .style(iced::theme::Text::Color(app.palette().secondary.base.text)),
+                            text("{Part} → \"Part 1\", \"Part 2\", … when splitting into parts (removed for single files)")
+                                .size(11)
+                                .style(iced::theme::Text::Color(app.palette().secondary.base.text)),
                             Space::with_height(Length::Fixed(10.0)),
                             text_input(
                                 "Template (e.g., {Author}/{Title}.m4b)",
@@ -178,6 +183,91 @@ pub fn view_settings(app: &Lectern) -> Element<'_, Message> {
                     .padding(16)
                     .style(iced::theme::Container::Box),
                     Space::with_height(Length::Fixed(10.0)),
+                    // Metadata Merge section
+                    container(
+                        column![
+                            text("Metadata Merge")
+                                .size(18)
+                                .style(iced::theme::Text::Color(app.palette().background.base.text)),
+                            Space::with_height(Length::Fixed(10.0)),
+                            text("Preferred source per field when merging search results (falls back to Current, then any source with a value)")
+                                .size(12)
+                                .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+                            Space::with_height(Length::Fixed(10.0)),
+                            view_merge_preferences(app),
+                        ]
+                        .spacing(10),
+                    )
+                    .padding(16)
+                    .style(iced::theme::Container::Box),
+                    Space::with_height(Length::Fixed(10.0)),
+                    // Cache section
+                    container(
+                        column![
+                            text("Cache")
+                                .size(18)
+                                .style(iced::theme::Text::Color(app.palette().background.base.text)),
+                            Space::with_height(Length::Fixed(10.0)),
+                            text("Provider responses and cover images are cached on disk (~/.cache/lectern)")
+                                .size(12)
+                                .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+                            Space::with_height(Length::Fixed(10.0)),
+                            row![
+                                toggler(
+                                    Some("Cache responses".to_string()),
+                                    app.cache_policy.enabled,
+                                    Message::CacheEnabledToggled,
+                                )
+                                .spacing(10.0)
+                                .width(Length::Shrink),
+                                toggler(
+                                    Some("Offline mode (serve only from cache)".to_string()),
+                                    app.cache_policy.offline,
+                                    Message::CacheOfflineToggled,
+                                )
+                                .spacing(10.0)
+                                .width(Length::Shrink),
+                            ]
+                            .spacing(30)
+                            .align_items(Alignment::Center),
+                            row![
+                                column![
+                                    text("Keep entries (days)")
+                                        .size(12)
+                                        .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+                                    text_input("7", &app.cache_ttl_input)
+                                        .on_input(Message::CacheTtlDaysChanged)
+                                        .width(Length::Fixed(100.0))
+                                        .padding(8),
+                                ]
+                                .spacing(5),
+                                column![
+                                    text("Size limit (MB)")
+                                        .size(12)
+                                        .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+                                    text_input("200", &app.cache_size_input)
+                                        .on_input(Message::CacheMaxSizeChanged)
+                                        .width(Length::Fixed(100.0))
+                                        .padding(8),
+                                ]
+                                .spacing(5),
+                                Space::with_width(Length::Fill),
+                                text(app.cache_status.as_deref().unwrap_or(""))
+                                    .size(12)
+                                    .style(iced::theme::Text::Color(app.palette().secondary.base.text)),
+                                button("Clear Cache")
+                                    .on_press(Message::ClearCache)
+                                    .style(iced::theme::Button::custom(crate::ui::theme::RoundedDestructive(app.theme_id)))
+                                    .padding([10, 15]),
+                            ]
+                            .spacing(20)
+                            .align_items(Alignment::End),
+                        ]
+                        .spacing(10),
+                    )
+                    .padding(16)
+                    .style(iced::theme::Container::Box),
+                    Space::with_height(Length::Fixed(10.0)),
                     // Audiobookshelf section
                     container(
                         column![
@@ -233,6 +323,39 @@ pub fn view_settings(app: &Lectern) -> Element<'_, Message> {
         .into()
 }
 
+/// Two-column grid of per-field source pickers for the merge view.
+fn view_merge_preferences(app: &Lectern) -> Element<'_, Message> {
+    let sources: Vec<String> = PREFERENCE_SOURCES.iter().map(|s| s.to_string()).collect();
+    let mut grid = Column::new().spacing(8);
+    for pair in MergeField::ALL.chunks(2) {
+        let mut line = row![].spacing(30).align_items(Alignment::Center);
+        for field in pair {
+            let field = *field;
+            let selected = app
+                .merge_preferences
+                .get(&field)
+                .cloned()
+                .unwrap_or_else(|| field.default_source().to_string());
+            line = line.push(
+                row![
+                    text(field.to_string())
+                        .size(12)
+                        .width(Length::Fixed(90.0))
+                        .style(iced::theme::Text::Color(app.palette().background.weak.text)),
+                    pick_list(sources.clone(), Some(selected), move |s| {
+                        Message::MergePreferenceChanged(field, s)
+                    })
+                    .width(Length::Fixed(150.0)),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            );
+        }
+        grid = grid.push(line);
+    }
+    grid.into()
+}
+
 /// Container style that draws a solid color (for accent swatch).
 struct AccentSwatchStyle(iced::Color);
 
@@ -1,17 +1,18 @@
 //! Parse chapter lists from external files (txt, json, cue, ini).
 //! Used when the user selects a directory; the app looks for chapter files automatically.
 
+use crate::error::{LecternError, Result};
 use crate::models::chapters::Chapter;
 use std::path::Path;
 use std::fs;
 
 /// Try to parse chapters from a file. Detects format by extension.
-pub fn parse_chapters_from_path(path: &str) -> Result<Vec<Chapter>, String> {
+pub fn parse_chapters_from_path(path: &str) -> Result<Vec<Chapter>> {
     let p = Path::new(path);
     if !p.is_file() {
-        return Err("Not a file".to_string());
+        return Err(LecternError::io(p, std::io::Error::new(std::io::ErrorKind::InvalidInput, "not a file")));
     }
-    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let content = fs::read_to_string(path).map_err(|e| LecternError::io(p, e))?;
     let ext = p
         .extension()
         .and_then(|e| e.to_str())
@@ -22,7 +23,7 @@ pub fn parse_chapters_from_path(path: &str) -> Result<Vec<Chapter>, String> {
         "json" => parse_json(&content),
         "cue" => parse_cue(&content),
         "ini" => parse_ini(&content),
-        _ => Err(format!("Unknown chapter file extension: {}", ext)),
+        _ => Err(LecternError::Parse(format!("Unknown chapter file extension: {}", ext))),
     }
 }
 
@@ -39,7 +40,7 @@ pub fn is_chapter_file_name(name: &str) -> bool {
 }
 
 /// Parse "HH:MM:SS Title" or "MM:SS Title" or "M:SS Title" per line.
-fn parse_txt(content: &str) -> Result<Vec<Chapter>, String> {
+fn parse_txt(content: &str) -> Result<Vec<Chapter>> {
     let mut chapters = Vec::new();
     for line in content.lines() {
         let line = line.trim();
@@ -56,7 +57,7 @@ fn parse_txt(content: &str) -> Result<Vec<Chapter>, String> {
             } else if c == ' ' || c == '\t' {
                 break;
             } else {
-                return Err(format!("Invalid timestamp in line: {}", line));
+                return Err(LecternError::Parse(format!("Invalid timestamp in line: {}", line)));
             }
         }
         let time_str = line[..i].trim_end_matches(|c| c == ':' || c == '.');
@@ -79,40 +80,40 @@ fn parse_txt(content: &str) -> Result<Vec<Chapter>, String> {
         chapters[i].duration = end_ms.saturating_sub(chapters[i].start_time);
     }
     if chapters.is_empty() {
-        return Err("No chapters found in file".to_string());
+        return Err(LecternError::Parse("No chapters found in file".to_string()));
     }
     Ok(chapters)
 }
 
-fn parse_timestamp_to_ms(s: &str) -> Result<u64, String> {
+fn parse_timestamp_to_ms(s: &str) -> Result<u64> {
     let parts: Vec<&str> = s.split(':').collect();
     let (h, m, sec) = match parts.len() {
         1 => {
             // seconds only
-            let sec: f64 = parts[0].trim().parse().map_err(|_| format!("Invalid number: {}", parts[0]))?;
+            let sec: f64 = parts[0].trim().parse().map_err(|_| LecternError::Parse(format!("Invalid number: {}", parts[0])))?;
             (0u64, 0u64, sec)
         }
         2 => {
-            let m: u64 = parts[0].trim().parse().map_err(|_| format!("Invalid minutes: {}", parts[0]))?;
-            let sec: f64 = parts[1].trim().parse().map_err(|_| format!("Invalid seconds: {}", parts[1]))?;
+            let m: u64 = parts[0].trim().parse().map_err(|_| LecternError::Parse(format!("Invalid minutes: {}", parts[0])))?;
+            let sec: f64 = parts[1].trim().parse().map_err(|_| LecternError::Parse(format!("Invalid seconds: {}", parts[1])))?;
             (0u64, m, sec)
         }
         3 => {
-            let h: u64 = parts[0].trim().parse().map_err(|_| format!("Invalid hours: {}", parts[0]))?;
-            let m: u64 = parts[1].trim().parse().map_err(|_| format!("Invalid minutes: {}", parts[1]))?;
-            let sec: f64 = parts[2].trim().parse().map_err(|_| format!("Invalid seconds: {}", parts[2]))?;
+            let h: u64 = parts[0].trim().parse().map_err(|_| LecternError::Parse(format!("Invalid hours: {}", parts[0])))?;
+            let m: u64 = parts[1].trim().parse().map_err(|_| LecternError::Parse(format!("Invalid minutes: {}", parts[1])))?;
+            let sec: f64 = parts[2].trim().parse().map_err(|_| LecternError::Parse(format!("Invalid seconds: {}", parts[2])))?;
             (h, m, sec)
         }
-        _ => return Err(format!("Invalid timestamp: {}", s)),
+        _ => return Err(LecternError::Parse(format!("Invalid timestamp: {}", s))),
     };
     let total_sec = (h * 3600) as f64 + (m * 60) as f64 + sec;
     Ok((total_sec * 1000.0).round() as u64)
 }
 
 /// Parse JSON array: [{ "title": "...", "start_ms": n }] or "start" in seconds.
-fn parse_json(content: &str) -> Result<Vec<Chapter>, String> {
+fn parse_json(content: &str) -> Result<Vec<Chapter>> {
     let arr: Vec<serde_json::Value> = serde_json::from_str(content)
-        .map_err(|e| format!("Invalid JSON: {}", e))?;
+        .map_err(|e| LecternError::Parse(format!("Invalid JSON: {}", e)))?;
     let mut chapters = Vec::new();
     for (i, obj) in arr.iter().enumerate() {
         let title = obj
@@ -143,7 +144,7 @@ fn parse_json(content: &str) -> Result<Vec<Chapter>, String> {
         chapters.push(Chapter::new(title, start_ms, duration_ms));
     }
     if chapters.is_empty() {
-        return Err("No chapters in JSON array".to_string());
+        return Err(LecternError::Parse("No chapters in JSON array".to_string()));
     }
     // If durations missing, fill from next start
     for i in 0..chapters.len() {
@@ -155,7 +156,7 @@ fn parse_json(content: &str) -> Result<Vec<Chapter>, String> {
 }
 
 /// CUE: TRACK nn TITLE "..." and INDEX 01 mm:ss:ff (75 frames per second).
-fn parse_cue(content: &str) -> Result<Vec<Chapter>, String> {
+fn parse_cue(content: &str) -> Result<Vec<Chapter>> {
     let mut tracks: Vec<(u64, String)> = Vec::new();
     let mut current_title = String::new();
     for line in content.lines() {
@@ -179,7 +180,7 @@ fn parse_cue(content: &str) -> Result<Vec<Chapter>, String> {
         }
     }
     if tracks.is_empty() {
-        return Err("No INDEX 01 entries in CUE file".to_string());
+        return Err(LecternError::Parse("No INDEX 01 entries in CUE file".to_string()));
     }
     let mut chapters = Vec::new();
     for (i, (start_ms, title)) in tracks.iter().enumerate() {
@@ -193,21 +194,21 @@ fn parse_cue(content: &str) -> Result<Vec<Chapter>, String> {
     Ok(chapters)
 }
 
-fn parse_cue_index_to_ms(s: &str) -> Result<u64, String> {
+fn parse_cue_index_to_ms(s: &str) -> Result<u64> {
     let parts: Vec<&str> = s.split(':').collect();
     if parts.len() != 3 {
-        return Err(format!("CUE INDEX must be mm:ss:ff, got: {}", s));
+        return Err(LecternError::Parse(format!("CUE INDEX must be mm:ss:ff, got: {}", s)));
     }
-    let m: u64 = parts[0].parse().map_err(|_| format!("Invalid minutes: {}", parts[0]))?;
-    let sec: u64 = parts[1].parse().map_err(|_| format!("Invalid seconds: {}", parts[1]))?;
-    let frames: u64 = parts[2].parse().map_err(|_| format!("Invalid frames: {}", parts[2]))?;
+    let m: u64 = parts[0].parse().map_err(|_| LecternError::Parse(format!("Invalid minutes: {}", parts[0])))?;
+    let sec: u64 = parts[1].parse().map_err(|_| LecternError::Parse(format!("Invalid seconds: {}", parts[1])))?;
+    let frames: u64 = parts[2].parse().map_err(|_| LecternError::Parse(format!("Invalid frames: {}", parts[2])))?;
     // 75 frames per second
     let total_ms = (m * 60 + sec) * 1000 + (frames * 1000 / 75);
     Ok(total_ms)
 }
 
 /// INI-style: [Chapter N] or [CHAPTER] with start= or start_time= (seconds or HH:MM:SS).
-fn parse_ini(content: &str) -> Result<Vec<Chapter>, String> {
+fn parse_ini(content: &str) -> Result<Vec<Chapter>> {
     let mut chapters = Vec::new();
     let mut current_title = String::new();
     let mut current_start_ms: Option<u64> = None;
@@ -245,7 +246,7 @@ fn parse_ini(content: &str) -> Result<Vec<Chapter>, String> {
     flush(&mut chapters, &current_title, current_start_ms.take());
 
     if chapters.is_empty() {
-        return Err("No chapters in INI file".to_string());
+        return Err(LecternError::Parse("No chapters in INI file".to_string()));
     }
     for i in 0..chapters.len() {
         if chapters[i].duration == 0 && i + 1 < chapters.len() {
@@ -255,11 +256,11 @@ fn parse_ini(content: &str) -> Result<Vec<Chapter>, String> {
     Ok(chapters)
 }
 
-fn parse_ini_start(v: &str) -> Result<u64, String> {
+fn parse_ini_start(v: &str) -> Result<u64> {
     if v.contains(':') {
         parse_timestamp_to_ms(v)
     } else {
-        let sec: f64 = v.parse().map_err(|_| format!("Invalid number: {}", v))?;
+        let sec: f64 = v.parse().map_err(|_| LecternError::Parse(format!("Invalid number: {}", v)))?;
         Ok((sec * 1000.0).round() as u64)
     }
 }
@@ -0,0 +1,355 @@
+// Chapter title cleanup
+// Rule-based rewrite of chapter titles ("01 - Track 01", "CHAPTER ONE", ...). Steps run in a
+// fixed order: strip numeric prefix, find/replace, numbering, number words, case.
+
+use regex::{Captures, Regex};
+use std::fmt;
+
+const UNITS: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+const TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+/// Lower-cased in title case unless first or after a colon.
+const SMALL_WORDS: [&str; 18] = [
+    "a", "an", "and", "as", "at", "but", "by", "for", "from", "in", "into", "nor", "of", "on", "or", "the", "to", "with",
+];
+
+/// "{n}" in the numbering template is replaced by the chapter's number.
+pub const NUMBER_PLACEHOLDER: &str = "{n}";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleNumbering {
+    Keep,
+    /// The title becomes the template ("Chapter {n}").
+    Replace,
+    /// The template goes in front of the title ("Chapter {n}: Title").
+    Prefix,
+}
+
+impl TitleNumbering {
+    pub const ALL: [TitleNumbering; 3] = [TitleNumbering::Keep, TitleNumbering::Replace, TitleNumbering::Prefix];
+}
+
+impl fmt::Display for TitleNumbering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TitleNumbering::Keep => write!(f, "No numbering"),
+            TitleNumbering::Replace => write!(f, "Replace title"),
+            TitleNumbering::Prefix => write!(f, "Prefix title"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberWords {
+    Keep,
+    /// "Chapter Twenty-One" -> "Chapter 21"
+    ToDigits,
+    /// "Chapter 21" -> "Chapter twenty-one"
+    ToWords,
+}
+
+impl NumberWords {
+    pub const ALL: [NumberWords; 3] = [NumberWords::Keep, NumberWords::ToDigits, NumberWords::ToWords];
+}
+
+impl fmt::Display for NumberWords {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberWords::Keep => write!(f, "Keep numbers"),
+            NumberWords::ToDigits => write!(f, "Words to digits"),
+            NumberWords::ToWords => write!(f, "Digits to words"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleCase {
+    Keep,
+    Title,
+    Sentence,
+}
+
+impl TitleCase {
+    pub const ALL: [TitleCase; 3] = [TitleCase::Keep, TitleCase::Title, TitleCase::Sentence];
+}
+
+impl fmt::Display for TitleCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TitleCase::Keep => write!(f, "Keep case"),
+            TitleCase::Title => write!(f, "Title Case"),
+            TitleCase::Sentence => write!(f, "Sentence case"),
+        }
+    }
+}
+
+/// The rules as edited in the Chapters tab.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleRules {
+    pub strip_prefix: bool,
+    /// Regex; empty disables find/replace. `$1` etc. refer to groups in `replace`.
+    pub find: String,
+    pub replace: String,
+    pub numbering: TitleNumbering,
+    pub template: String,
+    pub number_words: NumberWords,
+    pub case: TitleCase,
+}
+
+impl Default for TitleRules {
+    fn default() -> Self {
+        Self {
+            strip_prefix: true,
+            find: String::new(),
+            replace: String::new(),
+            numbering: TitleNumbering::Keep,
+            template: "Chapter {n}".to_string(),
+            number_words: NumberWords::Keep,
+            case: TitleCase::Keep,
+        }
+    }
+}
+
+impl TitleRules {
+    /// Compile the regexes; fails on an invalid find pattern.
+    pub fn compile(&self) -> Result<TitleTransformer, String> {
+        let find = if self.find.is_empty() {
+            None
+        } else {
+            Some(Regex::new(&self.find).map_err(|e| format!("Invalid find pattern: {}", e))?)
+        };
+        let units = UNITS.join("|");
+        let tens = TENS[2..].join("|");
+        Ok(TitleTransformer {
+            rules: self.clone(),
+            find,
+            prefix: Regex::new(r"(?i)^\s*(?:(?:chapter|chap\.?|ch\.?|track|part)\s*)?\d+\s*(?:[-_.:)\]]+\s*|\s+)")
+                .expect("valid regex"),
+            // Only a number word that is the whole title or follows chapter/part/book/track
+            // ("No One Else" keeps its "One")
+            number_word: Regex::new(&format!(
+                r"(?i)^\s*({num})\s*$|\b((?:chapter|part|book|track)\s+)({num})\b",
+                num = format!(r"(?:{})(?:[- ](?:{}))?|(?:{})", tens, units, units)
+            ))
+            .expect("valid regex"),
+            digits: Regex::new(r"\b\d+\b").expect("valid regex"),
+            word: Regex::new(r"[\p{L}\p{N}][\p{L}\p{N}'\u{2019}]*").expect("valid regex"),
+        })
+    }
+}
+
+/// Compiled `TitleRules`.
+pub struct TitleTransformer {
+    rules: TitleRules,
+    find: Option<Regex>,
+    prefix: Regex,
+    number_word: Regex,
+    digits: Regex,
+    word: Regex,
+}
+
+impl TitleTransformer {
+    /// Rewrite `title`; `number` is the chapter's 1-based number for the numbering template.
+    pub fn apply(&self, title: &str, number: usize) -> String {
+        let mut title = title.trim().to_string();
+        if self.rules.strip_prefix {
+            if let Some(m) = self.prefix.find(&title) {
+                // A title that is only a number ("Chapter 1") is left for the numbering step
+                if m.end() < title.len() {
+                    title = title[m.end()..].to_string();
+                }
+            }
+        }
+        if let Some(ref find) = self.find {
+            title = find.replace_all(&title, self.rules.replace.as_str()).to_string();
+        }
+        let numbered = self.rules.template.replace(NUMBER_PLACEHOLDER, &number.to_string());
+        title = match self.rules.numbering {
+            TitleNumbering::Keep => title,
+            TitleNumbering::Replace => numbered,
+            TitleNumbering::Prefix if title.trim().is_empty() => numbered,
+            TitleNumbering::Prefix => format!("{}: {}", numbered, title.trim()),
+        };
+        title = match self.rules.number_words {
+            NumberWords::Keep => title,
+            NumberWords::ToDigits => self.words_to_digits(&title),
+            NumberWords::ToWords => self.digits_to_words(&title),
+        };
+        title = match self.rules.case {
+            TitleCase::Keep => title,
+            TitleCase::Title => self.recase(&title, true),
+            TitleCase::Sentence => self.recase(&title, false),
+        };
+        title.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn words_to_digits(&self, title: &str) -> String {
+        self.number_word
+            .replace_all(title, |caps: &Captures| match caps.get(1) {
+                Some(whole) => number_word_value(whole.as_str()),
+                None => format!("{}{}", &caps[2], number_word_value(&caps[3])),
+            })
+            .to_string()
+    }
+
+    fn digits_to_words(&self, title: &str) -> String {
+        self.digits
+            .replace_all(title, |caps: &Captures| match caps[0].parse::<usize>() {
+                Ok(n) if n < 100 => number_to_words(n),
+                _ => caps[0].to_string(),
+            })
+            .to_string()
+    }
+
+    /// Title case (`every_word`) or sentence case. Lower-cases first, so ALL CAPS titles come out
+    /// right; roman numerals (I-XXXIX) and the pronoun "I" stay upper case.
+    fn recase(&self, title: &str, every_word: bool) -> String {
+        let mut out = String::with_capacity(title.len());
+        let mut last = 0;
+        for m in self.word.find_iter(title) {
+            let before = &title[last..m.start()];
+            out.push_str(before);
+            let starts_phrase = out.trim_end().is_empty() || out.trim_end().ends_with([':', '.', '!', '?']);
+            let word = m.as_str();
+            let lower = word.to_lowercase();
+            let recased = if is_roman_numeral(word) {
+                word.to_uppercase()
+            } else if starts_phrase || (every_word && !SMALL_WORDS.contains(&lower.as_str())) {
+                capitalize(&lower)
+            } else {
+                lower
+            };
+            out.push_str(&recased);
+            last = m.end();
+        }
+        out.push_str(&title[last..]);
+        out
+    }
+}
+
+/// 0-99 as English words ("twenty-one").
+pub fn number_to_words(n: usize) -> String {
+    if n < 20 {
+        return UNITS[n].to_string();
+    }
+    match n % 10 {
+        0 => TENS[n / 10].to_string(),
+        units => format!("{}-{}", TENS[n / 10], UNITS[units]),
+    }
+}
+
+/// Value of a matched number word ("twenty-one", "Seven").
+fn number_word_value(words: &str) -> String {
+    let position = |word: &str, list: &[&str]| list.iter().position(|l| l.eq_ignore_ascii_case(word));
+    let mut parts = words.split(['-', ' ']).filter(|w| !w.is_empty());
+    let first = parts.next().unwrap_or_default();
+    let value = match position(first, &TENS) {
+        Some(tens) => tens * 10 + parts.next().and_then(|u| position(u, &UNITS)).unwrap_or(0),
+        None => position(first, &UNITS).unwrap_or(0),
+    };
+    value.to_string()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// I to XXXIX, the range used for parts and chapters.
+fn is_roman_numeral(word: &str) -> bool {
+    let upper = word.to_uppercase();
+    if upper == "I" {
+        return true;
+    }
+    // A lone lower-case "v" or "x" is more likely a letter than a numeral
+    if !upper.chars().all(|c| matches!(c, 'I' | 'V' | 'X')) || (word != upper && upper.len() < 2) {
+        return false;
+    }
+    let tens = upper.chars().take_while(|c| *c == 'X').count();
+    let rest = &upper[tens..];
+    tens <= 3 && matches!(rest, "" | "I" | "II" | "III" | "IV" | "V" | "VI" | "VII" | "VIII" | "IX")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_digits(title: &str) -> String {
+        let rules = TitleRules { number_words: NumberWords::ToDigits, ..TitleRules::default() };
+        rules.compile().expect("valid rules").apply(title, 1)
+    }
+
+    fn apply(rules: TitleRules, title: &str) -> String {
+        rules.compile().expect("valid rules").apply(title, 1)
+    }
+
+    #[test]
+    fn number_words_after_a_label_become_digits() {
+        assert_eq!(to_digits("Chapter Twenty-One"), "Chapter 21");
+        assert_eq!(to_digits("CHAPTER twenty one"), "CHAPTER 21");
+        assert_eq!(to_digits("Part Fourteen: The Return"), "Part 14: The Return");
+        assert_eq!(to_digits("Book Three, Chapter Eight"), "Book 3, Chapter 8");
+        assert_eq!(to_digits("Track Ninety"), "Track 90");
+        assert_eq!(to_digits("Seventeen"), "17");
+    }
+
+    #[test]
+    fn number_words_in_running_text_are_kept() {
+        assert_eq!(to_digits("No One Else"), "No One Else");
+        assert_eq!(to_digits("The Three Musketeers"), "The Three Musketeers");
+        assert_eq!(to_digits("One Last Time"), "One Last Time");
+        assert_eq!(to_digits("Chapterhouse One"), "Chapterhouse One");
+    }
+
+    #[test]
+    fn numbering_uses_the_given_chapter_number() {
+        let rules = TitleRules { numbering: TitleNumbering::Prefix, ..TitleRules::default() };
+        assert_eq!(rules.compile().expect("valid rules").apply("03 - The Road", 7), "Chapter 7: The Road");
+    }
+
+    #[test]
+    fn strip_prefix_removes_one_leading_number() {
+        let strip = |title: &str| apply(TitleRules::default(), title);
+        assert_eq!(strip("01 - Track 01"), "Track 01");
+        assert_eq!(strip("Ch. 3: X"), "X");
+        assert_eq!(strip("Track 12 The Storm"), "The Storm");
+        // Nothing would be left, so the number stays
+        assert_eq!(strip("07"), "07");
+        assert_eq!(strip("Chapter 1"), "Chapter 1");
+        assert_eq!(strip("1984"), "1984");
+    }
+
+    #[test]
+    fn case_rules_recase_all_caps_titles() {
+        let title = |t: &str| apply(TitleRules { case: TitleCase::Title, ..TitleRules::default() }, t);
+        assert_eq!(title("THE LORD OF THE RINGS: THE RETURN OF THE KING"), "The Lord of the Rings: The Return of the King");
+        assert_eq!(title("A TALE OF TWO CITIES, PART IV"), "A Tale of Two Cities, Part IV");
+        assert_eq!(title("HENRY VIII AND THE KING'S MEN"), "Henry VIII and the King's Men");
+        assert_eq!(title("WHAT I SAW FROM THE ROAD"), "What I Saw from the Road");
+
+        let sentence = |t: &str| apply(TitleRules { case: TitleCase::Sentence, ..TitleRules::default() }, t);
+        assert_eq!(sentence("THE WAR OF THE WORLDS, BOOK XII"), "The war of the worlds, book XII");
+        assert_eq!(sentence("WHAT I SAW. THEN THE END"), "What I saw. Then the end");
+        assert_eq!(sentence("OF MICE AND MEN"), "Of mice and men");
+    }
+
+    #[test]
+    fn find_replace_expands_capture_groups() {
+        let rules = |find: &str, replace: &str| TitleRules {
+            strip_prefix: false,
+            find: find.to_string(),
+            replace: replace.to_string(),
+            ..TitleRules::default()
+        };
+        assert_eq!(apply(rules(r"\s*\(Unabridged\)", ""), "The Road (Unabridged)"), "The Road");
+        assert_eq!(apply(rules(r"^Part (\d+)$", "Book $1"), "Part 3"), "Book 3");
+        assert_eq!(apply(rules(r"^(.+?) - (.+)$", "$2: $1"), "Prologue - The Road"), "The Road: Prologue");
+        assert_eq!(apply(rules(r"(\d+)", "${1}st"), "Chapter 1"), "Chapter 1st");
+        assert!(rules("(unclosed", "").compile().is_err());
+    }
+}
@@ -0,0 +1,12 @@
+// Stable content hash
+// FNV-1a: the same bytes hash the same in every build, so it can name files on disk
+// (HTTP cache entries, extracted covers) across upgrades.
+
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
@@ -1,3 +1,5 @@
 pub mod time;
 pub mod format;
 pub mod chapter_file;
+pub mod chapter_titles;
+pub mod hash;